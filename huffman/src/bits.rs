@@ -0,0 +1,119 @@
+//! Packs variable-length codes into bytes and reads them back, most significant bit first.
+
+use std::io::{self, Write};
+
+/// Packs codes into bytes, writing each byte out as soon as it fills up.
+pub struct BitWriter<W: Write> {
+    inner: W,
+    /// The bits not yet written out, in the lowest `pending` bits.
+    acc: u64,
+    pending: u32,
+}
+
+impl<W: Write> BitWriter<W> {
+    pub fn new(inner: W) -> Self {
+        BitWriter {
+            inner,
+            acc: 0,
+            pending: 0,
+        }
+    }
+
+    /// Writes the lowest `len` bits of a code, most significant first.
+    pub fn write(&mut self, code: u64, len: u8) -> io::Result<()> {
+        let mut len = u32::from(len);
+        // fewer than 8 bits are ever pending, so 56 more always fit in the accumulator
+        while len > 0 {
+            let take = len.min(56);
+            len -= take;
+            let chunk = (code >> len) & ((1 << take) - 1);
+            self.acc = (self.acc << take) | chunk;
+            self.pending += take;
+
+            while self.pending >= 8 {
+                self.pending -= 8;
+                self.inner.write_all(&[(self.acc >> self.pending) as u8])?;
+            }
+            self.acc &= (1 << self.pending) - 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes out the last partial byte padded with zeros, and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.pending > 0 {
+            self.inner
+                .write_all(&[(self.acc << (8 - self.pending)) as u8])?;
+        }
+
+        Ok(self.inner)
+    }
+}
+
+/// Reads the first `len` bits of some bytes, most significant first.
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    len: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Creates a reader of `len` bits, which must fit in the data.
+    pub fn new(data: &'a [u8], len: usize) -> Self {
+        assert!(
+            len <= data.len() * 8,
+            "{} bits do not fit in {} bytes",
+            len,
+            data.len()
+        );
+
+        BitReader { data, pos: 0, len }
+    }
+}
+
+impl Iterator for BitReader<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.pos == self.len {
+            return None;
+        }
+
+        let bit = self.data[self.pos / 8] >> (7 - self.pos % 8) & 1 == 1;
+        self.pos += 1;
+
+        Some(bit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_writer() {
+        let mut writer = BitWriter::new(Vec::new());
+        writer.write(0b011, 3).unwrap();
+        writer.write(0b00100, 5).unwrap();
+        writer.write(0b1010_1001_0101, 12).unwrap();
+        writer.write(u64::MAX, 64).unwrap();
+
+        let mut expected = vec![0b0110_0100, 0b1010_1001, 0b0101_1111];
+        expected.extend_from_slice(&[0xFF; 7]);
+        expected.push(0b1111_0000);
+        assert_eq!(writer.finish().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_bit_reader() {
+        let bits: Vec<bool> = BitReader::new(&[0b0110_0100, 0b1000_0000], 9).collect();
+        let expected = [false, true, true, false, false, true, false, false, true];
+        assert_eq!(bits, expected);
+    }
+}
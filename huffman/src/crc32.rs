@@ -0,0 +1,50 @@
+//! Computes the CRC-32 checksum used by zip and PNG, to detect corrupted compressed files.
+
+/// The reversed polynomial of CRC-32.
+const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+/// The checksum of every byte value, computed once at compile time.
+const TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Computes the CRC-32 of the data.
+pub fn checksum(data: &[u8]) -> u32 {
+    update(0, data)
+}
+
+/// Extends the CRC-32 of the data seen so far with more data,
+/// so that data can be checksummed one chunk at a time.
+pub fn update(checksum: u32, data: &[u8]) -> u32 {
+    !data.iter().fold(!checksum, |crc, byte| {
+        TABLE[((crc ^ u32::from(*byte)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(b""), 0);
+        assert_eq!(checksum(b"123456789"), 0xCBF4_3926);
+        assert_eq!(update(checksum(b"1234"), b"56789"), 0xCBF4_3926);
+    }
+}
@@ -0,0 +1,91 @@
+//! The kinds of failure a run reports, each with an exit status for scripts to tell apart.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::mode::ParseModeError;
+
+/// The exit status of a run given invalid arguments.
+pub const EXIT_USAGE: i32 = 1;
+/// The exit status of a run that fails to read or write a file.
+pub const EXIT_IO: i32 = 2;
+/// The exit status of a run given input that is not valid compressed data.
+pub const EXIT_CORRUPT: i32 = 3;
+
+/// Invalid arguments, reported along with the usage.
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for UsageError {}
+
+/// Why compressed data could not be decoded, or a file could not be read or written.
+#[derive(Debug)]
+pub enum HuffmanError {
+    /// The data does not open with the signature of any format.
+    BadMagic,
+    /// The data opens with a format version other than the one written by this build.
+    UnsupportedVersion(u8),
+    /// The data ends partway through a header, or the lengths and names in it.
+    TruncatedHeader,
+    /// The data ends partway through a code tree.
+    TruncatedTree,
+    /// The payload holds a code that is not in the code tree.
+    InvalidCode,
+    /// The data ends before the payload does.
+    TruncatedPayload,
+    /// The decoded data does not match the checksum of the input.
+    ChecksumMismatch,
+    /// An archive names a file by a path rather than a bare file name, which could place it
+    /// outside the directory it is restored into.
+    InvalidName(String),
+    /// Reading or writing a file failed, along with what was being done.
+    Io(String, io::Error),
+}
+
+impl fmt::Display for HuffmanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HuffmanError::BadMagic => write!(f, "not a huffman-compressed file"),
+            HuffmanError::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version {}", version)
+            }
+            HuffmanError::TruncatedHeader => write!(f, "truncated header"),
+            HuffmanError::TruncatedTree => write!(f, "corrupted file: code tree is truncated"),
+            HuffmanError::InvalidCode => write!(f, "corrupted file: invalid code"),
+            HuffmanError::TruncatedPayload => write!(f, "corrupted file: payload is truncated"),
+            HuffmanError::ChecksumMismatch => write!(f, "corrupted file: checksum mismatch"),
+            HuffmanError::InvalidName(name) => {
+                write!(f, "corrupted file: invalid file name {:?}", name)
+            }
+            HuffmanError::Io(context, e) => write!(f, "{}: {}", context, e),
+        }
+    }
+}
+
+impl Error for HuffmanError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            HuffmanError::Io(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Picks the exit status of an error, taking any error not about the arguments or the input
+/// to be about reading or writing a file.
+pub fn exit_code(error: &(dyn Error + 'static)) -> i32 {
+    if error.is::<UsageError>() || error.is::<ParseModeError>() {
+        EXIT_USAGE
+    } else if let Some(HuffmanError::Io(..)) | None = error.downcast_ref::<HuffmanError>() {
+        EXIT_IO
+    } else {
+        EXIT_CORRUPT
+    }
+}
@@ -1,44 +1,35 @@
-use std::{cmp::Reverse, collections::{BinaryHeap, HashMap}};
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap}, io::{self, Write}};
+
+use crate::bits::{BitReader, BitWriter};
 
 trait HuffmanBaseNode: Eq + PartialEq + PartialOrd {
-    fn is_leaf(&self) -> bool;
-    fn weight(&self) -> u32;
+    fn weight(&self) -> u64;
 }
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
 struct HuffmanLeafNode {
-    element: char,
-    weight: u32,
+    element: u8,
+    weight: u64,
 }
 
 impl HuffmanLeafNode {
-    fn new(element: char, weight: u32) -> Self {
+    fn new(element: u8, weight: u64) -> Self {
         HuffmanLeafNode { element, weight }
     }
 
-    fn element(&self) -> char {
+    fn element(&self) -> u8 {
         self.element
     }
 }
 
 impl HuffmanBaseNode for HuffmanLeafNode {
-    fn weight(&self) -> u32 {
+    fn weight(&self) -> u64 {
         self.weight
     }
-    fn is_leaf(&self) -> bool {
-        true
-    }
 }
 
 impl HuffmanBaseNode for HuffmanNode {
-    fn is_leaf(&self) -> bool {
-        match self {
-            HuffmanNode::Leaf(_) => true,
-            HuffmanNode::Internal(_) => false,
-        }
-    }
-
-    fn weight(&self) -> u32 {
+    fn weight(&self) -> u64 {
         match self {
             HuffmanNode::Leaf(leaf) => leaf.weight(),
             HuffmanNode::Internal(internal) => internal.weight(),
@@ -48,13 +39,13 @@ impl HuffmanBaseNode for HuffmanNode {
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
 struct HuffmanInternalNode {
-    weight: u32,
+    weight: u64,
     left: HuffmanNode,
     right: HuffmanNode,
 }
 
 impl HuffmanInternalNode {
-    fn new(left: HuffmanNode, right: HuffmanNode, weight: u32) -> Self {
+    fn new(left: HuffmanNode, right: HuffmanNode, weight: u64) -> Self {
         HuffmanInternalNode {
             left,
             right,
@@ -72,12 +63,9 @@ impl HuffmanInternalNode {
 }
 
 impl HuffmanBaseNode for HuffmanInternalNode {
-    fn weight(&self) -> u32 {
+    fn weight(&self) -> u64 {
         self.weight
     }
-    fn is_leaf(&self) -> bool {
-        false
-    }
 }
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
@@ -96,19 +84,19 @@ impl HuffmanTree {
         HuffmanTree { root }
     }
 
-    fn merge(left: Box<HuffmanNode>, right: Box<HuffmanNode>, weight: u32) -> Self {
-        let root = HuffmanInternalNode::new(*left, *right, weight);
+    fn merge(left: HuffmanNode, right: HuffmanNode, weight: u64) -> Self {
+        let root = HuffmanInternalNode::new(left, right, weight);
         HuffmanTree::new(Box::new(HuffmanNode::Internal(Box::new(root))))
     }
 
     fn root(&self) -> &HuffmanNode {
-        &*self.root
+        &self.root
     }
 }
 
-type MinHeapTuple = Reverse<(u32, HuffmanTree)>;
+type MinHeapTuple = Reverse<(u64, HuffmanTree)>;
 
-fn build_tree(freq_map: &HashMap<char, u32>) -> HuffmanTree {
+fn build_tree(freq_map: &HashMap<u8, u64>) -> Option<HuffmanTree> {
     let mut heap = BinaryHeap::<MinHeapTuple>::new();
     for (c, freq) in freq_map {
         let leaf = HuffmanLeafNode::new(*c, *freq);
@@ -119,74 +107,562 @@ fn build_tree(freq_map: &HashMap<char, u32>) -> HuffmanTree {
     build_tree_from_heap(heap)
 }
 
-fn build_tree_from_heap(mut heap: BinaryHeap<MinHeapTuple>) -> HuffmanTree {
+/// Merges the trees of a heap into one, or yields `None` for an empty heap.
+fn build_tree_from_heap(mut heap: BinaryHeap<MinHeapTuple>) -> Option<HuffmanTree> {
     while heap.len() > 1 {
         let left = heap.pop().unwrap();
         let right = heap.pop().unwrap();
 
         let root = HuffmanTree::merge(
-            Box::new(left.0.1.root().clone()),
-            Box::new(right.0.1.root().clone()),
+            left.0.1.root().clone(),
+            right.0.1.root().clone(),
             left.0.0 + right.0.0,
         );
 
         heap.push(Reverse((root.root().weight(), root)));
     }
 
-    heap.pop().unwrap().0.1
+    heap.pop().map(|tree| tree.0.1)
 }
 
-pub fn build_code_lookup(freq_map: &HashMap<char, u32>) -> HashMap<char, String> {
+/// The bits of a code, right-aligned, along with their number.
+///
+/// Codes fit in 64 bits, since a Huffman tree gets deeper only as fast as the Fibonacci numbers
+/// grow, so a deeper tree than that needs more bytes than any input can hold.
+pub type Code = (u64, u8);
+
+/// Builds the canonical codes of the symbols of a frequency map,
+/// with the code lengths of its Huffman tree.
+pub fn build_code_lookup(freq_map: &HashMap<u8, u64>) -> HashMap<u8, Code> {
+    let mut code_lengths = HashMap::new();
+    // empty input has no symbols to code
+    if let Some(huffman_tree) = build_tree(freq_map) {
+        // a lone symbol sits at the root, but still needs a bit to be counted in the payload
+        let depth = match huffman_tree.root() {
+            HuffmanNode::Leaf(_) => 1,
+            HuffmanNode::Internal(_) => 0,
+        };
+        build_code_lengths_recursive(&mut code_lengths, huffman_tree.root(), depth);
+    }
+
+    canonical_code_lookup(&code_lengths)
+}
+
+/// Assigns canonical codes to symbols from their code lengths alone.
+///
+/// Symbols are ordered by code length, then by value, and each takes the code following
+/// the previous one, widened with zeros to its own length. Since the codes depend only on
+/// the lengths, the decoder can rebuild them from the lengths stored in the header.
+pub fn canonical_code_lookup(code_lengths: &HashMap<u8, u8>) -> HashMap<u8, Code> {
+    let mut symbols: Vec<(u8, u8)> = code_lengths.iter().map(|(c, len)| (*len, *c)).collect();
+    symbols.sort_unstable();
+
     let mut code_lookup = HashMap::new();
-    let huffman_tree = build_tree(freq_map);
-    let mut code = String::new();
-    build_code_lookup_recursive(&mut code_lookup, huffman_tree.root(), &mut code);
+    let mut previous: Option<Code> = None;
+    for (len, c) in symbols {
+        let code = match previous {
+            Some((code, previous_len)) => (code + 1) << (len - previous_len),
+            None => 0,
+        };
+
+        code_lookup.insert(c, (code, len));
+        previous = Some((code, len));
+    }
 
     code_lookup
 }
 
-fn build_code_lookup_recursive(
-    code_lookup: &mut HashMap<char, String>,
+/// Records the depth of each leaf below a node, which is the length of its code.
+fn build_code_lengths_recursive(
+    code_lengths: &mut HashMap<u8, u8>,
     node: &HuffmanNode,
-    code: &mut String,
+    depth: u8,
 ) {
     match node {
         HuffmanNode::Leaf(leaf) => {
-            code_lookup.insert(leaf.element(), code.to_string());
+            code_lengths.insert(leaf.element(), depth);
         },
         HuffmanNode::Internal(internal) => {
-            code.push('0');
-            build_code_lookup_recursive(code_lookup, internal.left(), code);
-            code.pop();
-
-            code.push('1');
-            build_code_lookup_recursive(code_lookup, internal.right(), code);
-            code.pop();
+            // a tree of at most 256 leaves is at most 255 levels deep
+            build_code_lengths_recursive(code_lengths, internal.left(), depth + 1);
+            build_code_lengths_recursive(code_lengths, internal.right(), depth + 1);
         },
     }
 }
 
+/// A tree rebuilt from a code lookup, which decodes each symbol by following its bits
+/// from the root down to a leaf.
+pub struct Decoder {
+    /// The children of each node, with the root first.
+    children: Vec<[Option<usize>; 2]>,
+    /// The symbol of each node, present only at the leaves.
+    symbols: Vec<Option<u8>>,
+}
+
+impl Decoder {
+    pub fn new(code_lookup: &HashMap<u8, Code>) -> Self {
+        let mut decoder = Decoder {
+            children: vec![[None, None]],
+            symbols: vec![None],
+        };
+
+        for (c, (code, len)) in code_lookup {
+            let mut node = 0;
+            for i in (0..*len).rev() {
+                let branch = usize::from(code >> i & 1 == 1);
+                node = match decoder.children[node][branch] {
+                    Some(child) => child,
+                    None => {
+                        decoder.children.push([None, None]);
+                        decoder.symbols.push(None);
+                        let child = decoder.children.len() - 1;
+                        decoder.children[node][branch] = Some(child);
+                        child
+                    }
+                };
+            }
+            decoder.symbols[node] = Some(*c);
+        }
+
+        decoder
+    }
+
+    /// Decodes a sequence of bits, ignoring a trailing incomplete code.
+    ///
+    /// Returns `None` if the bits take a branch that is not in the tree.
+    pub fn decode(&self, bits: impl IntoIterator<Item = bool>) -> Option<Vec<u8>> {
+        let mut decoded_data = Vec::new();
+        let mut node = 0;
+        for bit in bits {
+            let child = self.children[node][usize::from(bit)]?;
+
+            node = match self.symbols[child] {
+                Some(byte) => {
+                    decoded_data.push(byte);
+                    0
+                }
+                None => child,
+            };
+        }
+
+        Some(decoded_data)
+    }
+}
+
+impl Decoder {
+    /// Writes the shape of the tree in pre-order, as `0` for an internal node followed by
+    /// its two children, and `1` for a leaf followed by the 8 bits of its symbol.
+    ///
+    /// A lone symbol is written as a leaf at the root, and no symbols at all as no bits.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut writer = BitWriter::new(Vec::new());
+        match self.children[0] {
+            [None, None] => {}
+            [Some(leaf), None] => self.serialize_recursive(&mut writer, leaf),
+            _ => self.serialize_recursive(&mut writer, 0),
+        }
+
+        writer.finish().expect("writing to memory cannot fail")
+    }
+
+    fn serialize_recursive(&self, writer: &mut BitWriter<Vec<u8>>, node: usize) {
+        const INFALLIBLE: &str = "writing to memory cannot fail";
+        match (self.symbols[node], self.children[node]) {
+            (Some(c), _) => writer.write(0x100 | u64::from(c), 9).expect(INFALLIBLE),
+            (None, [Some(left), Some(right)]) => {
+                writer.write(0, 1).expect(INFALLIBLE);
+                self.serialize_recursive(writer, left);
+                self.serialize_recursive(writer, right);
+            }
+            (None, _) => unreachable!("internal nodes below the root have two children"),
+        }
+    }
+
+    /// Rebuilds a tree from the bits written by `serialize`, ignoring the padding after them.
+    ///
+    /// Returns `None` if the bits run out before the tree is complete.
+    pub fn deserialize(data: &[u8]) -> Option<Self> {
+        let mut bits = BitReader::new(data, data.len() * 8);
+        let mut decoder = Decoder {
+            children: vec![[None, None]],
+            symbols: vec![None],
+        };
+
+        match bits.next() {
+            None => return Some(decoder),
+            // a lone symbol takes the code `0`, as in `build_code_lookup`
+            Some(true) => {
+                let leaf = decoder.push(read_symbol(&mut bits)?);
+                decoder.children[0][0] = Some(leaf);
+                return Some(decoder);
+            }
+            Some(false) => {}
+        }
+
+        // the internal nodes still missing a child, filled left to right as they are read
+        let mut open = vec![0];
+        while let Some(&parent) = open.last() {
+            let branch = usize::from(decoder.children[parent][0].is_some());
+            let child = if bits.next()? {
+                decoder.push(read_symbol(&mut bits)?)
+            } else {
+                decoder.push(None)
+            };
+
+            decoder.children[parent][branch] = Some(child);
+            if branch == 1 {
+                open.pop();
+            }
+            if decoder.symbols[child].is_none() {
+                open.push(child);
+            }
+        }
+
+        Some(decoder)
+    }
+
+    /// Adds a node without children, returning its index.
+    fn push(&mut self, symbol: Option<u8>) -> usize {
+        self.children.push([None, None]);
+        self.symbols.push(symbol);
+
+        self.children.len() - 1
+    }
+
+    /// Lists the code of each symbol, following the path from the root to its leaf.
+    #[cfg(test)]
+    pub fn code_lookup(&self) -> HashMap<u8, Code> {
+        let mut code_lookup = HashMap::new();
+        let mut nodes = vec![(0, (0, 0))];
+        while let Some((node, code)) = nodes.pop() {
+            if let Some(c) = self.symbols[node] {
+                code_lookup.insert(c, code);
+                continue;
+            }
+
+            for (branch, child) in self.children[node].iter().enumerate() {
+                if let Some(child) = child {
+                    nodes.push((*child, ((code.0 << 1) | branch as u64, code.1 + 1)));
+                }
+            }
+        }
+
+        code_lookup
+    }
+}
+
+/// The symbol coded after the last byte of an adaptive stream, which has no length up front.
+pub const END_OF_STREAM: u16 = 256;
+
+/// The bits of a symbol sent along with the code of the not-yet-transmitted node,
+/// enough for every byte and `END_OF_STREAM`.
+const SYMBOL_BITS: u8 = 9;
+
+/// A node of an adaptive tree, kept in an arena like the nodes of `Decoder`.
+struct AdaptiveNode {
+    weight: u64,
+    parent: Option<usize>,
+    /// The left and right children of an internal node, or `None` for a leaf.
+    children: Option<[usize; 2]>,
+}
+
+/// A Huffman tree that adapts to the symbols as they are coded, with the FGK algorithm,
+/// so that the input can be coded in a single pass, and the decoder can follow along
+/// without a code tree being stored.
+///
+/// It starts as a lone not-yet-transmitted (NYT) leaf. The first time a symbol is seen,
+/// the code of the NYT leaf is sent, followed by the 9 bits of the symbol, and the NYT leaf
+/// splits into a new NYT leaf and a leaf for the symbol. Later on, the symbol is sent as
+/// the code of its leaf. Either way the weights on the path to the root are then incremented,
+/// swapping each node with the first node of the same weight, so that nodes stay ordered
+/// by weight and the tree stays a Huffman tree.
+pub struct AdaptiveCoder {
+    nodes: Vec<AdaptiveNode>,
+    /// The nodes by decreasing weight, starting with the root, with siblings side by side.
+    order: Vec<usize>,
+    /// The position of each node in `order`.
+    rank: Vec<usize>,
+    /// The leaf of each symbol seen so far.
+    leaves: HashMap<u16, usize>,
+    nyt: usize,
+}
+
+impl AdaptiveCoder {
+    pub fn new() -> Self {
+        AdaptiveCoder {
+            nodes: vec![AdaptiveNode {
+                weight: 0,
+                parent: None,
+                children: None,
+            }],
+            order: vec![0],
+            rank: vec![0],
+            leaves: HashMap::new(),
+            nyt: 0,
+        }
+    }
+
+    /// Writes the code of a symbol, then updates the tree for it.
+    pub fn encode<W: Write>(&mut self, symbol: u16, writer: &mut BitWriter<W>) -> io::Result<()> {
+        let (node, new) = match self.leaves.get(&symbol) {
+            Some(&leaf) => (leaf, false),
+            None => (self.nyt, true),
+        };
+
+        let mut code = Vec::new();
+        let mut child = node;
+        while let Some(parent) = self.nodes[child].parent {
+            let children = self.nodes[parent].children.expect("parents have children");
+            code.push(children[1] == child);
+            child = parent;
+        }
+        for bit in code.into_iter().rev() {
+            writer.write(u64::from(bit), 1)?;
+        }
+        if new {
+            writer.write(u64::from(symbol), SYMBOL_BITS)?;
+        }
+
+        self.update(symbol);
+        Ok(())
+    }
+
+    /// Reads the code of a symbol, then updates the tree for it.
+    ///
+    /// Returns `None` if the bits run out before the symbol is complete.
+    pub fn decode(&mut self, bits: &mut impl Iterator<Item = bool>) -> Option<u16> {
+        let mut node = 0;
+        while let Some(children) = self.nodes[node].children {
+            node = children[usize::from(bits.next()?)];
+        }
+
+        let symbol = if node == self.nyt {
+            let mut symbol = 0;
+            for _ in 0..SYMBOL_BITS {
+                symbol = (symbol << 1) | u16::from(bits.next()?);
+            }
+            symbol
+        } else {
+            *self
+                .leaves
+                .iter()
+                .find(|(_, leaf)| **leaf == node)
+                .expect("every leaf but the NYT leaf has a symbol")
+                .0
+        };
+
+        self.update(symbol);
+        Some(symbol)
+    }
+
+    /// Counts one more occurrence of a symbol, adding a leaf for it if it is new.
+    fn update(&mut self, symbol: u16) {
+        let mut node = match self.leaves.get(&symbol) {
+            Some(&leaf) => leaf,
+            None => self.split_nyt(symbol),
+        };
+
+        loop {
+            // the first node of the same weight, which is never further up than the parent
+            let weight = self.nodes[node].weight;
+            let mut first = self.rank[node];
+            while first > 0 && self.nodes[self.order[first - 1]].weight == weight {
+                first -= 1;
+            }
+            let first = self.order[first];
+            if first != node && Some(first) != self.nodes[node].parent {
+                self.swap(node, first);
+            }
+
+            self.nodes[node].weight += 1;
+            match self.nodes[node].parent {
+                Some(parent) => node = parent,
+                None => break,
+            }
+        }
+    }
+
+    /// Turns the NYT leaf into the parent of a new NYT leaf and a leaf for a symbol,
+    /// returning the leaf of the symbol.
+    fn split_nyt(&mut self, symbol: u16) -> usize {
+        let parent = self.nyt;
+        let push = |nodes: &mut Vec<AdaptiveNode>| {
+            nodes.push(AdaptiveNode {
+                weight: 0,
+                parent: Some(parent),
+                children: None,
+            });
+            nodes.len() - 1
+        };
+        let nyt = push(&mut self.nodes);
+        let leaf = push(&mut self.nodes);
+        self.nodes[parent].children = Some([nyt, leaf]);
+
+        // both come last in the order, with the NYT leaf always at the very end
+        self.order.extend([leaf, nyt]);
+        let last = self.order.len() - 1;
+        self.rank.extend([last, last - 1]);
+        self.leaves.insert(symbol, leaf);
+        self.nyt = nyt;
+
+        leaf
+    }
+
+    /// Swaps two nodes along with their subtrees, and their places in the order.
+    fn swap(&mut self, a: usize, b: usize) {
+        let parent_a = self.nodes[a].parent.expect("the root is never swapped");
+        let parent_b = self.nodes[b].parent.expect("the root is never swapped");
+        let slot = |nodes: &[AdaptiveNode], parent: usize, child: usize| {
+            let children = nodes[parent].children.expect("parents have children");
+            usize::from(children[1] == child)
+        };
+        let slot_a = slot(&self.nodes, parent_a, a);
+        let slot_b = slot(&self.nodes, parent_b, b);
+
+        self.nodes[parent_a].children.as_mut().expect("parents have children")[slot_a] = b;
+        self.nodes[parent_b].children.as_mut().expect("parents have children")[slot_b] = a;
+        self.nodes[a].parent = Some(parent_b);
+        self.nodes[b].parent = Some(parent_a);
+
+        self.order.swap(self.rank[a], self.rank[b]);
+        self.rank.swap(a, b);
+    }
+}
+
+/// Reads the 8 bits of a symbol, or `None` if fewer are left.
+fn read_symbol(bits: &mut BitReader) -> Option<Option<u8>> {
+    let mut symbol = 0;
+    for _ in 0..8 {
+        symbol = (symbol << 1) | u8::from(bits.next()?);
+    }
+
+    Some(Some(symbol))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_build_code_lookup_single_symbol() {
+        let mut freq_map = HashMap::new();
+        freq_map.insert(b'a', 6);
+
+        let code_lookup = build_code_lookup(&freq_map);
+        assert_eq!(code_lookup.get(&b'a'), Some(&(0b0, 1)));
+    }
+
+    #[test]
+    fn test_build_code_lookup_empty() {
+        assert!(build_code_lookup(&HashMap::new()).is_empty());
+    }
+
     #[test]
     fn test_build_code_lookup() {
         let mut freq_map = HashMap::new();
-        freq_map.insert('a', 4);
-        freq_map.insert('b', 4);
-        freq_map.insert('c', 2);
-        freq_map.insert('d', 2);
-        freq_map.insert('e', 3);
-        freq_map.insert('f', 1);
+        freq_map.insert(b'a', 4);
+        freq_map.insert(b'b', 4);
+        freq_map.insert(b'c', 2);
+        freq_map.insert(b'd', 2);
+        freq_map.insert(b'e', 3);
+        freq_map.insert(b'f', 1);
+
+        let code_lookup = build_code_lookup(&freq_map);
+
+        assert_eq!(code_lookup.get(&b'a'), Some(&(0b00, 2)));
+        assert_eq!(code_lookup.get(&b'b'), Some(&(0b01, 2)));
+        assert_eq!(code_lookup.get(&b'c'), Some(&(0b100, 3)));
+        assert_eq!(code_lookup.get(&b'd'), Some(&(0b101, 3)));
+        assert_eq!(code_lookup.get(&b'e'), Some(&(0b110, 3)));
+        assert_eq!(code_lookup.get(&b'f'), Some(&(0b111, 3)));
+    }
+
+    #[test]
+    fn test_canonical_code_lookup() {
+        let mut code_lengths = HashMap::new();
+        code_lengths.insert(b'x', 3);
+        code_lengths.insert(b'a', 1);
+        code_lengths.insert(b'c', 3);
+        code_lengths.insert(b'b', 2);
+
+        let code_lookup = canonical_code_lookup(&code_lengths);
+
+        assert_eq!(code_lookup.get(&b'a'), Some(&(0b0, 1)));
+        assert_eq!(code_lookup.get(&b'b'), Some(&(0b10, 2)));
+        assert_eq!(code_lookup.get(&b'c'), Some(&(0b110, 3)));
+        assert_eq!(code_lookup.get(&b'x'), Some(&(0b111, 3)));
+    }
 
+    #[test]
+    fn test_serialize_tree_round_trip() {
+        let mut freq_map = HashMap::new();
+        for (i, c) in b"the quick brown fox jumps over the lazy dog".iter().enumerate() {
+            *freq_map.entry(*c).or_insert(0) += i as u64 % 7 + 1;
+        }
         let code_lookup = build_code_lookup(&freq_map);
 
-        assert_eq!(code_lookup.get(&'a').unwrap(), "01");
-        assert_eq!(code_lookup.get(&'b').unwrap(), "10");
-        assert_eq!(code_lookup.get(&'c').unwrap(), "001");
-        assert_eq!(code_lookup.get(&'d').unwrap(), "110");
-        assert_eq!(code_lookup.get(&'e').unwrap(), "111");
-        assert_eq!(code_lookup.get(&'f').unwrap(), "000");
+        let tree = Decoder::new(&code_lookup).serialize();
+        let decoder = Decoder::deserialize(&tree).unwrap();
+        assert_eq!(decoder.code_lookup(), code_lookup);
+        // one bit per internal node, and nine per leaf
+        assert_eq!(tree.len(), (10 * code_lookup.len() - 1).div_ceil(8));
+    }
+
+    #[test]
+    fn test_serialize_tree_small() {
+        for symbols in [&b""[..], b"a", b"ab"] {
+            let freq_map = symbols.iter().map(|c| (*c, 1)).collect();
+            let code_lookup = build_code_lookup(&freq_map);
+
+            let tree = Decoder::new(&code_lookup).serialize();
+            assert_eq!(Decoder::deserialize(&tree).unwrap().code_lookup(), code_lookup);
+        }
+
+        // a lone leaf, then an internal node with one leaf and a missing second child
+        let code_lookup = build_code_lookup(&HashMap::from([(b'a', 3)]));
+        assert_eq!(Decoder::new(&code_lookup).serialize(), [0b1011_0000, 0b1000_0000]);
+        assert!(Decoder::deserialize(&[0b0101_1000, 0b0100_0000]).is_none());
+    }
+
+    /// Codes some symbols adaptively, ended with `END_OF_STREAM`.
+    fn encode_adaptive(symbols: &[u16]) -> Vec<u8> {
+        let mut coder = AdaptiveCoder::new();
+        let mut writer = BitWriter::new(Vec::new());
+        for symbol in symbols.iter().chain([&END_OF_STREAM]) {
+            coder.encode(*symbol, &mut writer).unwrap();
+        }
+
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_adaptive_round_trip() {
+        let data = b"abracadabra, the quick brown fox jumps over the lazy dog";
+        let symbols: Vec<u16> = data.iter().map(|c| u16::from(*c)).collect();
+        let bytes = encode_adaptive(&symbols);
+
+        let mut coder = AdaptiveCoder::new();
+        let mut bits = BitReader::new(&bytes, bytes.len() * 8);
+        let mut decoded = Vec::new();
+        loop {
+            match coder.decode(&mut bits).unwrap() {
+                END_OF_STREAM => break,
+                symbol => decoded.push(symbol as u8),
+            }
+        }
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_adaptive_codes_adapt() {
+        // the first symbol is sent whole, and once it dominates it takes a single bit
+        let bytes = encode_adaptive(&[u16::from(b'a'); 64]);
+        assert_eq!(bytes[..2], [0b0011_0000, 0b1111_1111]);
+        // 9 bits for `a`, 63 bits for its repeats, then 1 bit for the NYT leaf and 9 for the end
+        assert_eq!(bytes.len(), (9 + 63 + 1 + 9usize).div_ceil(8));
+
+        // a skewed input codes to fewer bits than its symbols would take at a fixed width
+        let skewed: Vec<u16> = (0..1_000).map(|i| if i % 10 == 0 { i % 7 } else { 42 }).collect();
+        assert!(encode_adaptive(&skewed).len() * 8 < skewed.len() * 3);
     }
 }
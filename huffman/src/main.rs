@@ -2,101 +2,584 @@ use std::collections::HashMap;
 use std::env;
 use std::error::Error;
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::process;
+use std::time::Instant;
 
 mod mode;
 use mode::Mode;
 
+mod bits;
+use bits::{BitReader, BitWriter};
+
+mod crc32;
+
+mod error;
+use error::{HuffmanError, UsageError};
+
 mod huffman;
 
-// TODO: use string builders instead of strings
+mod stats;
+use stats::Stats;
+
+/// The signature opening every file written by `compress`.
+const MAGIC: &[u8; 4] = b"HUFF";
+/// The signature opening every archive written by `compress_multi`.
+const MAGIC_MULTI: &[u8; 4] = b"HUFA";
+/// The signature opening every file written by `compress_blocks`.
+const MAGIC_BLOCKS: &[u8; 4] = b"HUFB";
+/// The signature opening every file written by `compress_adaptive`.
+const MAGIC_ADAPTIVE: &[u8; 4] = b"HUFD";
+/// The version of the compressed format, written right after the signature.
+const VERSION: u8 = 5;
+
+/// The number of input bytes coded with each code tree by `compress_blocks`, unless given.
+const DEFAULT_BLOCK_SIZE: usize = 128 * 1024;
+
+/// The file name standing for stdin as input, or stdout as output.
+const STDIO: &str = "-";
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    let mode: Mode;
-    let in_file_name: &str;
-    let out_file_name: &str;
+const USAGE: &str = "\
+usage: huffman <c|ca|cb|d> [--block-size=BYTES] [in] [out]
+       huffman t [in]
+       huffman c <archive> <in> <in>...
+       huffman d <archive> [dir]";
 
-    match args.len() {
-        4 => {
-            mode = args[1].parse()?;
-            in_file_name = &args[2];
-            out_file_name = &args[3];
+/// The name of an input file along with its contents.
+type NamedFile = (String, Vec<u8>);
+
+/// Runs the CLI, reporting a failure on stderr with an exit status telling its kind,
+/// as listed in the `error` module.
+fn main() {
+    if let Err(e) = try_main() {
+        eprintln!("huffman: {}", e);
+        let code = error::exit_code(e.as_ref());
+        if code == error::EXIT_USAGE {
+            eprintln!("{}", USAGE);
         }
-        _ => {
-            return Err("invalid arguments".into());
+        process::exit(code);
+    }
+}
+
+fn try_main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = env::args().collect();
+    let json_stats = take_flag(&mut args, &["--json-stats"]);
+    let verbose = take_flag(&mut args, &["-v", "--verbose"]);
+    let block_size = take_option(&mut args, "--block-size");
+    // nothing is opened until the arguments are known to be valid
+    let mut mode = parse_mode(&args)?;
+    if let Some(block_size) = block_size {
+        let Mode::CompressBlocks(size) = &mut mode else {
+            return Err(UsageError("--block-size only applies to cb".to_string()).into());
+        };
+        *size = parse_block_size(&block_size)?;
+    }
+    match (&mode, args.len()) {
+        // several inputs are bundled into an archive, named first
+        (Mode::Compress, 5..) => run_compress_multi(&args[2], &args[3..]),
+        // an archive is restored into a directory rather than a file
+        (Mode::Decompress, 3 | 4) if is_archive(&args[2]) => {
+            run_decompress_multi(&args[2], args.get(3).map_or(".", String::as_str))
+        }
+        // omitted file names stand for stdin and stdout
+        (
+            Mode::Compress | Mode::CompressAdaptive | Mode::CompressBlocks(_) | Mode::Decompress,
+            2..=4,
+        ) => {
+            let in_file_name = args.get(2).map_or(STDIO, String::as_str);
+            let out_file_name = args.get(3).map_or(STDIO, String::as_str);
+            let stats = run(&mode, in_file_name, out_file_name)?;
+            if json_stats {
+                eprintln!("{}", stats.to_json());
+            }
+            if verbose && !matches!(mode, Mode::Decompress) {
+                eprintln!("{}", stats.to_summary());
+            }
+            Ok(())
         }
+        // nothing is written, so only the input is named
+        (Mode::Test, 2 | 3) => {
+            let in_file_name = args.get(2).map_or(STDIO, String::as_str);
+            run_test(in_file_name, io::stdin().lock())?;
+            println!("{}: OK", in_file_name);
+            Ok(())
+        }
+        _ => Err(UsageError("invalid arguments".to_string()).into()),
     }
+}
 
-    let mut file_in = File::open(in_file_name).expect("unable to open file");
-    let mut file_out = File::create(out_file_name)?;
-    let data_out: Vec<u8>;
+/// Parses the mode from the first argument after the program name.
+fn parse_mode(args: &[String]) -> Result<Mode, Box<dyn Error>> {
+    let mode = args.get(1).ok_or_else(|| UsageError("missing mode".to_string()))?;
 
-    println!("{} -> {}", in_file_name, out_file_name);
-    match mode {
+    Ok(mode.parse()?)
+}
+
+/// Removes every occurrence of a flag from the arguments, reporting whether it was given.
+fn take_flag(args: &mut Vec<String>, names: &[&str]) -> bool {
+    let len = args.len();
+    args.retain(|arg| !names.contains(&arg.as_str()));
+
+    args.len() < len
+}
+
+/// Removes every occurrence of an option given as `name=value`, returning the last value given.
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let mut value = None;
+    args.retain(|arg| match arg.strip_prefix(name).and_then(|arg| arg.strip_prefix('=')) {
+        Some(arg) => {
+            value = Some(arg.to_string());
+            false
+        }
+        None => true,
+    });
+
+    value
+}
+
+/// Parses a block size, which must be a positive number of bytes.
+fn parse_block_size(value: &str) -> Result<usize, Box<dyn Error>> {
+    match value.parse() {
+        Ok(0) | Err(_) => Err(UsageError(format!("invalid block size '{}'", value)).into()),
+        Ok(block_size) => Ok(block_size),
+    }
+}
+
+/// Compresses or decompresses one file into another, returning the stats of the run.
+///
+/// Either file name may be `-` to read from stdin or write to stdout.
+fn run(mode: &Mode, in_file_name: &str, out_file_name: &str) -> Result<Stats, Box<dyn Error>> {
+    run_with(mode, in_file_name, out_file_name, io::stdin().lock(), io::stdout().lock())
+}
+
+/// Runs like `run`, with the given streams standing in for stdin and stdout.
+fn run_with(
+    mode: &Mode,
+    in_file_name: &str,
+    out_file_name: &str,
+    stdin: impl Read,
+    stdout: impl Write,
+) -> Result<Stats, Box<dyn Error>> {
+    // fail on a missing input before creating the output
+    let input: Box<dyn Read + '_> = if in_file_name == STDIO {
+        Box::new(stdin)
+    } else {
+        let file = File::open(in_file_name)
+            .map_err(|e| HuffmanError::Io(format!("unable to read {}", in_file_name), e))?;
+        Box::new(BufReader::new(file))
+    };
+    let output: Box<dyn Write + '_> = if out_file_name == STDIO {
+        Box::new(stdout)
+    } else {
+        // stdout carries nothing but the data when it is the output
+        println!("{} -> {}", in_file_name, out_file_name);
+        Box::new(create_file(out_file_name)?)
+    };
+    let mut output = CountingWriter::new(BufWriter::new(output));
+
+    let started = Instant::now();
+    let (input_bytes, freq_map) = match mode {
         Mode::Compress => {
-            let mut data_in = String::new();
-            file_in
-                .read_to_string(&mut data_in)
-                .expect("unable to read file");
+            let freq_map = if in_file_name == STDIO {
+                // stdin cannot be read twice, so it is held in memory instead
+                let data = read_all(input, in_file_name)?;
+                compress_stream(|| Ok(&data[..]), &mut output)
+            } else {
+                let mut input = Some(input);
+                let open = || match input.take() {
+                    Some(input) => Ok(input),
+                    None => File::open(in_file_name)
+                        .map(|file| Box::new(BufReader::new(file)) as Box<dyn Read>),
+                };
+                compress_stream(open, &mut output)
+            };
+            let freq_map = freq_map
+                .and_then(|freq_map| output.flush().map(|()| freq_map))
+                .map_err(|e| HuffmanError::Io(format!("unable to compress {}", in_file_name), e))?;
+
+            (freq_map.values().map(|freq| *freq as usize).sum(), freq_map)
+        }
+        Mode::CompressBlocks(block_size) => {
+            let freq_map = compress_blocks_stream(input, *block_size, &mut output)
+                .and_then(|freq_map| output.flush().map(|()| freq_map))
+                .map_err(|e| HuffmanError::Io(format!("unable to compress {}", in_file_name), e))?;
 
-            data_out = compress(&data_in);
+            (freq_map.values().map(|freq| *freq as usize).sum(), freq_map)
         }
-        Mode::Decompress => {
-            let mut data_in = Vec::<u8>::new();
-            file_in
-                .read_to_end(&mut data_in)
-                .expect("unable to read file");
+        Mode::CompressAdaptive => {
+            let freq_map = compress_adaptive_stream(input, &mut output)
+                .and_then(|freq_map| output.flush().map(|()| freq_map))
+                .map_err(|e| HuffmanError::Io(format!("unable to compress {}", in_file_name), e))?;
 
-            data_out = decompress(&data_in);
+            (freq_map.values().map(|freq| *freq as usize).sum(), freq_map)
         }
+        _ => {
+            let data_in = read_all(input, in_file_name)?;
+            let data_out = decompress(&data_in)?;
+            output
+                .write_all(&data_out)
+                .and_then(|()| output.flush())
+                .map_err(|e| HuffmanError::Io(format!("unable to write {}", out_file_name), e))?;
+
+            (data_in.len(), create_freq_map(&data_out))
+        }
+    };
+    let elapsed = started.elapsed();
+
+    Ok(Stats::new(&freq_map, input_bytes, output.count, elapsed))
+}
+
+/// Counts the bytes written through to another writer.
+struct CountingWriter<W> {
+    inner: W,
+    count: usize,
+}
+
+impl<W: Write> CountingWriter<W> {
+    fn new(inner: W) -> Self {
+        CountingWriter { inner, count: 0 }
     }
+}
 
-    file_out.write_all(&data_out)?;
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.count += len;
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Decompresses a file or archive without writing the output anywhere,
+/// to check that it is intact.
+///
+/// The file name may be `-` to read from the given stand-in for stdin.
+fn run_test(in_file_name: &str, stdin: impl Read) -> Result<(), Box<dyn Error>> {
+    let data_in = if in_file_name == STDIO {
+        read_all(stdin, in_file_name)?
+    } else {
+        read_file(in_file_name)?
+    };
+
+    // the decompressed data is dropped as soon as it has been checked
+    if data_in.starts_with(MAGIC_MULTI) {
+        decompress_multi(&data_in)?;
+    } else {
+        decompress(&data_in)?;
+    }
 
     Ok(())
 }
 
-fn compress(data: &str) -> Vec<u8> {
-    let freq_map = create_freq_map(data);
+/// Compresses several files into one archive sharing a single code table.
+fn run_compress_multi(out_file_name: &str, in_file_names: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut files = Vec::new();
+    for in_file_name in in_file_names {
+        let name = Path::new(in_file_name)
+            .file_name()
+            .ok_or("invalid file name")?
+            .to_string_lossy()
+            .to_string();
+        files.push((name, read_file(in_file_name)?));
+        println!("{} -> {}", in_file_name, out_file_name);
+    }
+
+    let compressed = compress_multi(&files)
+        .map_err(|e| HuffmanError::Io(format!("unable to compress {}", out_file_name), e))?;
+    create_file(out_file_name)?
+        .write_all(&compressed)
+        .map_err(|e| HuffmanError::Io(format!("unable to write {}", out_file_name), e))?;
+
+    Ok(())
+}
+
+/// Restores every file of a shared-table archive into a directory.
+fn run_decompress_multi(in_file_name: &str, out_dir: &str) -> Result<(), Box<dyn Error>> {
+    let data_in = read_file(in_file_name)?;
+
+    for (name, data) in decompress_multi(&data_in)? {
+        let out_file_name = Path::new(out_dir).join(&name).to_string_lossy().to_string();
+        println!("{} -> {}", in_file_name, out_file_name);
+        create_file(&out_file_name)?
+            .write_all(&data)
+            .map_err(|e| HuffmanError::Io(format!("unable to write {}", out_file_name), e))?;
+    }
+
+    Ok(())
+}
+
+/// Checks whether a file opens with the signature of an archive written by `compress_multi`.
+fn is_archive(file_name: &str) -> bool {
+    let mut magic = [0; 4];
+    File::open(file_name)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|()| &magic == MAGIC_MULTI)
+}
+
+/// Reads a whole file, naming it in the error if that fails.
+fn read_file(file_name: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut data = Vec::new();
+    File::open(file_name)
+        .and_then(|mut file| file.read_to_end(&mut data))
+        .map_err(|e| HuffmanError::Io(format!("unable to read {}", file_name), e))?;
+
+    Ok(data)
+}
+
+/// Reads a whole input, naming it in the error if that fails.
+fn read_all(mut input: impl Read, file_name: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut data = Vec::new();
+    input
+        .read_to_end(&mut data)
+        .map_err(|e| HuffmanError::Io(format!("unable to read {}", file_name), e))?;
+
+    Ok(data)
+}
+
+/// Creates or truncates a file, naming it in the error if that fails.
+fn create_file(file_name: &str) -> Result<File, Box<dyn Error>> {
+    Ok(File::create(file_name)
+        .map_err(|e| HuffmanError::Io(format!("unable to create {}", file_name), e))?)
+}
+
+/// Compresses an input into a header of its signature, format version, checksum and code tree,
+/// followed by the length of its payload in bits, and the payload itself.
+#[cfg(test)]
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::<u8>::new();
+    compress_stream(|| Ok(data), &mut compressed).expect("writing to memory cannot fail");
+
+    compressed
+}
+
+/// Compresses an input into the same format as `compress`, without holding it in memory.
+///
+/// The input is opened twice: once to count its bytes and compute its checksum,
+/// and once more to encode it straight into the output as the bits fill up each byte.
+///
+/// Returns the frequencies of the bytes of the input.
+fn compress_stream<R: Read, W: Write>(
+    open: impl FnMut() -> io::Result<R>,
+    output: &mut W,
+) -> io::Result<HashMap<u8, u64>> {
+    output.write_all(MAGIC)?;
+    output.write_all(&[VERSION])?;
+
+    compress_body(open, output)
+}
+
+/// Compresses an input into its checksum and code tree, followed by the length of its payload
+/// in bits, and the payload itself, opening it once to build the tree and once to encode it.
+///
+/// Returns the frequencies of the bytes of the input.
+fn compress_body<R: Read, W: Write>(
+    mut open: impl FnMut() -> io::Result<R>,
+    output: &mut W,
+) -> io::Result<HashMap<u8, u64>> {
+    let mut freq_map = HashMap::new();
+    let mut checksum = 0;
+    for_each_chunk(open()?, |chunk| {
+        add_freqs(&mut freq_map, chunk);
+        checksum = crc32::update(checksum, chunk);
+        Ok(())
+    })?;
+
     let code_lookup = huffman::build_code_lookup(&freq_map);
+    let code_bits_len: usize = freq_map
+        .iter()
+        .map(|(byte, freq)| usize::from(code_lookup[byte].1) * *freq as usize)
+        .sum();
+
+    output.write_all(&checksum.to_le_bytes())?;
+    output.write_all(&serialize_tree(&code_lookup))?;
+    output.write_all(&(code_bits_len as u64).to_le_bytes())?;
+    encode_stream(open()?, &code_lookup, output)?;
+
+    Ok(freq_map)
+}
 
+/// Compresses an input in blocks of `block_size` bytes, each with a code tree of its own,
+/// after a header of the signature and format version.
+///
+/// Each block is framed by its length in bytes, followed by its checksum, code tree,
+/// and payload as written by `compress`, so that it suits the bytes of its own region.
+#[cfg(test)]
+fn compress_blocks(data: &[u8], block_size: usize) -> Vec<u8> {
     let mut compressed = Vec::<u8>::new();
-    compressed.extend_from_slice(&serialize_code_lookup(&code_lookup));
-    compressed.extend_from_slice(&encode_data(data, &code_lookup));
+    compress_blocks_stream(data, block_size, &mut compressed)
+        .expect("writing to memory cannot fail");
 
     compressed
 }
 
-fn create_freq_map(data: &str) -> HashMap<char, u32> {
+/// Compresses an input into the same format as `compress_blocks`, holding one block in memory
+/// at a time.
+///
+/// Returns the frequencies of the bytes of the input.
+fn compress_blocks_stream<R: Read, W: Write>(
+    mut input: R,
+    block_size: usize,
+    output: &mut W,
+) -> io::Result<HashMap<u8, u64>> {
+    output.write_all(MAGIC_BLOCKS)?;
+    output.write_all(&[VERSION])?;
+
     let mut freq_map = HashMap::new();
+    let mut block = Vec::with_capacity(block_size);
+    let mut body = Vec::new();
+    loop {
+        block.clear();
+        (&mut input).take(block_size as u64).read_to_end(&mut block)?;
+        if block.is_empty() {
+            return Ok(freq_map);
+        }
 
-    for c in data.chars() {
-        *freq_map.entry(c).or_insert(0) += 1;
+        body.clear();
+        for (byte, freq) in compress_body(|| Ok(&block[..]), &mut body)? {
+            *freq_map.entry(byte).or_insert(0) += freq;
+        }
+        output.write_all(&u32_len(body.len(), "compressed block")?.to_le_bytes())?;
+        output.write_all(&body)?;
     }
+}
 
-    freq_map
+/// Converts a length to the `u32` the format records it as, failing if it does not fit
+/// rather than recording it truncated.
+fn u32_len(len: usize, what: &str) -> io::Result<u32> {
+    u32::try_from(len).map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("{} is over 4 GiB", what))
+    })
+}
+
+/// Compresses an input into a header of its signature and format version, followed by
+/// a payload coded with an adaptive Huffman tree, and the checksum of the input.
+#[cfg(test)]
+fn compress_adaptive(data: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::<u8>::new();
+    compress_adaptive_stream(data, &mut compressed).expect("writing to memory cannot fail");
+
+    compressed
 }
 
-fn serialize_code_lookup(code_lookup: &HashMap<char, String>) -> Vec<u8> {
-    let mut header = Vec::<u8>::new();
-    let code_lookup_len = code_lookup.len() as u32;
-    header.extend_from_slice(&code_lookup_len.to_le_bytes());
+/// Compresses an input into the same format as `compress_adaptive`, in a single pass.
+///
+/// The tree adapts to the input as it is coded, and the decoder rebuilds it the same way,
+/// so no code tree is written, and nothing needs to be known about the input up front.
+/// Its end is marked by `END_OF_STREAM` rather than a length, and its checksum comes last.
+///
+/// Returns the frequencies of the bytes of the input.
+fn compress_adaptive_stream<R: Read, W: Write>(
+    input: R,
+    output: &mut W,
+) -> io::Result<HashMap<u8, u64>> {
+    output.write_all(MAGIC_ADAPTIVE)?;
+    output.write_all(&[VERSION])?;
+
+    let mut freq_map = HashMap::new();
+    let mut checksum = 0;
+    let mut coder = huffman::AdaptiveCoder::new();
+    let mut writer = BitWriter::new(&mut *output);
+    for_each_chunk(input, |chunk| {
+        add_freqs(&mut freq_map, chunk);
+        checksum = crc32::update(checksum, chunk);
+        chunk
+            .iter()
+            .try_for_each(|byte| coder.encode(u16::from(*byte), &mut writer))
+    })?;
+    coder.encode(huffman::END_OF_STREAM, &mut writer)?;
+    writer.finish()?;
+    output.write_all(&checksum.to_le_bytes())?;
 
-    for (c, code) in code_lookup {
-        let c_bytes_len = c.len_utf8() as u32;
-        header.extend_from_slice(&c_bytes_len.to_le_bytes());
-        header.extend_from_slice(c.to_string().as_bytes());
+    Ok(freq_map)
+}
 
-        let mut code_bits = string_to_bits(code);
-        let code_bits_len = code.len() as u32;
-        header.extend_from_slice(&code_bits_len.to_le_bytes());
-        header.append(&mut code_bits);
+/// Feeds an input to a function one buffered chunk at a time.
+fn for_each_chunk<R: Read>(
+    mut input: R,
+    mut f: impl FnMut(&[u8]) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut buffer = [0; 8 * 1024];
+    loop {
+        match input.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(len) => f(&buffer[..len])?,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
     }
+}
+
+/// Compresses several named inputs with one code tree built from all of them.
+///
+/// The tree is written once after the signature and format version, followed by
+/// the number of files and, for each file, its name, the length of its payload in bits,
+/// and the payload itself.
+///
+/// # Errors
+///
+/// Fails if there are too many files, or a name or payload is too long, for the format to record.
+fn compress_multi(files: &[NamedFile]) -> io::Result<Vec<u8>> {
+    let mut freq_map = HashMap::new();
+    for (_, data) in files {
+        add_freqs(&mut freq_map, data);
+    }
+    let code_lookup = huffman::build_code_lookup(&freq_map);
+
+    let mut compressed = Vec::<u8>::new();
+    compressed.extend_from_slice(MAGIC_MULTI);
+    compressed.push(VERSION);
+    compressed.extend_from_slice(&serialize_tree(&code_lookup));
+    compressed.extend_from_slice(&u32_len(files.len(), "number of files")?.to_le_bytes());
+
+    for (name, data) in files {
+        compressed.extend_from_slice(&u32_len(name.len(), "file name")?.to_le_bytes());
+        compressed.extend_from_slice(name.as_bytes());
+
+        let code_bits_len = u32_len(code_bits_len(data, &code_lookup), "compressed file")?;
+        compressed.extend_from_slice(&code_bits_len.to_le_bytes());
+        compressed.extend_from_slice(&encode_data(data, &code_lookup));
+    }
+
+    Ok(compressed)
+}
+
+/// Counts the bits an input takes up once encoded, without the padding of its last byte.
+fn code_bits_len(data: &[u8], code_lookup: &HashMap<u8, huffman::Code>) -> usize {
+    data.iter().map(|byte| usize::from(code_lookup[byte].1)).sum()
+}
+
+fn create_freq_map(data: &[u8]) -> HashMap<u8, u64> {
+    let mut freq_map = HashMap::new();
+    add_freqs(&mut freq_map, data);
+
+    freq_map
+}
+
+fn add_freqs(freq_map: &mut HashMap<u8, u64>, data: &[u8]) {
+    for byte in data {
+        *freq_map.entry(*byte).or_insert(0) += 1;
+    }
+}
+
+/// Writes the shape of the tree of the codes, preceded by its length in bytes.
+///
+/// The codes are canonical, so their tree is the same as the one the decoder would rebuild
+/// from the code lengths, but it takes about 10 bits per symbol rather than 16.
+fn serialize_tree(code_lookup: &HashMap<u8, huffman::Code>) -> Vec<u8> {
+    let tree = huffman::Decoder::new(code_lookup).serialize();
+    // at most 256 leaves and 255 internal nodes take 2559 bits
+    let mut header = (tree.len() as u16).to_le_bytes().to_vec();
+    header.extend_from_slice(&tree);
 
     header
 }
 
+#[cfg(test)]
 fn string_to_bits(s: &str) -> Vec<u8> {
     let mut bits = Vec::new();
     let mut padded_s = s.to_string();
@@ -115,64 +598,232 @@ fn string_to_bits(s: &str) -> Vec<u8> {
     bits
 }
 
-fn encode_data(data: &str, code_lookup: &HashMap<char, String>) -> Vec<u8> {
+fn encode_data(data: &[u8], code_lookup: &HashMap<u8, huffman::Code>) -> Vec<u8> {
     let mut coded_data = Vec::<u8>::new();
-    let mut code = String::new();
+    encode_stream(data, code_lookup, &mut coded_data).expect("writing to memory cannot fail");
 
-    for c in data.chars() {
-        code.push_str(code_lookup.get(&c).unwrap());
-        while code.len() >= 8 {
-            let byte = u8::from_str_radix(&code[..8], 2).unwrap();
-            coded_data.push(byte);
-            code = code[8..].to_string();
+    coded_data
+}
+
+/// Encodes an input into an output, writing out each byte of codes as soon as it fills up.
+fn encode_stream<R: Read, W: Write>(
+    input: R,
+    code_lookup: &HashMap<u8, huffman::Code>,
+    output: &mut W,
+) -> io::Result<()> {
+    let codes = pack_codes(code_lookup);
+    let mut writer = BitWriter::new(output);
+
+    for_each_chunk(input, |chunk| {
+        for byte in chunk {
+            let (code, len) = codes[usize::from(*byte)];
+            writer.write(code, len)?;
         }
+
+        Ok(())
+    })?;
+
+    // the last byte is padded with zeros
+    writer.finish().map(|_| ())
+}
+
+/// Lays out the codes of a lookup in an array indexed by symbol.
+fn pack_codes(code_lookup: &HashMap<u8, huffman::Code>) -> [huffman::Code; 256] {
+    let mut codes = [(0, 0); 256];
+    for (byte, code) in code_lookup {
+        codes[usize::from(*byte)] = *code;
     }
 
-    if code.len() > 0 {
-        let padding = 8 - code.len();
-        code.push_str(&"0".repeat(padding));
-        let byte = u8::from_str_radix(&code[..8], 2).unwrap();
-        coded_data.push(byte);
+    codes
+}
+
+/// Restores the input of a file written by `compress`.
+///
+/// # Errors
+///
+/// Fails if the data does not open with the signature and version of the format,
+/// or if the payload is truncated or does not match the checksum of the input.
+fn decompress(data: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    if data.starts_with(MAGIC_ADAPTIVE) {
+        return decompress_adaptive(data);
     }
+    if data.starts_with(MAGIC_BLOCKS) {
+        return decompress_blocks(data);
+    }
+    let mut data = data;
 
-    coded_data
+    take_header(&mut data, MAGIC)?;
+
+    take_body(data)
+}
+
+/// Decodes the blocks written by `compress_blocks` in sequence, each with its own code tree.
+///
+/// # Errors
+///
+/// Fails if the data does not open with the signature and version of the format,
+/// or if a block is truncated or does not match the checksum of its input.
+fn decompress_blocks(data: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let mut data = data;
+
+    take_header(&mut data, MAGIC_BLOCKS)?;
+    let mut decoded_data = Vec::new();
+    while !data.is_empty() {
+        let block_len = take_u32(&mut data)? as usize;
+        let block = take_bytes(&mut data, block_len)?;
+        decoded_data.extend(take_body(block)?);
+    }
+
+    Ok(decoded_data)
+}
+
+/// Decodes the checksum, code tree and payload written by `compress_body`.
+///
+/// # Errors
+///
+/// Fails if the payload is truncated or does not match the checksum of the input.
+fn take_body(mut data: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let checksum = take_u32(&mut data)?;
+    let decoder = parse_tree(&mut data)?;
+    // the padding of the last byte is not part of the payload, and could decode to symbols
+    let code_bits_len = take_u64(&mut data)?;
+    if code_bits_len > data.len() as u64 * 8 {
+        return Err(HuffmanError::TruncatedPayload);
+    }
+    let decoded_data = decode_data(data, code_bits_len as usize, &decoder)?;
+    if crc32::checksum(&decoded_data) != checksum {
+        return Err(HuffmanError::ChecksumMismatch);
+    }
+
+    Ok(decoded_data)
 }
 
-fn decompress(data: &Vec<u8>) -> Vec<u8> {
-    let mut data = data.clone();
+/// Decodes data written by `compress_adaptive`, rebuilding the tree as the symbols are decoded.
+///
+/// # Errors
+///
+/// Fails if the data does not open with the signature and version of the format,
+/// or if the payload is truncated or does not match the checksum of the input.
+fn decompress_adaptive(data: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let mut data = data;
+
+    take_header(&mut data, MAGIC_ADAPTIVE)?;
+    let (payload, checksum) = data
+        .split_last_chunk()
+        .ok_or(HuffmanError::TruncatedPayload)?;
+    let checksum = u32::from_le_bytes(*checksum);
 
-    let code_lookup = parse_code_lookup(&mut data);
-    let code = bits_to_string(&data, data.len() * 8);
-    let decoded_data = decode_data(code, code_lookup);
+    let mut coder = huffman::AdaptiveCoder::new();
+    let mut bits = BitReader::new(payload, payload.len() * 8);
+    let mut decoded_data = Vec::new();
+    loop {
+        match coder.decode(&mut bits) {
+            Some(huffman::END_OF_STREAM) => break,
+            Some(symbol) => {
+                let byte = u8::try_from(symbol).map_err(|_| HuffmanError::InvalidCode)?;
+                decoded_data.push(byte);
+            }
+            None => return Err(HuffmanError::TruncatedPayload),
+        }
+    }
+    if crc32::checksum(&decoded_data) != checksum {
+        return Err(HuffmanError::ChecksumMismatch);
+    }
 
-    decoded_data.as_bytes().to_vec()
+    Ok(decoded_data)
 }
 
-fn parse_code_lookup(data: &mut Vec<u8>) -> HashMap<char, String> {
-    let code_lookup_len = u32::from_le_bytes(data[..4].to_vec().try_into().unwrap());
-    data.drain(0..4);
+/// Restores the named inputs of an archive written by `compress_multi`.
+///
+/// # Errors
+///
+/// Fails if the data does not open with the signature and version of the archive format,
+/// if it is truncated or holds a code that is not in its code tree, or if it names a file
+/// by anything but a bare file name, as `run_compress_multi` stores them.
+fn decompress_multi(data: &[u8]) -> Result<Vec<NamedFile>, HuffmanError> {
+    let mut data = data;
 
-    let mut code_lookup = HashMap::new();
-    for _ in 0..code_lookup_len {
-        let c_bytes_len = u32::from_le_bytes(data[..4].to_vec().try_into().unwrap()) as usize;
-        data.drain(0..4);
-        let c_bytes = data[..c_bytes_len].to_vec();
-        let c = std::str::from_utf8(&c_bytes).unwrap().chars().next().unwrap();
-        data.drain(0..c_bytes_len);
+    take_header(&mut data, MAGIC_MULTI)?;
+    let decoder = parse_tree(&mut data)?;
+    let files_len = take_u32(&mut data)?;
 
-        let code_bits_len = u32::from_le_bytes(data[..4].try_into().unwrap()) as usize;
-        data.drain(0..4);
-        let code_bytes_len = (code_bits_len + 7) / 8;
-        let code_bits = data[..code_bytes_len].to_vec();
-        let code = bits_to_string(&code_bits, code_bits_len);
-        data.drain(0..code_bytes_len);
+    let mut files = Vec::new();
+    for _ in 0..files_len {
+        let name_len = take_u32(&mut data)? as usize;
+        let (name, rest) = data.split_at_checked(name_len).ok_or(HuffmanError::TruncatedHeader)?;
+        let name = String::from_utf8_lossy(name).to_string();
+        data = rest;
+        // names with a directory, a root or `..` could be restored anywhere
+        if Path::new(&name).file_name() != Some(name.as_ref()) {
+            return Err(HuffmanError::InvalidName(name));
+        }
+
+        let code_bits_len = take_u32(&mut data)? as usize;
+        let code = take_bytes(&mut data, code_bits_len.div_ceil(8))?;
+        let decoded_data = decode_data(code, code_bits_len, &decoder)?;
+
+        files.push((name, decoded_data));
+    }
+
+    Ok(files)
+}
 
-        code_lookup.insert(c, code);
+/// Checks and strips the signature and format version opening compressed data.
+///
+/// Like the other `take_` functions, it moves the start of the data past what it took,
+/// without copying the rest.
+fn take_header(data: &mut &[u8], magic: &[u8; 4]) -> Result<(), HuffmanError> {
+    if !data.starts_with(magic) {
+        return Err(HuffmanError::BadMagic);
     }
 
-    code_lookup
+    match data.get(magic.len()) {
+        Some(&VERSION) => {
+            *data = &data[magic.len() + 1..];
+            Ok(())
+        }
+        Some(version) => Err(HuffmanError::UnsupportedVersion(*version)),
+        None => Err(HuffmanError::TruncatedHeader),
+    }
+}
+
+/// Takes a little-endian `u32` off the front of the data.
+fn take_u32(data: &mut &[u8]) -> Result<u32, HuffmanError> {
+    let (value, rest) = data.split_first_chunk().ok_or(HuffmanError::TruncatedHeader)?;
+    *data = rest;
+
+    Ok(u32::from_le_bytes(*value))
+}
+
+/// Takes a little-endian `u64` off the front of the data.
+fn take_u64(data: &mut &[u8]) -> Result<u64, HuffmanError> {
+    let (value, rest) = data.split_first_chunk().ok_or(HuffmanError::TruncatedHeader)?;
+    *data = rest;
+
+    Ok(u64::from_le_bytes(*value))
+}
+
+/// Takes the given number of bytes of a payload off the front of the data.
+fn take_bytes<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], HuffmanError> {
+    let (bytes, rest) = data.split_at_checked(len).ok_or(HuffmanError::TruncatedPayload)?;
+    *data = rest;
+
+    Ok(bytes)
+}
+
+/// Reads the code tree written by `serialize_tree`, rebuilding the decoder it describes.
+fn parse_tree(data: &mut &[u8]) -> Result<huffman::Decoder, HuffmanError> {
+    let (tree_len, rest) = data.split_first_chunk().ok_or(HuffmanError::TruncatedHeader)?;
+    let tree_len = usize::from(u16::from_le_bytes(*tree_len));
+
+    let (tree, rest) = rest.split_at_checked(tree_len).ok_or(HuffmanError::TruncatedTree)?;
+    let decoder = huffman::Decoder::deserialize(tree).ok_or(HuffmanError::TruncatedTree)?;
+    *data = rest;
+
+    Ok(decoder)
 }
 
+#[cfg(test)]
 fn bits_to_string(bytes: &[u8], len: usize) -> String {
     let mut code = String::new();
     for byte in bytes {
@@ -182,43 +833,43 @@ fn bits_to_string(bytes: &[u8], len: usize) -> String {
     code[..len].to_string()
 }
 
-fn decode_data(code: String, code_lookup: HashMap<char, String>) -> String {
-    let mut decoded_data = String::new();
-    let mut i = 0;
-    while i < code.len() {
-        let mut j = i + 1;
-        while !code_lookup.values().any(|v| **v == code[i..j]) {
-            j += 1;
-        }
-
-        let c = code_lookup.iter().find(|(_, v)| ***v == code[i..j]).unwrap().0;
-        decoded_data.push(*c);
-        i = j;
-    }
-    decoded_data
+/// Decodes the first `code_bits_len` bits of some data.
+///
+/// # Errors
+///
+/// Fails if the bits hold a code that is not in the tree of the decoder.
+fn decode_data(
+    data: &[u8],
+    code_bits_len: usize,
+    decoder: &huffman::Decoder,
+) -> Result<Vec<u8>, HuffmanError> {
+    decoder
+        .decode(BitReader::new(data, code_bits_len))
+        .ok_or(HuffmanError::InvalidCode)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_create_freq_map() {
-        let data = "hello";
+        let data = b"hello";
         let mut expected = HashMap::new();
-        expected.insert('h', 1);
-        expected.insert('e', 1);
-        expected.insert('l', 2);
-        expected.insert('o', 1);
+        expected.insert(b'h', 1);
+        expected.insert(b'e', 1);
+        expected.insert(b'l', 2);
+        expected.insert(b'o', 1);
 
-        assert_eq!(create_freq_map(&data), expected);
+        assert_eq!(create_freq_map(data), expected);
     }
 
     #[test]
     fn test_string_to_bits() {
         let s = "01100100101010010101000001";
         let expected = vec![0b01100100, 0b10101001, 0b01010000, 0b01000000];
-        assert_eq!(string_to_bits(&s), expected);
+        assert_eq!(string_to_bits(s), expected);
     }
 
     #[test]
@@ -231,31 +882,520 @@ mod tests {
 
     #[test]
     fn test_encode_data() {
-        let data = "hello";
+        let data = b"hello";
         let code_lookup = {
             let mut code_lookup = HashMap::new();
-            code_lookup.insert('h', "00".to_string());
-            code_lookup.insert('e', "01".to_string());
-            code_lookup.insert('l', "10".to_string());
-            code_lookup.insert('o', "11".to_string());
+            code_lookup.insert(b'h', (0b00, 2));
+            code_lookup.insert(b'e', (0b01, 2));
+            code_lookup.insert(b'l', (0b10, 2));
+            code_lookup.insert(b'o', (0b11, 2));
             code_lookup
         };
         let expected = vec![0b00011010, 0b11000000];
-        assert_eq!(encode_data(&data, &code_lookup), expected);
+        assert_eq!(encode_data(data, &code_lookup), expected);
     }
 
     #[test]
     fn test_decode_data() {
-        let code = "0001101011".to_string();
+        let code = "0001101011";
         let code_lookup = {
             let mut code_lookup = HashMap::new();
-            code_lookup.insert('h', "00".to_string());
-            code_lookup.insert('e', "01".to_string());
-            code_lookup.insert('l', "10".to_string());
-            code_lookup.insert('o', "11".to_string());
+            code_lookup.insert(b'h', (0b00, 2));
+            code_lookup.insert(b'e', (0b01, 2));
+            code_lookup.insert(b'l', (0b10, 2));
+            code_lookup.insert(b'o', (0b11, 2));
             code_lookup
         };
-        let expected = "hello";
-        assert_eq!(decode_data(code, code_lookup), expected);
+        let expected = b"hello";
+        let decoder = huffman::Decoder::new(&code_lookup);
+        assert_eq!(decode_data(&string_to_bits(code), code.len(), &decoder).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_compress_multi_round_trip() {
+        let files = vec![
+            ("a.txt".to_string(), b"hello world".to_vec()),
+            ("b.txt".to_string(), b"hello there".to_vec()),
+            ("c.txt".to_string(), b"world, hello".to_vec()),
+        ];
+
+        assert_eq!(decompress_multi(&compress_multi(&files).unwrap()).unwrap(), files);
+    }
+
+    /// Returns a directory in the system's temporary directory for a test to use alone,
+    /// named after the test and the process so that concurrent runs do not collide.
+    fn unique_temp_dir(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("huffman-{}-{}", name, process::id()))
+    }
+
+    #[test]
+    fn test_decompress_multi_rejects_paths() {
+        for name in ["../escape.txt", "/tmp/escape.txt", "nested/file.txt", "..", "."] {
+            let files = vec![(name.to_string(), b"escaped".to_vec())];
+            let result = decompress_multi(&compress_multi(&files).unwrap());
+            assert!(matches!(result, Err(HuffmanError::InvalidName(rejected)) if rejected == name));
+        }
+    }
+
+    #[test]
+    fn test_run_decompress_multi_stays_in_out_dir() {
+        let dir = unique_temp_dir("decompress-multi-escape");
+        let out_dir = dir.join("restored");
+        fs::create_dir_all(&out_dir).unwrap();
+        let archive = dir.join("malicious.hz");
+        let files = vec![
+            ("safe.txt".to_string(), b"safe".to_vec()),
+            ("../escaped.txt".to_string(), b"escaped".to_vec()),
+        ];
+        fs::write(&archive, compress_multi(&files).unwrap()).unwrap();
+
+        let result = run_decompress_multi(archive.to_str().unwrap(), out_dir.to_str().unwrap());
+        let escaped = dir.join("escaped.txt").exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.unwrap_err().to_string().contains("invalid file name"));
+        assert!(!escaped);
+    }
+
+    #[test]
+    fn test_run_multi_round_trip() {
+        let dir = unique_temp_dir("run-multi");
+        let out_dir = dir.join("restored");
+        fs::create_dir_all(&out_dir).unwrap();
+        let file_name = |name: &str| dir.join(name).to_str().unwrap().to_string();
+
+        fs::write(file_name("first.txt"), b"the first file").unwrap();
+        fs::write(file_name("second.txt"), b"and then the second one").unwrap();
+        let in_file_names = [file_name("first.txt"), file_name("second.txt")];
+        run_compress_multi(&file_name("bundle.hz"), &in_file_names).unwrap();
+        assert!(is_archive(&file_name("bundle.hz")));
+        assert!(!is_archive(&file_name("first.txt")));
+
+        run_decompress_multi(&file_name("bundle.hz"), out_dir.to_str().unwrap()).unwrap();
+        let first = fs::read(out_dir.join("first.txt")).unwrap();
+        let second = fs::read(out_dir.join("second.txt")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first, b"the first file");
+        assert_eq!(second, b"and then the second one");
+    }
+
+    #[test]
+    fn test_compress_multi_shares_header() {
+        let files: Vec<(String, Vec<u8>)> = (0..10)
+            .map(|i| (format!("{}.txt", i), format!("the quick brown fox {}", i).into_bytes()))
+            .collect();
+
+        let shared = compress_multi(&files).unwrap().len();
+        let independent: usize = files.iter().map(|(_, data)| compress(data).len()).sum();
+        assert!(shared < independent);
+    }
+
+    #[test]
+    fn test_decode_data_large_input() {
+        let data: Vec<u8> = (0..200_000u64).map(|i| b'a' + (i * i % 26) as u8).collect();
+        let code_lookup = huffman::build_code_lookup(&create_freq_map(&data));
+        let code = encode_data(&data, &code_lookup);
+
+        let started = Instant::now();
+        let decoder = huffman::Decoder::new(&code_lookup);
+        assert_eq!(decode_data(&code, code_bits_len(&data, &code_lookup), &decoder).unwrap(), data);
+        assert!(started.elapsed().as_secs() < 5);
+    }
+
+    #[test]
+    fn test_compress_round_trip_without_padding() {
+        let data = b"abracadabra";
+        let code_lookup = huffman::build_code_lookup(&create_freq_map(data));
+        assert_ne!(code_bits_len(data, &code_lookup) % 8, 0);
+
+        assert_eq!(decompress(&compress(data)).unwrap(), data);
+    }
+
+    #[test]
+    fn test_compress_round_trip_binary() {
+        let data = vec![0xFF, 0x00, 0xC3, 0x28, 0xFF, 0xFE, 0x80, 0xFF];
+        assert!(std::str::from_utf8(&data).is_err());
+
+        assert_eq!(decompress(&compress(&data)).unwrap(), data);
+    }
+
+    #[test]
+    fn test_compress_empty() {
+        let compressed = compress(b"");
+        // the header with the checksum of no data and an empty code tree, followed by a payload of zero bits
+        assert_eq!(compressed, *b"HUFF\x05\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
+        assert!(decompress(&compressed).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_compress_multi_empty_file() {
+        let files = vec![("empty.txt".to_string(), Vec::new())];
+        assert_eq!(decompress_multi(&compress_multi(&files).unwrap()).unwrap(), files);
+    }
+
+    #[test]
+    fn test_compress_round_trip_single_symbol() {
+        let data = b"aaaaaa";
+        assert_eq!(decompress(&compress(data)).unwrap(), data);
+    }
+
+    #[test]
+    fn test_run_missing_input() {
+        let dir = unique_temp_dir("missing-input");
+        fs::create_dir_all(&dir).unwrap();
+        let in_file_name = dir.join("missing.txt");
+        let out_file_name = dir.join("missing.huff");
+        let in_file_name = in_file_name.to_str().unwrap();
+        let out_file_name = out_file_name.to_str().unwrap();
+
+        let error = run(&Mode::Compress, in_file_name, out_file_name).unwrap_err();
+        let written = Path::new(out_file_name).exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(error.to_string().starts_with(&format!("unable to read {}", in_file_name)));
+        assert!(!written);
+    }
+
+    #[test]
+    fn test_run_reports_sizes() {
+        let dir = unique_temp_dir("run-sizes");
+        fs::create_dir_all(&dir).unwrap();
+        let in_file_name = dir.join("sizes.txt");
+        let out_file_name = dir.join("sizes.huff");
+        let data = b"the quick brown fox jumps over the lazy dog".repeat(20);
+        fs::write(&in_file_name, &data).unwrap();
+
+        let stats = run(
+            &Mode::Compress,
+            in_file_name.to_str().unwrap(),
+            out_file_name.to_str().unwrap(),
+        )
+        .unwrap();
+        let out_len = fs::metadata(&out_file_name).unwrap().len() as usize;
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(stats.input_bytes, data.len());
+        assert_eq!(stats.output_bytes, out_len);
+        assert!(stats.to_summary().starts_with(&format!("original: {} bytes", data.len())));
+    }
+
+    #[test]
+    fn test_run_stdin_to_stdout() {
+        let data = b"piped through a shell, like cat file | huffman c - -".to_vec();
+
+        let mut compressed = Vec::new();
+        let stats = run_with(&Mode::Compress, STDIO, STDIO, &data[..], &mut compressed).unwrap();
+        assert_eq!(stats.input_bytes, data.len());
+        assert_eq!(stats.output_bytes, compressed.len());
+
+        let mut decompressed = Vec::new();
+        run_with(&Mode::Decompress, STDIO, STDIO, &compressed[..], &mut decompressed).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert!(matches!(parse_mode(&args(&["huffman", "d", "a"])), Ok(Mode::Decompress)));
+        assert!(matches!(parse_mode(&args(&["huffman", "ca", "a"])), Ok(Mode::CompressAdaptive)));
+        assert!(matches!(
+            parse_mode(&args(&["huffman", "cb", "a"])),
+            Ok(Mode::CompressBlocks(DEFAULT_BLOCK_SIZE))
+        ));
+
+        assert!(matches!(parse_mode(&args(&["huffman", "t", "a"])), Ok(Mode::Test)));
+
+        let error = parse_mode(&args(&["huffman", "z", "a", "b"])).unwrap_err();
+        assert!(error.is::<mode::ParseModeError>());
+        assert_eq!(error.to_string(), "invalid mode");
+
+        let error = parse_mode(&args(&["huffman"])).unwrap_err();
+        assert_eq!(error.to_string(), "missing mode");
+    }
+
+    #[test]
+    fn test_take_flag() {
+        let mut args: Vec<String> = ["huffman", "-v", "c", "a", "b"].map(String::from).to_vec();
+        assert!(take_flag(&mut args, &["-v", "--verbose"]));
+        assert_eq!(args, ["huffman", "c", "a", "b"]);
+        assert!(!take_flag(&mut args, &["--json-stats"]));
+    }
+
+    #[test]
+    fn test_compress_header() {
+        let compressed = compress(b"hello");
+        assert!(compressed.starts_with(b"HUFF\x05"));
+        let archive = compress_multi(&[("a.txt".to_string(), b"hello".to_vec())]).unwrap();
+        assert!(archive.starts_with(b"HUFA\x05"));
+    }
+
+    #[test]
+    fn test_decompress_foreign_file() {
+        let error = decompress(b"GIF89a, not huffman at all").unwrap_err();
+        assert_eq!(error.to_string(), "not a huffman-compressed file");
+
+        let error = decompress(b"HUFF\x09").unwrap_err();
+        assert_eq!(error.to_string(), "unsupported format version 9");
+
+        let archive = compress_multi(&[("a.txt".to_string(), b"hello".to_vec())]).unwrap();
+        assert!(decompress(&archive).is_err());
+    }
+
+    #[test]
+    fn test_decompress_corrupted() {
+        let mut compressed = compress(b"the quick brown fox jumps over the lazy dog");
+        let last = compressed.len() - 1;
+        compressed[last] ^= 0b1000_0000;
+
+        let error = decompress(&compressed).unwrap_err();
+        assert_eq!(error.to_string(), "corrupted file: checksum mismatch");
+
+        compressed.truncate(last);
+        let error = decompress(&compressed).unwrap_err();
+        assert_eq!(error.to_string(), "corrupted file: payload is truncated");
+    }
+
+    #[test]
+    fn test_run_test() {
+        let data = b"the quick brown fox jumps over the lazy dog";
+        for compressed in [compress(data), compress_blocks(data, 8), compress_adaptive(data)] {
+            assert!(run_test(STDIO, &compressed[..]).is_ok());
+
+            let mut corrupted = compressed.clone();
+            let last = corrupted.len() - 1;
+            corrupted[last] ^= 0b1000_0000;
+            let error = run_test(STDIO, &corrupted[..]).unwrap_err();
+            assert_eq!(error::exit_code(error.as_ref()), error::EXIT_CORRUPT);
+        }
+
+        let archive = compress_multi(&[("a.txt".to_string(), data.to_vec())]).unwrap();
+        assert!(run_test(STDIO, &archive[..]).is_ok());
+        assert!(run_test(STDIO, &archive[..archive.len() - 1]).is_err());
+
+        let error = run_test("missing.hz", io::empty()).unwrap_err();
+        assert_eq!(error::exit_code(error.as_ref()), error::EXIT_IO);
+    }
+
+    #[test]
+    fn test_serialize_tree() {
+        let data = b"the quick brown fox jumps over the lazy dog, again and again";
+        let code_lookup = huffman::build_code_lookup(&create_freq_map(data));
+        let header = serialize_tree(&code_lookup);
+
+        // the previous table held each symbol with its code length
+        let previous_len = 2 + 2 * code_lookup.len();
+        assert!(header.len() < previous_len);
+
+        let mut rest = &header[..];
+        assert_eq!(parse_tree(&mut rest).unwrap().code_lookup(), code_lookup);
+        assert!(rest.is_empty());
+        assert_eq!(decompress(&compress(data)).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decompress_truncated_tree() {
+        let mut compressed = compress(b"hello");
+        // claim a longer tree than the file holds
+        compressed[9..11].copy_from_slice(&u16::MAX.to_le_bytes());
+
+        let error = decompress(&compressed).unwrap_err();
+        assert_eq!(error.to_string(), "corrupted file: code tree is truncated");
+    }
+
+    #[test]
+    fn test_compress_stream_large_file() {
+        let dir = unique_temp_dir("stream-large-input");
+        fs::create_dir_all(&dir).unwrap();
+        let file_name = dir.join("large.txt");
+        let mut file = BufWriter::new(File::create(&file_name).unwrap());
+        for i in 0..(1u64 << 16) {
+            writeln!(file, "line {} of a large file that is never held in memory", i).unwrap();
+        }
+        file.flush().unwrap();
+        drop(file);
+
+        // only a fixed-size chunk of the input is in memory at any time,
+        // and the output is discarded as soon as it is written
+        let mut sink = CountingWriter::new(io::sink());
+        let freq_map =
+            compress_stream(|| File::open(&file_name).map(BufReader::new), &mut sink).unwrap();
+        let input_len = fs::metadata(&file_name).unwrap().len() as usize;
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(freq_map.values().sum::<u64>() as usize, input_len);
+        assert!(sink.count < input_len);
+    }
+
+    #[test]
+    fn test_compress_adaptive_round_trip() {
+        let binary: Vec<u8> = (0..=255).chain((0..=255).rev()).collect();
+        for data in [&b"abracadabra"[..], b"", b"aaaaaa", &binary, b"the quick brown fox jumps"] {
+            assert_eq!(decompress(&compress_adaptive(data)).unwrap(), data);
+        }
+
+        let data: Vec<u8> = (0..200_000u64).map(|i| b'a' + (i * i % 26) as u8).collect();
+        let compressed = compress_adaptive(&data);
+        assert!(compressed.len() < data.len());
+        assert_eq!(decompress(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn test_compress_adaptive_has_no_code_tree() {
+        // the header, the NYT code of no bits followed by the 9 bits of the end, and the checksum
+        let compressed = compress_adaptive(b"");
+        assert_eq!(compressed, *b"HUFD\x05\x80\x00\0\0\0\0");
+
+        // the first byte is coded right after the header, not after a code tree
+        let compressed = compress_adaptive(b"a");
+        assert_eq!(compressed[..MAGIC_ADAPTIVE.len() + 2], *b"HUFD\x05\x30");
+    }
+
+    #[test]
+    fn test_decompress_adaptive_corrupted() {
+        let mut compressed = compress_adaptive(b"hello world");
+        let last = compressed.len() - 1;
+        compressed[last] ^= 1;
+        assert!(decompress(&compressed).is_err());
+
+        assert!(decompress(b"HUFD\x05\x30").is_err());
+    }
+
+    #[test]
+    fn test_u32_len() {
+        assert_eq!(u32_len(u32::MAX as usize, "block").unwrap(), u32::MAX);
+        let error = u32_len(u32::MAX as usize + 1, "block").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(error.to_string(), "block is over 4 GiB");
+    }
+
+    #[test]
+    fn test_compress_blocks_round_trip() {
+        // the regions of the input use different bytes, each coded with a tree of its own
+        let data: Vec<u8> = (0..10_000u32)
+            .map(|i| if i < 5_000 { b"abcd"[i as usize % 4] } else { b"wxyz"[i as usize % 4] })
+            .collect();
+        for block_size in [1, 999, 4_096, 5_000, 10_000, DEFAULT_BLOCK_SIZE] {
+            let compressed = compress_blocks(&data, block_size);
+            assert_eq!(decompress(&compressed).unwrap(), data);
+        }
+
+        // two blocks of two symbols each take two bits less per byte than all four would
+        assert!(compress_blocks(&data, 5_000).len() < compress(&data).len());
+
+        assert_eq!(compress_blocks(b"", 4), *b"HUFB\x05");
+        assert!(decompress(&compress_blocks(b"", 4)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_decompress_blocks_truncated() {
+        let compressed = compress_blocks(b"hello world", 4);
+        assert!(decompress(&compressed[..compressed.len() - 1]).is_err());
+        assert!(decompress(&compressed[..MAGIC_BLOCKS.len() + 3]).is_err());
+    }
+
+    #[test]
+    fn test_take_option() {
+        let mut args: Vec<String> =
+            ["huffman", "cb", "--block-size=16", "a"].map(String::from).to_vec();
+        assert_eq!(take_option(&mut args, "--block-size"), Some("16".to_string()));
+        assert_eq!(args, ["huffman", "cb", "a"]);
+        assert_eq!(take_option(&mut args, "--block-size"), None);
+
+        assert_eq!(parse_block_size("16").unwrap(), 16);
+        assert_eq!(parse_block_size("0").unwrap_err().to_string(), "invalid block size '0'");
+        assert!(parse_block_size("x").is_err());
+    }
+
+    #[test]
+    fn test_exit_code() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let error = parse_mode(&args(&["huffman", "z"])).unwrap_err();
+        assert_eq!(error::exit_code(error.as_ref()), error::EXIT_USAGE);
+        let error = parse_mode(&args(&["huffman"])).unwrap_err();
+        assert_eq!(error::exit_code(error.as_ref()), error::EXIT_USAGE);
+        let error = parse_block_size("0").unwrap_err();
+        assert_eq!(error::exit_code(error.as_ref()), error::EXIT_USAGE);
+
+        for data in [&b"not compressed"[..], b"HUFF\x09", b"HUFF\x05\0\0\0\0\x09\0"] {
+            let error = decompress(data).unwrap_err();
+            assert_eq!(error::exit_code(&error), error::EXIT_CORRUPT);
+        }
+        let mut compressed = compress_blocks(b"hello world", 4);
+        compressed.pop();
+        let error = decompress(&compressed).unwrap_err();
+        assert_eq!(error::exit_code(&error), error::EXIT_CORRUPT);
+
+        let in_file_name = unique_temp_dir("exit-code-missing").join("missing.txt");
+        let in_file_name = in_file_name.to_str().unwrap();
+        let error = run_with(&Mode::Decompress, in_file_name, STDIO, io::empty(), io::sink())
+            .unwrap_err();
+        assert_eq!(error::exit_code(error.as_ref()), error::EXIT_IO);
+    }
+
+    #[test]
+    fn test_decompress_malformed() {
+        let decompress_err = |data: &[u8]| decompress(data).unwrap_err();
+        assert!(matches!(decompress_err(b"HUF"), HuffmanError::BadMagic));
+        assert!(matches!(decompress_err(b"HUFF"), HuffmanError::TruncatedHeader));
+        assert!(matches!(decompress_err(b"HUFF\x07"), HuffmanError::UnsupportedVersion(7)));
+
+        let compressed = compress(b"hello");
+        // cut off partway through the checksum, the length of the tree, and the payload length
+        for len in [MAGIC.len() + 3, MAGIC.len() + 6, compressed.len() - 3] {
+            let error = decompress_err(&compressed[..len]);
+            assert!(matches!(error, HuffmanError::TruncatedHeader), "{}: {:?}", len, error);
+        }
+        assert!(matches!(decompress_err(&compressed[..12]), HuffmanError::TruncatedTree));
+        let error = decompress_err(&compressed[..compressed.len() - 1]);
+        assert!(matches!(error, HuffmanError::TruncatedPayload));
+
+        // a lone symbol has the code `0`, so a `1` is not in its tree
+        let mut compressed = compress(b"aaaa");
+        let last = compressed.len() - 1;
+        compressed[last] |= 0b1000_0000;
+        assert!(matches!(decompress_err(&compressed), HuffmanError::InvalidCode));
+
+        // a symbol past `END_OF_STREAM`, sent along with the code of the NYT leaf
+        let error = decompress_err(b"HUFD\x05\xFF\x80\0\0\0\0");
+        assert!(matches!(error, HuffmanError::InvalidCode));
+        assert!(matches!(decompress_err(b"HUFD\x05\0\0"), HuffmanError::TruncatedPayload));
+
+        let mut archive = compress_multi(&[("a.txt".to_string(), b"hello".to_vec())]).unwrap();
+        archive.truncate(archive.len() - 1);
+        let error = decompress_multi(&archive).unwrap_err();
+        assert!(matches!(error, HuffmanError::TruncatedPayload));
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Arbitrary bytes, along with the edge cases of no bytes and a single byte repeated.
+    fn data() -> impl Strategy<Value = Vec<u8>> {
+        prop_oneof![
+            prop::collection::vec(any::<u8>(), 0..4096),
+            prop::collection::vec(any::<u8>(), 0..=1),
+            (any::<u8>(), 0..4096usize).prop_map(|(byte, len)| vec![byte; len]),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_compress_round_trip(data in data()) {
+            prop_assert_eq!(decompress(&compress(&data)).unwrap(), data);
+        }
+
+        #[test]
+        fn test_compress_blocks_round_trip(data in data(), block_size in 1..1024usize) {
+            prop_assert_eq!(decompress(&compress_blocks(&data, block_size)).unwrap(), data);
+        }
+
+        #[test]
+        fn test_compress_adaptive_round_trip(data in data()) {
+            prop_assert_eq!(decompress(&compress_adaptive(&data)).unwrap(), data);
+        }
     }
 }
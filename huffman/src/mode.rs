@@ -2,9 +2,16 @@ use std::error::Error;
 use std::fmt;
 use std::str::FromStr;
 
+#[derive(Debug)]
 pub enum Mode {
     Compress,
+    /// Compresses in a single pass with an adaptive tree, for input that cannot be read twice.
+    CompressAdaptive,
+    /// Compresses in blocks of the given number of bytes, each with a code tree of its own.
+    CompressBlocks(usize),
     Decompress,
+    /// Decompresses and checks the integrity of a file, discarding the output.
+    Test,
 }
 
 impl FromStr for Mode {
@@ -13,7 +20,10 @@ impl FromStr for Mode {
     fn from_str(s: &str) -> Result<Mode, Self::Err>{
         match s {
             "c" => Ok(Mode::Compress),
+            "ca" => Ok(Mode::CompressAdaptive),
+            "cb" => Ok(Mode::CompressBlocks(crate::DEFAULT_BLOCK_SIZE)),
             "d" => Ok(Mode::Decompress),
+            "t" => Ok(Mode::Test),
             _ => Err(ParseModeError),
         }
     }
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::huffman;
+
+/// Summarizes a compression or decompression run.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub input_bytes: usize,
+    pub output_bytes: usize,
+    pub ratio: f64,
+    pub symbols: usize,
+    pub avg_code_length: f64,
+    pub elapsed_ms: f64,
+}
+
+impl Stats {
+    /// Computes the stats of a run from the byte frequencies of the uncompressed data
+    /// and the sizes on either side.
+    pub fn new(
+        freq_map: &HashMap<u8, u64>,
+        input_bytes: usize,
+        output_bytes: usize,
+        elapsed: Duration,
+    ) -> Self {
+        let code_lookup = huffman::build_code_lookup(freq_map);
+        let total_symbols: u64 = freq_map.values().sum();
+        let total_bits: usize = freq_map
+            .iter()
+            .map(|(byte, freq)| usize::from(code_lookup[byte].1) * *freq as usize)
+            .sum();
+
+        Stats {
+            input_bytes,
+            output_bytes,
+            ratio: ratio(output_bytes, input_bytes),
+            symbols: code_lookup.len(),
+            avg_code_length: ratio(total_bits, total_symbols as usize),
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+        }
+    }
+
+    /// Formats the stats as a single-line JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"input_bytes\":{},\"output_bytes\":{},\"ratio\":{},\"symbols\":{},\"avg_code_length\":{},\"elapsed_ms\":{}}}",
+            self.input_bytes,
+            self.output_bytes,
+            self.ratio,
+            self.symbols,
+            self.avg_code_length,
+            self.elapsed_ms,
+        )
+    }
+
+    /// Formats the sizes on either side of a compression, and the share of the input it saved.
+    pub fn to_summary(&self) -> String {
+        format!(
+            "original: {} bytes, compressed: {} bytes, saved: {:.1}%",
+            self.input_bytes,
+            self.output_bytes,
+            // nothing is saved on empty input
+            ratio(self.input_bytes.saturating_sub(self.output_bytes), self.input_bytes) * 100.0,
+        )
+    }
+}
+
+/// Divides two counts, yielding zero rather than NaN for an empty denominator.
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let text = b"hello huffman";
+        let stats = Stats::new(&crate::create_freq_map(text), text.len(), 40, Duration::from_millis(3));
+        let json: serde_json::Value = serde_json::from_str(&stats.to_json()).unwrap();
+
+        let input_bytes = json["input_bytes"].as_f64().unwrap();
+        let output_bytes = json["output_bytes"].as_f64().unwrap();
+        assert_eq!(input_bytes, text.len() as f64);
+        assert_eq!(output_bytes, 40.0);
+        assert!((json["ratio"].as_f64().unwrap() - output_bytes / input_bytes).abs() < 1e-9);
+        assert_eq!(json["symbols"].as_u64(), Some(10));
+        assert!(json["avg_code_length"].as_f64().unwrap() > 0.0);
+        assert!(json["elapsed_ms"].as_f64().unwrap() >= 3.0);
+    }
+
+    #[test]
+    fn test_to_summary() {
+        let stats = Stats::new(&crate::create_freq_map(b"aaaa"), 1000, 450, Duration::ZERO);
+        assert_eq!(
+            stats.to_summary(),
+            "original: 1000 bytes, compressed: 450 bytes, saved: 55.0%"
+        );
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let stats = Stats::new(&HashMap::new(), 0, 0, Duration::ZERO);
+        assert_eq!(stats.ratio, 0.0);
+        assert!(stats.to_summary().ends_with("saved: 0.0%"));
+        assert!(serde_json::from_str::<serde_json::Value>(&stats.to_json()).is_ok());
+    }
+}
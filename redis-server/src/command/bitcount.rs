@@ -0,0 +1,145 @@
+/// This module contains the implementation of the `BitCount` command.
+/// The `BitCount` command counts the set bits of a string, optionally only within a range
+/// of bytes, where negative offsets count from the end of the string.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `BitCount` command.
+pub struct BitCount {
+    key: String,
+    range: Option<(i64, i64)>,
+}
+
+impl ExecuteOn for BitCount {
+    /// Executes the `BitCount` command and returns the number of set bits.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.bitcount(&self.key, self.range) {
+            Ok(count) => Response::Integer(i64::try_from(count).unwrap_or(i64::MAX)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `BitCount` command.
+pub struct Builder {
+    key: Option<String>,
+    range: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            range: Vec::new(),
+        }
+    }
+
+    /// Sets the key of the string.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the start and end offsets of the range of bytes, if any.
+    pub fn range(mut self, range: &[String]) -> Self {
+        self.range = range.to_vec();
+        self
+    }
+
+    /// Builds the `BitCount` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided,
+    /// an `ArgumentError::Syntax` if the range does not have both offsets,
+    /// or an `ArgumentError::Integer` if an offset is not an integer.
+    pub fn build(self) -> Result<BitCount, ArgumentError> {
+        let range = match self.range.as_slice() {
+            [] => None,
+            [start, end] => Some((
+                start.parse().map_err(|_| ArgumentError::Integer)?,
+                end.parse().map_err(|_| ArgumentError::Integer)?,
+            )),
+            _ => return Err(ArgumentError::Syntax),
+        };
+
+        Ok(BitCount {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            range,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitcount(store: &KvStore, key: &str, range: &[&str]) -> Response {
+        let range: Vec<String> = range.iter().map(ToString::to_string).collect();
+        Builder::new()
+            .key(key)
+            .range(&range)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_bitcount() {
+        let store = KvStore::new();
+        store.set("bitcount:foobar", "foobar");
+
+        assert_eq!(
+            bitcount(&store, "bitcount:foobar", &[]),
+            Response::Integer(26)
+        );
+        assert_eq!(
+            bitcount(&store, "bitcount:missing", &[]),
+            Response::Integer(0)
+        );
+    }
+
+    #[test]
+    fn test_bitcount_range() {
+        let store = KvStore::new();
+        store.set("bitcount:range", "foobar");
+
+        assert_eq!(
+            bitcount(&store, "bitcount:range", &["0", "0"]),
+            Response::Integer(4)
+        );
+        assert_eq!(
+            bitcount(&store, "bitcount:range", &["1", "1"]),
+            Response::Integer(6)
+        );
+        assert_eq!(
+            bitcount(&store, "bitcount:range", &["1", "-2"]),
+            Response::Integer(18)
+        );
+        assert_eq!(
+            bitcount(&store, "bitcount:range", &["-1", "0"]),
+            Response::Integer(0)
+        );
+    }
+
+    #[test]
+    fn test_bitcount_after_setbit() {
+        let store = KvStore::new();
+        store.setbit("bitcount:setbit", 0, true).unwrap();
+        assert_eq!(
+            bitcount(&store, "bitcount:setbit", &[]),
+            Response::Integer(1)
+        );
+    }
+
+    #[test]
+    fn test_bitcount_invalid_range() {
+        let result = Builder::new()
+            .key("bitcount:invalid")
+            .range(&["0".into()])
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Syntax)));
+    }
+}
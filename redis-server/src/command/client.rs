@@ -0,0 +1,175 @@
+/// This module contains the implementation of the `Client` command,
+/// which inspects and names client connections.
+use super::{
+    config::CommandBuildError,
+    types::{ArgumentError, ExecuteInSession, SubcommandError},
+};
+
+use crate::{
+    response::types::Response,
+    session::{self, Session},
+};
+
+use std::str::FromStr;
+
+/// Represents the `Client` command.
+#[derive(Debug)]
+pub struct Client {
+    subcommand: ClientSubcommand,
+}
+
+#[derive(Debug)]
+enum ClientSubcommand {
+    Id,
+    GetName,
+    SetName(String),
+    List,
+}
+
+impl FromStr for ClientSubcommand {
+    type Err = SubcommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "ID" => Ok(Self::Id),
+            "GETNAME" => Ok(Self::GetName),
+            "SETNAME" => Ok(Self::SetName(String::new())),
+            "LIST" => Ok(Self::List),
+            _ => Err(Self::Err::Unknown),
+        }
+    }
+}
+
+impl ExecuteInSession for Client {
+    /// Executes the `Client` command against the client's own connection.
+    fn execute_in(self, session: &mut Session) -> Response {
+        match self.subcommand {
+            ClientSubcommand::Id => {
+                Response::Integer(i64::try_from(session.id()).unwrap_or(i64::MAX))
+            }
+            ClientSubcommand::GetName => session
+                .name()
+                .map_or(Response::Null, |name| Response::bs(&name)),
+            ClientSubcommand::SetName(name) => {
+                if name.chars().any(|c| !c.is_ascii_graphic()) {
+                    return Response::err(
+                        "",
+                        "Client names cannot contain spaces, newlines or special characters.",
+                    );
+                }
+                session.set_name(&name);
+                Response::ss("OK")
+            }
+            ClientSubcommand::List => Response::bs(&session::client_list()),
+        }
+    }
+}
+
+/// Builder for the `Client` command.
+pub struct Builder {
+    args_raw: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { args_raw: None }
+    }
+
+    /// Sets the subcommand and its arguments.
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args_raw = Some(args);
+        self
+    }
+
+    /// Builds the `Client` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CommandBuildError::Subcommand` if the subcommand is missing or unknown,
+    /// or a `CommandBuildError::Argument` if it has the wrong number of arguments.
+    pub fn build(self) -> Result<Client, CommandBuildError> {
+        let Some(args) = self.args_raw else {
+            return Err(CommandBuildError::Subcommand(SubcommandError::Missing));
+        };
+
+        let subcommand = match args.first() {
+            Some(sub) => ClientSubcommand::from_str(sub)?,
+            None => return Err(CommandBuildError::Subcommand(SubcommandError::Missing)),
+        };
+
+        let subcommand = match (subcommand, &args[1..]) {
+            (ClientSubcommand::SetName(_), [name]) => ClientSubcommand::SetName(name.clone()),
+            (ClientSubcommand::SetName(_), _) => {
+                return Err(CommandBuildError::Argument(ArgumentError::Missing));
+            }
+            (subcommand, []) => subcommand,
+            _ => return Err(CommandBuildError::Argument(ArgumentError::Syntax)),
+        };
+
+        Ok(Client { subcommand })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    fn client(args: &[&str]) -> Client {
+        Builder::new()
+            .args(args.iter().map(ToString::to_string).collect())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_client_setname_getname() {
+        let mut session = Session::new(mpsc::channel(16).0);
+        assert_eq!(
+            client(&["GETNAME"]).execute_in(&mut session),
+            Response::Null
+        );
+        assert_eq!(
+            client(&["SETNAME", "foo"]).execute_in(&mut session),
+            Response::ss("OK")
+        );
+        assert_eq!(
+            client(&["GETNAME"]).execute_in(&mut session),
+            Response::bs("foo")
+        );
+        assert!(matches!(
+            client(&["SETNAME", "foo bar"]).execute_in(&mut session),
+            Response::Error(_)
+        ));
+    }
+
+    #[test]
+    fn test_client_id_and_list() {
+        let mut session = Session::new(mpsc::channel(16).0);
+        let id = session.id();
+        assert_eq!(
+            client(&["ID"]).execute_in(&mut session),
+            Response::Integer(i64::try_from(id).unwrap())
+        );
+
+        client(&["SETNAME", "lister"]).execute_in(&mut session);
+        let Response::BulkString(list) = client(&["LIST"]).execute_in(&mut session) else {
+            panic!("CLIENT LIST should return a bulk string");
+        };
+        assert!(String::from_utf8(list)
+            .unwrap()
+            .lines()
+            .any(|line| line.starts_with(&format!("id={id} name=lister "))));
+
+        drop(session);
+        let mut other = Session::new(mpsc::channel(16).0);
+        let Response::BulkString(list) = client(&["LIST"]).execute_in(&mut other) else {
+            panic!("CLIENT LIST should return a bulk string");
+        };
+        assert!(!String::from_utf8(list)
+            .unwrap()
+            .lines()
+            .any(|line| line.starts_with(&format!("id={id} "))));
+    }
+}
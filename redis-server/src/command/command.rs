@@ -0,0 +1,202 @@
+/// This module contains the implementation of the `Command` command,
+/// which lets clients introspect the commands implemented by the server.
+use super::{
+    config::CommandBuildError,
+    metadata::{self, Metadata},
+    types::{Execute, SubcommandError},
+};
+
+use crate::response::types::Response;
+
+use std::str::FromStr;
+
+/// Represents the `Command` command.
+#[derive(Debug)]
+pub struct Command {
+    subcommand: CommandSubcommand,
+    args: Vec<String>,
+}
+
+#[derive(Debug)]
+enum CommandSubcommand {
+    List,
+    Count,
+    Docs,
+}
+
+impl FromStr for CommandSubcommand {
+    type Err = SubcommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "COUNT" => Ok(Self::Count),
+            "DOCS" => Ok(Self::Docs),
+            _ => Err(Self::Err::Unknown),
+        }
+    }
+}
+
+impl Execute for Command {
+    /// Executes the `Command` command and returns the requested introspection reply.
+    fn execute(self) -> Response {
+        match &self.subcommand {
+            CommandSubcommand::List => {
+                Response::Array(metadata::COMMANDS.iter().map(info).collect())
+            }
+            CommandSubcommand::Count => {
+                Response::Integer(i64::try_from(metadata::COMMANDS.len()).unwrap_or(i64::MAX))
+            }
+            CommandSubcommand::Docs => {
+                let documented: Vec<&Metadata> = if self.args.is_empty() {
+                    metadata::COMMANDS.iter().collect()
+                } else {
+                    self.args
+                        .iter()
+                        .filter_map(|name| metadata::lookup(name))
+                        .collect()
+                };
+
+                Response::Array(
+                    documented
+                        .into_iter()
+                        .flat_map(|metadata| [Response::bs(metadata.name), docs(metadata)])
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// Builds the description of a command, in the shape of a `COMMAND INFO` reply.
+fn info(metadata: &Metadata) -> Response {
+    let (first_key, last_key, step) = metadata.keys;
+    Response::Array(vec![
+        Response::bs(metadata.name),
+        Response::Integer(metadata.arity),
+        Response::Array(vec![]),
+        Response::Integer(first_key),
+        Response::Integer(last_key),
+        Response::Integer(step),
+    ])
+}
+
+/// Builds the documentation map of a command, as a flat array of field names and values.
+fn docs(metadata: &Metadata) -> Response {
+    Response::Array(vec![
+        Response::bs("summary"),
+        Response::bs(metadata.summary),
+        Response::bs("since"),
+        Response::bs(metadata.since),
+        Response::bs("group"),
+        Response::bs(metadata.group),
+        Response::bs("arity"),
+        Response::Integer(metadata.arity),
+    ])
+}
+
+/// Builder for the `Command` command.
+pub struct Builder {
+    args_raw: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { args_raw: None }
+    }
+
+    /// Sets the subcommand and its arguments.
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args_raw = Some(args);
+        self
+    }
+
+    /// Builds the `Command` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CommandBuildError::Subcommand` if the subcommand is unknown.
+    pub fn build(self) -> Result<Command, CommandBuildError> {
+        let args = self.args_raw.unwrap_or_default();
+
+        let subcommand = match args.first() {
+            Some(sub) => CommandSubcommand::from_str(sub)?,
+            None => CommandSubcommand::List,
+        };
+
+        Ok(Command {
+            subcommand,
+            args: args.into_iter().skip(1).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_count() {
+        let cmd = Builder::new().args(vec!["COUNT".into()]).build().unwrap();
+        let Response::Integer(count) = cmd.execute() else {
+            panic!("COMMAND COUNT should return an integer");
+        };
+        assert!(count > 0);
+        assert_eq!(usize::try_from(count).unwrap(), metadata::COMMANDS.len());
+    }
+
+    #[test]
+    fn test_command_list() {
+        let cmd = Builder::new().build().unwrap();
+        let Response::Array(reply) = cmd.execute() else {
+            panic!("COMMAND should return an array");
+        };
+        assert_eq!(reply.len(), metadata::COMMANDS.len());
+        assert!(reply.contains(&Response::Array(vec![
+            Response::bs("rename"),
+            Response::Integer(3),
+            Response::Array(vec![]),
+            Response::Integer(1),
+            Response::Integer(2),
+            Response::Integer(1),
+        ])));
+    }
+
+    #[test]
+    fn test_command_docs_get() {
+        let cmd = Builder::new()
+            .args(vec!["DOCS".into(), "GET".into()])
+            .build()
+            .unwrap();
+        let Response::Array(reply) = cmd.execute() else {
+            panic!("COMMAND DOCS should return an array");
+        };
+        assert_eq!(reply.len(), 2);
+        assert_eq!(reply[0], Response::bs("get"));
+
+        let Response::Array(fields) = &reply[1] else {
+            panic!("command docs should be a map");
+        };
+        let field = |name: &str| {
+            fields
+                .chunks(2)
+                .find(|pair| pair[0] == Response::bs(name))
+                .map(|pair| pair[1].clone())
+        };
+        assert!(matches!(field("summary"), Some(Response::BulkString(s)) if !s.is_empty()));
+        assert_eq!(field("arity"), Some(Response::Integer(2)));
+    }
+
+    #[test]
+    fn test_command_docs_omits_unknown() {
+        let cmd = Builder::new()
+            .args(vec!["DOCS".into(), "FOOBAR".into(), "ECHO".into()])
+            .build()
+            .unwrap();
+        let Response::Array(reply) = cmd.execute() else {
+            panic!("COMMAND DOCS should return an array");
+        };
+        assert_eq!(reply.len(), 2);
+        assert_eq!(reply[0], Response::bs("echo"));
+    }
+}
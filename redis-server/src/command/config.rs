@@ -1,9 +1,9 @@
 use super::types::{ArgumentError, Execute, SubcommandError};
 
-use crate::response::types::Response;
+use crate::{config::SERVER_CONFIG, response::types::Response};
 
 use core::fmt;
-use std::{error::Error, str::FromStr, vec};
+use std::{error::Error, str::FromStr, sync::PoisonError};
 
 #[derive(Debug)]
 pub struct Config {
@@ -30,14 +30,25 @@ impl FromStr for ConfigSubcommand {
 impl Execute for Config {
     fn execute(self) -> Response {
         match &self.subcommand {
-            ConfigSubcommand::Get => self.args.first().map_or_else(
-                || Response::err("", "missing argument for CONFIG GET"),
-                |arg| match arg.as_str() {
-                    "save" => Response::arr(vec!["save".into(), String::new()]),
-                    "appendonly" => Response::arr(vec!["appendonly".into(), "no".into()]),
-                    _ => Response::err("", "unexpected CONFIG GET argument"),
-                },
-            ),
+            // every parameter matching any of the patterns is reported once
+            ConfigSubcommand::Get => {
+                let config = SERVER_CONFIG.read().unwrap_or_else(PoisonError::into_inner);
+                let mut pairs: Vec<(String, String)> = Vec::new();
+                for pattern in &self.args {
+                    for pair in config.get_matching(pattern) {
+                        if !pairs.contains(&pair) {
+                            pairs.push(pair);
+                        }
+                    }
+                }
+
+                Response::arr(
+                    pairs
+                        .into_iter()
+                        .flat_map(|(parameter, value)| [parameter, value])
+                        .collect(),
+                )
+            }
         }
     }
 }
@@ -89,14 +100,69 @@ impl Builder {
             None => return Err(CommandBuildError::Subcommand(SubcommandError::Missing)),
         };
 
-        let mut args_iter = args.iter().skip(1);
-        if args_iter.any(String::is_empty) {
+        let args = &args[1..];
+        if args.is_empty() || args.iter().any(String::is_empty) {
             Err(CommandBuildError::Argument(ArgumentError::Missing))
         } else {
             Ok(Config {
                 subcommand,
-                args: args_iter.map(String::to_string).collect(),
+                args: args.to_vec(),
             })
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(args: &[&str]) -> Result<Config, CommandBuildError> {
+        Builder::new()
+            .args(args.iter().map(ToString::to_string).collect())
+            .build()
+    }
+
+    fn config_get(patterns: &[&str]) -> Vec<Response> {
+        let args: Vec<&str> = ["GET"].iter().chain(patterns).copied().collect();
+        let Response::Array(reply) = config(&args).unwrap().execute() else {
+            panic!("CONFIG GET should reply with an array");
+        };
+        reply
+    }
+
+    /// Picks the parameter names out of a `CONFIG GET` reply.
+    fn parameters(reply: &[Response]) -> Vec<Response> {
+        reply.iter().step_by(2).cloned().collect()
+    }
+
+    #[test]
+    fn test_config_get_all() {
+        let reply = config_get(&["*"]);
+        assert_eq!(reply.len() % 2, 0);
+        let parameters = parameters(&reply);
+        assert!(parameters.len() > 1);
+        assert!(parameters.contains(&Response::bs("port")));
+        assert!(parameters.contains(&Response::bs("appendonly")));
+    }
+
+    #[test]
+    fn test_config_get_glob() {
+        assert_eq!(
+            parameters(&config_get(&["TCP-*"])),
+            vec![Response::bs("tcp-nodelay"), Response::bs("tcp-keepalive")]
+        );
+
+        // a parameter matched by several patterns is reported once
+        assert_eq!(config_get(&["hz", "h?"]), config_get(&["hz"]));
+        assert_eq!(parameters(&config_get(&["hz"])), vec![Response::bs("hz")]);
+        assert!(config_get(&["maxmemory*"]).is_empty());
+    }
+
+    #[test]
+    fn test_config_get_missing_pattern() {
+        assert!(matches!(
+            config(&["GET"]),
+            Err(CommandBuildError::Argument(ArgumentError::Missing))
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+/// This module contains the implementation of the `Copy` command.
+/// The `Copy` command copies the value of a key to another key, leaving the original intact.
+/// An existing destination is only overwritten when the `REPLACE` option is given.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `Copy` command.
+pub struct Copy {
+    key: String,
+    destination: String,
+    replace: bool,
+}
+
+impl ExecuteOn for Copy {
+    /// Executes the `Copy` command and returns `1` if the value was copied, `0` otherwise.
+    fn execute_on(self, store: &KvStore) -> Response {
+        if self.key == self.destination {
+            return Response::err("", "source and destination objects are the same");
+        }
+
+        Response::Integer(
+            store
+                .copy(&self.key, &self.destination, self.replace)
+                .into(),
+        )
+    }
+}
+
+/// Builder for the `Copy` command.
+pub struct Builder {
+    key: Option<String>,
+    destination: Option<String>,
+    options: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            destination: None,
+            options: Vec::new(),
+        }
+    }
+
+    /// Sets the source key for the `Copy` command.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the destination key for the `Copy` command.
+    pub fn destination(mut self, destination: &str) -> Self {
+        self.destination = Some(destination.into());
+        self
+    }
+
+    /// Sets the trailing options for the `Copy` command.
+    pub fn options(mut self, options: &[String]) -> Self {
+        self.options = options.to_vec();
+        self
+    }
+
+    /// Builds the `Copy` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if either key is not provided,
+    /// or an `ArgumentError::Syntax` if an option other than `REPLACE` is given.
+    pub fn build(self) -> Result<Copy, ArgumentError> {
+        let mut replace = false;
+        for option in &self.options {
+            match option.to_uppercase().as_str() {
+                "REPLACE" => replace = true,
+                _ => return Err(ArgumentError::Syntax),
+            }
+        }
+
+        Ok(Copy {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            destination: self.destination.ok_or(ArgumentError::Missing)?,
+            replace,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_to_new_key() {
+        let store = KvStore::new();
+        store.set("copy:src", "value");
+
+        let cmd = Builder::new()
+            .key("copy:src")
+            .destination("copy:dest")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+        assert_eq!(store.get("copy:src"), Ok(Some("value".into())));
+        assert_eq!(store.get("copy:dest"), Ok(Some("value".into())));
+    }
+
+    #[test]
+    fn test_copy_onto_existing_key() {
+        let store = KvStore::new();
+        store.set("copy:existing:src", "value");
+        store.set("copy:existing:dest", "old");
+
+        let cmd = Builder::new()
+            .key("copy:existing:src")
+            .destination("copy:existing:dest")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(0));
+        assert_eq!(store.get("copy:existing:dest"), Ok(Some("old".into())));
+
+        let cmd = Builder::new()
+            .key("copy:existing:src")
+            .destination("copy:existing:dest")
+            .options(&["replace".into()])
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+        assert_eq!(store.get("copy:existing:dest"), Ok(Some("value".into())));
+    }
+
+    #[test]
+    fn test_copy_missing_source() {
+        let store = KvStore::new();
+        let cmd = Builder::new()
+            .key("copy:missing")
+            .destination("copy:missing:dest")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(0));
+        assert!(!store.exists("copy:missing:dest"));
+    }
+
+    #[test]
+    fn test_copy_invalid_option() {
+        let result = Builder::new()
+            .key("copy:src")
+            .destination("copy:dest")
+            .options(&["NOPE".into()])
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Syntax)));
+    }
+}
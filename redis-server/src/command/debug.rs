@@ -0,0 +1,210 @@
+/// This module contains the implementation of the `Debug` command,
+/// which offers the hooks test suites rely on to drive the server into specific states.
+use super::{
+    config::CommandBuildError,
+    types::{ArgumentError, ExecuteInSession, SubcommandError},
+};
+
+use crate::{
+    kvstore::{KvStore, DATABASES},
+    response::types::Response,
+    session::Session,
+};
+
+use std::{str::FromStr, time::Duration};
+
+/// Represents the `Debug` command.
+#[derive(Debug)]
+pub struct Debug {
+    subcommand: DebugSubcommand,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DebugSubcommand {
+    FlushAll,
+    Reload,
+    SetActiveExpire(bool),
+    Sleep(Duration),
+}
+
+impl FromStr for DebugSubcommand {
+    type Err = SubcommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "FLUSHALL" => Ok(Self::FlushAll),
+            "RELOAD" => Ok(Self::Reload),
+            "SET-ACTIVE-EXPIRE" => Ok(Self::SetActiveExpire(true)),
+            "SLEEP" => Ok(Self::Sleep(Duration::ZERO)),
+            _ => Err(Self::Err::Unknown),
+        }
+    }
+}
+
+impl ExecuteInSession for Debug {
+    /// Executes the `Debug` command against every database of the server.
+    ///
+    /// `DEBUG SLEEP` holds back the client's responses rather than sleeping here,
+    /// so that only the client's own connection waits.
+    fn execute_in(self, session: &mut Session) -> Response {
+        if let DebugSubcommand::Sleep(duration) = self.subcommand {
+            session.delay(duration);
+            return Response::ss("OK");
+        }
+
+        self.execute_on(DATABASES.iter())
+    }
+}
+
+impl Debug {
+    /// Executes the `Debug` command against each of the given key-value stores.
+    fn execute_on<'a>(self, stores: impl IntoIterator<Item = &'a KvStore>) -> Response {
+        for store in stores {
+            match self.subcommand {
+                DebugSubcommand::FlushAll => store.flush(),
+                DebugSubcommand::Reload => store.reload(),
+                DebugSubcommand::SetActiveExpire(enabled) => store.set_active_expire(enabled),
+                DebugSubcommand::Sleep(_) => {}
+            }
+        }
+        Response::ss("OK")
+    }
+}
+
+/// Builder for the `Debug` command.
+pub struct Builder {
+    args_raw: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { args_raw: None }
+    }
+
+    /// Sets the subcommand and its arguments.
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args_raw = Some(args);
+        self
+    }
+
+    /// Builds the `Debug` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CommandBuildError::Subcommand` if the subcommand is missing or unknown,
+    /// or a `CommandBuildError::Argument` if its arguments are invalid.
+    pub fn build(self) -> Result<Debug, CommandBuildError> {
+        let Some(args) = self.args_raw else {
+            return Err(CommandBuildError::Subcommand(SubcommandError::Missing));
+        };
+
+        let subcommand = match args.first() {
+            Some(sub) => DebugSubcommand::from_str(sub)?,
+            None => return Err(CommandBuildError::Subcommand(SubcommandError::Missing)),
+        };
+
+        let subcommand = match (subcommand, &args[1..]) {
+            (DebugSubcommand::SetActiveExpire(_), [enabled]) => match enabled.as_str() {
+                "0" => DebugSubcommand::SetActiveExpire(false),
+                "1" => DebugSubcommand::SetActiveExpire(true),
+                _ => return Err(CommandBuildError::Argument(ArgumentError::Syntax)),
+            },
+            (DebugSubcommand::Sleep(_), [seconds]) => seconds
+                .parse()
+                .ok()
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                .map(DebugSubcommand::Sleep)
+                .ok_or(CommandBuildError::Argument(ArgumentError::Float))?,
+            (DebugSubcommand::SetActiveExpire(_) | DebugSubcommand::Sleep(_), _) => {
+                return Err(CommandBuildError::Argument(ArgumentError::Missing));
+            }
+            (subcommand, []) => subcommand,
+            _ => return Err(CommandBuildError::Argument(ArgumentError::Syntax)),
+        };
+
+        Ok(Debug { subcommand })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    fn debug(args: &[&str]) -> Debug {
+        Builder::new()
+            .args(args.iter().map(ToString::to_string).collect())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_debug_flushall() {
+        let store = KvStore::new();
+        store.set("debug:flushall", "value");
+
+        assert_eq!(
+            debug(&["FLUSHALL"]).execute_on([&store]),
+            Response::ss("OK")
+        );
+        assert_eq!(store.len(), 0);
+    }
+
+    #[test]
+    fn test_debug_reload() {
+        let store = KvStore::new();
+        store.set("debug:reload", "value");
+
+        assert_eq!(debug(&["RELOAD"]).execute_on([&store]), Response::ss("OK"));
+        assert_eq!(store.get("debug:reload"), Ok(Some("value".into())));
+    }
+
+    #[test]
+    fn test_debug_set_active_expire() {
+        let store = KvStore::new();
+
+        assert_eq!(
+            debug(&["SET-ACTIVE-EXPIRE", "0"]).execute_on([&store]),
+            Response::ss("OK")
+        );
+        assert!(!store.active_expire());
+
+        debug(&["set-active-expire", "1"]).execute_on([&store]);
+        assert!(store.active_expire());
+
+        let result = Builder::new()
+            .args(vec!["SET-ACTIVE-EXPIRE".into(), "2".into()])
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_debug_flushall_every_store() {
+        let stores = [KvStore::new(), KvStore::new()];
+        stores[0].set("debug:first", "value");
+        stores[1].set("debug:second", "value");
+
+        debug(&["FLUSHALL"]).execute_on(&stores);
+        assert!(stores.iter().all(|store| store.len() == 0));
+    }
+
+    #[test]
+    fn test_debug_sleep() {
+        let mut session = Session::new(mpsc::channel(16).0);
+
+        assert_eq!(
+            debug(&["SLEEP", "0.25"]).execute_in(&mut session),
+            Response::ss("OK")
+        );
+        assert_eq!(session.take_delay(), Duration::from_millis(250));
+        assert_eq!(session.take_delay(), Duration::ZERO);
+
+        for seconds in ["-1", "soon"] {
+            let result = Builder::new()
+                .args(vec!["SLEEP".into(), seconds.into()])
+                .build();
+            assert!(result.is_err());
+        }
+    }
+}
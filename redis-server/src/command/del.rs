@@ -1,18 +1,18 @@
 /// This module contains the implementation of the `Del` command.
 /// The `Del` command is used to delete a key from the key-value store.
-use crate::{kvstore::KV_STORE, response::types::Response};
+use crate::{kvstore::KvStore, response::types::Response};
 
-use super::types::{ArgumentError, Execute};
+use super::types::{ArgumentError, ExecuteOn};
 
 /// Represents the `Del` command.
 pub struct Del {
     key: String,
 }
 
-impl Execute for Del {
+impl ExecuteOn for Del {
     /// Executes the `Del` command by deleting the specified key from the key-value store.
-    fn execute(self) -> Response {
-        KV_STORE.del(&self.key);
+    fn execute_on(self, store: &KvStore) -> Response {
+        store.del(&self.key);
         Response::ss("OK")
     }
 }
@@ -0,0 +1,54 @@
+/// This module contains the implementation of the `Discard` command.
+/// The `Discard` command drops the commands queued since `MULTI` and ends the transaction.
+use crate::{response::types::Response, session::Session};
+
+use super::types::ExecuteInSession;
+
+/// Represents the `Discard` command.
+pub struct Discard;
+
+impl ExecuteInSession for Discard {
+    /// Executes the `Discard` command and returns `OK` once the transaction has ended.
+    fn execute_in(self, session: &mut Session) -> Response {
+        match session.take_transaction() {
+            Some(_) => Response::ss("OK"),
+            None => Response::err("ERR", "DISCARD without MULTI"),
+        }
+    }
+}
+
+/// Builder for the `Discard` command.
+pub struct Builder;
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Builds the `Discard` command, which takes no arguments.
+    #[allow(clippy::unused_self)] // consumed like every other builder
+    pub const fn build(self) -> Discard {
+        Discard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_discard() {
+        let mut session = Session::new(mpsc::channel(16).0);
+
+        assert_eq!(
+            Discard.execute_in(&mut session),
+            Response::err("ERR", "DISCARD without MULTI")
+        );
+
+        session.begin_transaction();
+        assert_eq!(Discard.execute_in(&mut session), Response::ss("OK"));
+        assert!(!session.in_transaction());
+    }
+}
@@ -0,0 +1,130 @@
+/// This module contains the implementation of the `Dump` command.
+/// The `Dump` command serializes the value of a key into an opaque blob,
+/// which `RESTORE` can turn back into a key, possibly on another server.
+///
+/// The blob is a version byte, the value as encoded in snapshots, and a checksum of both,
+/// hex-encoded so that it can travel as text.
+use std::fmt::Write;
+
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// The version of the serialization format, to be bumped whenever it changes.
+const DUMP_VERSION: u8 = 1;
+
+/// The length of the checksum trailing a blob, in bytes.
+const CHECKSUM_LEN: usize = 8;
+
+/// Represents the `Dump` command.
+pub struct Dump {
+    key: String,
+}
+
+impl ExecuteOn for Dump {
+    /// Executes the `Dump` command and returns the serialized value, or null if the key does not exist.
+    fn execute_on(self, store: &KvStore) -> Response {
+        store.dump(&self.key).map_or(Response::Null, |payload| {
+            Response::BulkString(encode(&payload).into())
+        })
+    }
+}
+
+/// Wraps a serialized value into a blob with a version and a checksum.
+pub fn encode(payload: &[u8]) -> String {
+    let mut blob = Vec::with_capacity(1 + payload.len() + CHECKSUM_LEN);
+    blob.push(DUMP_VERSION);
+    blob.extend_from_slice(payload);
+    blob.extend_from_slice(&checksum(&blob).to_le_bytes());
+
+    blob.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+/// Unwraps the serialized value from a blob made by `encode`.
+///
+/// # Returns
+///
+/// The serialized value, or `None` if the blob is malformed,
+/// of another version, or fails its checksum.
+pub fn decode(serialized: &str) -> Option<Vec<u8>> {
+    if !serialized.len().is_multiple_of(2) {
+        return None;
+    }
+    let blob = (0..serialized.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(serialized.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    let (content, sum) = blob.split_last_chunk::<CHECKSUM_LEN>()?;
+    let (&version, payload) = content.split_first()?;
+    (version == DUMP_VERSION && checksum(content) == u64::from_le_bytes(*sum))
+        .then(|| payload.to_vec())
+}
+
+/// Computes the 64-bit FNV-1a hash of some bytes.
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Builder for the `Dump` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key to serialize.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `Dump` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<Dump, ArgumentError> {
+        Ok(Dump {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let blob = encode(b"\x00payload");
+        assert_eq!(decode(&blob), Some(b"\x00payload".to_vec()));
+
+        // a flipped bit fails the checksum
+        let corrupted = format!(
+            "{}{}",
+            &blob[..4],
+            if &blob[4..5] == "0" { "1" } else { "0" }
+        );
+        assert_eq!(decode(&(corrupted + &blob[5..])), None);
+        assert_eq!(decode(&blob[1..]), None);
+        assert_eq!(decode("not hex"), None);
+        assert_eq!(decode(""), None);
+    }
+
+    #[test]
+    fn test_dump_missing() {
+        let store = KvStore::new();
+        let cmd = Builder::new().key("dump:missing").build().unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Null);
+    }
+}
@@ -0,0 +1,78 @@
+/// This module contains the implementation of the `Exec` command.
+/// The `Exec` command executes the commands queued since `MULTI` in order,
+/// and replies with an array of their replies.
+/// If any command failed to be queued, the whole transaction is discarded instead.
+use crate::{response::types::Response, session::Session};
+
+use super::types::ExecuteInSession;
+
+/// Represents the `Exec` command.
+pub struct Exec;
+
+impl ExecuteInSession for Exec {
+    /// Executes the `Exec` command and returns the replies of the queued commands.
+    fn execute_in(self, session: &mut Session) -> Response {
+        match session.take_transaction() {
+            None => Response::err("ERR", "EXEC without MULTI"),
+            Some(transaction) if transaction.aborted => Response::err(
+                "EXECABORT",
+                "Transaction discarded because of previous errors.",
+            ),
+            Some(transaction) => Response::Array(
+                transaction
+                    .commands
+                    .into_iter()
+                    .map(|command| command.execute_in(session))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Builder for the `Exec` command.
+pub struct Builder;
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Builds the `Exec` command, which takes no arguments.
+    #[allow(clippy::unused_self)] // consumed like every other builder
+    pub const fn build(self) -> Exec {
+        Exec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_exec_without_multi() {
+        let mut session = Session::new(mpsc::channel(16).0);
+
+        assert_eq!(
+            Exec.execute_in(&mut session),
+            Response::err("ERR", "EXEC without MULTI")
+        );
+    }
+
+    #[test]
+    fn test_exec_aborted() {
+        let mut session = Session::new(mpsc::channel(16).0);
+        session.begin_transaction();
+        session.abort_transaction();
+
+        assert_eq!(
+            Exec.execute_in(&mut session),
+            Response::err(
+                "EXECABORT",
+                "Transaction discarded because of previous errors."
+            )
+        );
+        assert!(!session.in_transaction());
+    }
+}
@@ -1,8 +1,8 @@
 /// This module contains the implementation of the `Exists` command.
 /// The `Exists` command checks if a key exists in the key-value store.
 /// It implements the `Execute` trait and returns a `Response` indicating whether the key exists or not.
-use super::types::{ArgumentError, Execute};
-use crate::kvstore::KV_STORE;
+use super::types::{ArgumentError, ExecuteOn};
+use crate::kvstore::KvStore;
 use crate::response::types::Response;
 
 /// Represents the `Exists` command.
@@ -10,10 +10,10 @@ pub struct Exists {
     key: String,
 }
 
-impl Execute for Exists {
+impl ExecuteOn for Exists {
     /// Executes the `Exists` command and returns a `Response`.
-    fn execute(self) -> Response {
-        Response::ss(&u64::from(KV_STORE.exists(&self.key)).to_string())
+    fn execute_on(self, store: &KvStore) -> Response {
+        Response::ss(&u64::from(store.exists(&self.key)).to_string())
     }
 }
 
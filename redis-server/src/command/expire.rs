@@ -0,0 +1,116 @@
+/// This module contains the implementation of the `Expire` command.
+/// The `Expire` command sets a key to expire after a number of seconds (`EXPIRE`)
+/// or milliseconds (`PEXPIRE`). A non-positive time to live deletes the key right away.
+use std::time::Duration;
+
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `Expire` command, covering both `EXPIRE` and `PEXPIRE`.
+pub struct Expire {
+    key: String,
+    ttl: i64,
+    millis: bool,
+}
+
+impl ExecuteOn for Expire {
+    /// Executes the `Expire` command and returns `1` if the expiry was set, `0` otherwise.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let applied = match u64::try_from(self.ttl) {
+            Ok(ttl) if ttl > 0 => store.expire(
+                &self.key,
+                if self.millis {
+                    Duration::from_millis(ttl)
+                } else {
+                    Duration::from_secs(ttl)
+                },
+            ),
+            _ => store.del(&self.key),
+        };
+
+        Response::Integer(applied.into())
+    }
+}
+
+/// Builder for the `Expire` command.
+pub struct Builder {
+    key: Option<String>,
+    ttl: Option<String>,
+    millis: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `EXPIRE`.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            ttl: None,
+            millis: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `PEXPIRE`.
+    pub const fn new_millis() -> Self {
+        Self {
+            key: None,
+            ttl: None,
+            millis: true,
+        }
+    }
+
+    /// Sets the key for the `Expire` command.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the time to live, in seconds for `EXPIRE` or milliseconds for `PEXPIRE`.
+    pub fn ttl(mut self, ttl: &str) -> Self {
+        self.ttl = Some(ttl.into());
+        self
+    }
+
+    /// Builds the `Expire` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key or time to live are not provided,
+    /// or an `ArgumentError::Integer` if the time to live is not an integer.
+    pub fn build(self) -> Result<Expire, ArgumentError> {
+        Ok(Expire {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            ttl: self
+                .ttl
+                .ok_or(ArgumentError::Missing)?
+                .parse()
+                .map_err(|_| ArgumentError::Integer)?,
+            millis: self.millis,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expire_non_positive_deletes() {
+        let store = KvStore::new();
+        store.set("expire:negative", "value");
+
+        let cmd = Builder::new()
+            .key("expire:negative")
+            .ttl("-1")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+        assert!(!store.exists("expire:negative"));
+    }
+
+    #[test]
+    fn test_expire_invalid_ttl() {
+        let result = Builder::new().key("expire:invalid").ttl("soon").build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
@@ -0,0 +1,175 @@
+/// This module contains the implementation of the `ExpireAt` command.
+/// The `ExpireAt` command sets a key to expire at an absolute Unix time, in seconds (`EXPIREAT`)
+/// or milliseconds (`PEXPIREAT`). A time already in the past deletes the key right away.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `ExpireAt` command, covering both `EXPIREAT` and `PEXPIREAT`.
+pub struct ExpireAt {
+    key: String,
+    timestamp: i64,
+    millis: bool,
+}
+
+impl ExpireAt {
+    /// Converts the timestamp into the time left until it, or `None` if it has already passed.
+    fn remaining(&self) -> Option<Duration> {
+        let timestamp = u64::try_from(self.timestamp).ok()?;
+        let expires_at = if self.millis {
+            Duration::from_millis(timestamp)
+        } else {
+            Duration::from_secs(timestamp)
+        };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+
+        expires_at
+            .checked_sub(now)
+            .filter(|remaining| !remaining.is_zero())
+    }
+}
+
+impl ExecuteOn for ExpireAt {
+    /// Executes the `ExpireAt` command and returns `1` if the expiry was set, `0` otherwise.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let applied = match self.remaining() {
+            Some(ttl) => store.expire(&self.key, ttl),
+            None => store.del(&self.key),
+        };
+
+        Response::Integer(applied.into())
+    }
+}
+
+/// Builder for the `ExpireAt` command.
+pub struct Builder {
+    key: Option<String>,
+    timestamp: Option<String>,
+    millis: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `EXPIREAT`.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            timestamp: None,
+            millis: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `PEXPIREAT`.
+    pub const fn new_millis() -> Self {
+        Self {
+            key: None,
+            timestamp: None,
+            millis: true,
+        }
+    }
+
+    /// Sets the key for the `ExpireAt` command.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the Unix time to expire at, in seconds for `EXPIREAT` or milliseconds for `PEXPIREAT`.
+    pub fn timestamp(mut self, timestamp: &str) -> Self {
+        self.timestamp = Some(timestamp.into());
+        self
+    }
+
+    /// Builds the `ExpireAt` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key or timestamp are not provided,
+    /// or an `ArgumentError::Integer` if the timestamp is not an integer.
+    pub fn build(self) -> Result<ExpireAt, ArgumentError> {
+        Ok(ExpireAt {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            timestamp: self
+                .timestamp
+                .ok_or(ArgumentError::Missing)?
+                .parse()
+                .map_err(|_| ArgumentError::Integer)?,
+            millis: self.millis,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kvstore::Expiry;
+
+    fn unix_now() -> Duration {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap()
+    }
+
+    #[test]
+    fn test_expireat_future() {
+        let store = KvStore::new();
+        store.set("expireat:future", "value");
+
+        let timestamp = (unix_now() + Duration::from_secs(100))
+            .as_secs()
+            .to_string();
+        let cmd = Builder::new()
+            .key("expireat:future")
+            .timestamp(&timestamp)
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+        assert!(matches!(
+            store.ttl("expireat:future"),
+            Some(Expiry::In(ttl)) if ttl > Duration::from_secs(98) && ttl <= Duration::from_secs(100)
+        ));
+
+        let timestamp = (unix_now() + Duration::from_secs(100))
+            .as_millis()
+            .to_string();
+        let cmd = Builder::new_millis()
+            .key("expireat:missing")
+            .timestamp(&timestamp)
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(0));
+    }
+
+    #[test]
+    fn test_expireat_past_deletes() {
+        let store = KvStore::new();
+        store.set("expireat:past", "value");
+
+        let timestamp = unix_now()
+            .saturating_sub(Duration::from_secs(1))
+            .as_millis()
+            .to_string();
+        let cmd = Builder::new_millis()
+            .key("expireat:past")
+            .timestamp(&timestamp)
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+        assert!(!store.exists("expireat:past"));
+
+        let cmd = Builder::new()
+            .key("expireat:past")
+            .timestamp("-1")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(0));
+    }
+
+    #[test]
+    fn test_expireat_invalid_timestamp() {
+        let result = Builder::new()
+            .key("expireat:invalid")
+            .timestamp("tomorrow")
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
@@ -1,8 +1,8 @@
 // Import necessary modules
-use crate::{kvstore::KV_STORE, response::types::Response};
+use crate::{kvstore::KvStore, response::types::Response};
 
 // Import types from the same module
-use super::types::{ArgumentError, Execute};
+use super::types::{ArgumentError, ExecuteOn};
 
 // Define a struct for the Get command
 pub struct Get {
@@ -10,15 +10,23 @@ pub struct Get {
 }
 
 // Implement the Execute trait for the Get command
-impl Execute for Get {
+impl ExecuteOn for Get {
     // Define the execute method for the Get command
-    fn execute(self) -> Response {
-        // Use the KV_STORE to get the value associated with the key
+    fn execute_on(self, store: &KvStore) -> Response {
+        // Use the store to get the value associated with the key
         // If the key is not found, return a Null response
-        // Otherwise, return a response with the value
-        KV_STORE
-            .get(&self.key)
-            .map_or(Response::Null, |value| Response::ss(&value))
+        // If the key holds a hash, return a WRONGTYPE error
+        // Otherwise, return a response with the value,
+        // as a bulk string if its bytes are not valid UTF-8, such as after SETBIT,
+        // or if it spans several lines, which a simple string cannot
+        match store.get(&self.key) {
+            Ok(value) => value.map_or(Response::Null, |value| match String::from_utf8(value) {
+                Ok(value) if !value.contains(['\r', '\n']) => Response::ss(&value),
+                Ok(value) => Response::BulkString(value.into_bytes()),
+                Err(error) => Response::BulkString(error.into_bytes()),
+            }),
+            Err(error) => Response::from(error),
+        }
     }
 }
 
@@ -0,0 +1,111 @@
+/// This module contains the implementation of the `GetBit` command.
+/// The `GetBit` command replies with a bit of a string, or `0` if it is beyond the string.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::{
+    setbit::MAX_BITS,
+    types::{ArgumentError, ExecuteOn},
+};
+
+/// Represents the `GetBit` command.
+pub struct GetBit {
+    key: String,
+    offset: usize,
+}
+
+impl ExecuteOn for GetBit {
+    /// Executes the `GetBit` command and returns the value of the bit.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.getbit(&self.key, self.offset) {
+            Ok(bit) => Response::Integer(bit.into()),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `GetBit` command.
+pub struct Builder {
+    key: Option<String>,
+    offset: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            offset: None,
+        }
+    }
+
+    /// Sets the key of the string.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the offset of the bit.
+    pub fn offset(mut self, offset: &str) -> Self {
+        self.offset = Some(offset.into());
+        self
+    }
+
+    /// Builds the `GetBit` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::BitOffset` if the offset is not a non-negative integer
+    /// within the largest string.
+    pub fn build(self) -> Result<GetBit, ArgumentError> {
+        let offset = self
+            .offset
+            .ok_or(ArgumentError::Missing)?
+            .parse()
+            .ok()
+            .filter(|&offset| offset < MAX_BITS)
+            .ok_or(ArgumentError::BitOffset)?;
+
+        Ok(GetBit {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            offset,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn getbit(store: &KvStore, key: &str, offset: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .offset(offset)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_getbit() {
+        let store = KvStore::new();
+        store.setbit("getbit:bits", 7, true).unwrap();
+        store.setbit("getbit:bits", 100, true).unwrap();
+
+        assert_eq!(getbit(&store, "getbit:bits", "7"), Response::Integer(1));
+        assert_eq!(getbit(&store, "getbit:bits", "100"), Response::Integer(1));
+        assert_eq!(getbit(&store, "getbit:bits", "6"), Response::Integer(0));
+        assert_eq!(getbit(&store, "getbit:bits", "1000"), Response::Integer(0));
+        assert_eq!(getbit(&store, "getbit:missing", "0"), Response::Integer(0));
+    }
+
+    #[test]
+    fn test_getbit_wrong_type() {
+        let store = KvStore::new();
+        store.sadd("getbit:set", &["a".into()]).unwrap();
+        assert_eq!(
+            getbit(&store, "getbit:set", "0"),
+            Response::from(crate::kvstore::WrongType)
+        );
+    }
+}
@@ -0,0 +1,148 @@
+/// This module contains the implementation of the `GetRange` command.
+/// The `GetRange` command returns the bytes of a string between two offsets, both inclusive,
+/// where negative offsets count from the end of the string.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `GetRange` command.
+pub struct GetRange {
+    key: String,
+    start: i64,
+    end: i64,
+}
+
+impl ExecuteOn for GetRange {
+    /// Executes the `GetRange` command and returns the bytes in the range.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.getrange(&self.key, self.start, self.end) {
+            Ok(range) => Response::BulkString(range),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `GetRange` command.
+pub struct Builder {
+    key: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            start: None,
+            end: None,
+        }
+    }
+
+    /// Sets the key of the string.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the offset of the first byte.
+    pub fn start(mut self, start: &str) -> Self {
+        self.start = Some(start.into());
+        self
+    }
+
+    /// Sets the offset of the last byte.
+    pub fn end(mut self, end: &str) -> Self {
+        self.end = Some(end.into());
+        self
+    }
+
+    /// Builds the `GetRange` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::Integer` if an offset is not an integer.
+    pub fn build(self) -> Result<GetRange, ArgumentError> {
+        let offset = |offset: Option<String>| {
+            offset
+                .ok_or(ArgumentError::Missing)?
+                .parse()
+                .map_err(|_| ArgumentError::Integer)
+        };
+
+        Ok(GetRange {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            start: offset(self.start)?,
+            end: offset(self.end)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn getrange(store: &KvStore, key: &str, start: &str, end: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .start(start)
+            .end(end)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_getrange() {
+        let store = KvStore::new();
+        store.set("getrange:key", "This is a string");
+
+        assert_eq!(
+            getrange(&store, "getrange:key", "0", "3"),
+            Response::bs("This")
+        );
+        assert_eq!(
+            getrange(&store, "getrange:key", "-3", "-1"),
+            Response::bs("ing")
+        );
+        assert_eq!(
+            getrange(&store, "getrange:key", "0", "-1"),
+            Response::bs("This is a string")
+        );
+        assert_eq!(
+            getrange(&store, "getrange:key", "10", "100"),
+            Response::bs("string")
+        );
+        assert_eq!(getrange(&store, "getrange:key", "5", "3"), Response::bs(""));
+        assert_eq!(
+            getrange(&store, "getrange:missing", "0", "-1"),
+            Response::bs("")
+        );
+    }
+
+    #[test]
+    fn test_getrange_bytes() {
+        let store = KvStore::new();
+        store.set("getrange:bytes", "héllo");
+        assert_eq!(
+            getrange(&store, "getrange:bytes", "3", "-1"),
+            Response::bs("llo")
+        );
+    }
+
+    #[test]
+    fn test_getrange_splits_character() {
+        let store = KvStore::new();
+        // "é" is the two bytes C3 A9
+        store.set("getrange:split", "é");
+        assert_eq!(
+            getrange(&store, "getrange:split", "0", "0"),
+            Response::BulkString(vec![0xc3])
+        );
+        assert_eq!(
+            getrange(&store, "getrange:split", "1", "1"),
+            Response::BulkString(vec![0xa9])
+        );
+    }
+}
@@ -0,0 +1,113 @@
+/// This module contains the implementation of the `HDel` command.
+/// The `HDel` command removes fields from a hash, removing the hash once no fields remain,
+/// and replies with the number of fields that were removed.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HDel` command.
+pub struct HDel {
+    key: String,
+    fields: Vec<String>,
+}
+
+impl ExecuteOn for HDel {
+    /// Executes the `HDel` command and returns the number of removed fields.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let mut removed = 0;
+        for field in &self.fields {
+            match store.hdel(&self.key, field) {
+                Ok(true) => removed += 1,
+                Ok(false) => {}
+                Err(error) => return Response::from(error),
+            }
+        }
+
+        Response::Integer(removed)
+    }
+}
+
+/// Builder for the `HDel` command.
+pub struct Builder {
+    key: Option<String>,
+    fields: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            fields: None,
+        }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the fields to remove.
+    pub fn fields(mut self, fields: &[String]) -> Self {
+        self.fields = Some(fields.to_vec());
+        self
+    }
+
+    /// Builds the `HDel` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key or fields are not provided.
+    pub fn build(self) -> Result<HDel, ArgumentError> {
+        let fields = self.fields.ok_or(ArgumentError::Missing)?;
+        if fields.is_empty() {
+            return Err(ArgumentError::Missing);
+        }
+
+        Ok(HDel {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            fields,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hdel(store: &KvStore, key: &str, fields: &[&str]) -> Response {
+        let fields: Vec<String> = fields.iter().map(ToString::to_string).collect();
+        Builder::new()
+            .key(key)
+            .fields(&fields)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_hdel() {
+        let store = KvStore::new();
+        store.hset("hdel:hash", "a", "1").unwrap();
+        store.hset("hdel:hash", "b", "2").unwrap();
+
+        assert_eq!(
+            hdel(&store, "hdel:hash", &["a", "missing"]),
+            Response::Integer(1)
+        );
+        assert_eq!(hdel(&store, "hdel:hash", &["b"]), Response::Integer(1));
+        assert!(!store.exists("hdel:hash"));
+        assert_eq!(hdel(&store, "hdel:hash", &["a"]), Response::Integer(0));
+    }
+
+    #[test]
+    fn test_hdel_wrong_type() {
+        let store = KvStore::new();
+        store.set("hdel:string", "value");
+        assert!(matches!(
+            hdel(&store, "hdel:string", &["a"]),
+            Response::Error(_)
+        ));
+    }
+}
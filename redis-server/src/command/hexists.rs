@@ -0,0 +1,98 @@
+/// This module contains the implementation of the `HExists` command.
+/// The `HExists` command returns `1` if a field exists in a hash, or `0` if the hash
+/// or field does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HExists` command.
+pub struct HExists {
+    key: String,
+    field: String,
+}
+
+impl ExecuteOn for HExists {
+    /// Executes the `HExists` command and returns whether the field exists.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.hexists(&self.key, &self.field) {
+            Ok(exists) => Response::Integer(i64::from(exists)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `HExists` command.
+pub struct Builder {
+    key: Option<String>,
+    field: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            field: None,
+        }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the field to look up.
+    pub fn field(mut self, field: &str) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// Builds the `HExists` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided.
+    pub fn build(self) -> Result<HExists, ArgumentError> {
+        Ok(HExists {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            field: self.field.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hexists(store: &KvStore, key: &str, field: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .field(field)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_hexists() {
+        let store = KvStore::new();
+        store.hset("hexists:hash", "a", "1").unwrap();
+        assert_eq!(hexists(&store, "hexists:hash", "a"), Response::Integer(1));
+        assert_eq!(hexists(&store, "hexists:hash", "b"), Response::Integer(0));
+        assert_eq!(
+            hexists(&store, "hexists:missing", "a"),
+            Response::Integer(0)
+        );
+    }
+
+    #[test]
+    fn test_hexists_wrong_type() {
+        let store = KvStore::new();
+        store.set("hexists:string", "value");
+        assert!(matches!(
+            hexists(&store, "hexists:string", "a"),
+            Response::Error(_)
+        ));
+    }
+}
@@ -0,0 +1,214 @@
+/// This module contains the implementation of the `HExpire` command.
+/// The `HExpire` command sets fields of a hash to expire after a number of seconds,
+/// replying for each field with `1` if the expiry was set, `2` if a time to live of `0`
+/// deleted the field right away, or `-2` if the field does not exist.
+use std::time::Duration;
+
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HExpire` command.
+pub struct HExpire {
+    key: String,
+    ttl: u64,
+    fields: Vec<String>,
+}
+
+impl ExecuteOn for HExpire {
+    /// Executes the `HExpire` command and returns the outcome for each field.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let mut replies = Vec::with_capacity(self.fields.len());
+        for field in &self.fields {
+            let applied = if self.ttl == 0 {
+                store.hdel(&self.key, field).map(|deleted| (deleted, 2))
+            } else {
+                store
+                    .hexpire(&self.key, field, Duration::from_secs(self.ttl))
+                    .map(|set| (set, 1))
+            };
+
+            match applied {
+                Ok((true, reply)) => replies.push(Response::Integer(reply)),
+                Ok((false, _)) => replies.push(Response::Integer(-2)),
+                Err(error) => return Response::from(error),
+            }
+        }
+
+        Response::Array(replies)
+    }
+}
+
+/// Parses the `FIELDS numfields field...` arguments shared by the hash field expiry commands.
+///
+/// # Errors
+///
+/// Returns an `ArgumentError::Syntax` if the `FIELDS` keyword is missing or the number of fields
+/// does not match, or an `ArgumentError::Integer` if the number of fields is not an integer.
+pub fn parse_fields(args: &[String]) -> Result<Vec<String>, ArgumentError> {
+    let [keyword, count, fields @ ..] = args else {
+        return Err(ArgumentError::Syntax);
+    };
+
+    if !keyword.eq_ignore_ascii_case("FIELDS") {
+        return Err(ArgumentError::Syntax);
+    }
+
+    let count: usize = count.parse().map_err(|_| ArgumentError::Integer)?;
+    if count == 0 || count != fields.len() {
+        return Err(ArgumentError::Syntax);
+    }
+
+    Ok(fields.to_vec())
+}
+
+/// Builder for the `HExpire` command.
+pub struct Builder {
+    key: Option<String>,
+    ttl: Option<String>,
+    fields: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            ttl: None,
+            fields: None,
+        }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the time to live in seconds.
+    pub fn ttl(mut self, ttl: &str) -> Self {
+        self.ttl = Some(ttl.into());
+        self
+    }
+
+    /// Sets the `FIELDS numfields field...` arguments.
+    pub fn fields(mut self, args: &[String]) -> Self {
+        self.fields = Some(args.to_vec());
+        self
+    }
+
+    /// Builds the `HExpire` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// an `ArgumentError::Integer` if the time to live is not a non-negative integer,
+    /// or the error of `parse_fields` if the fields are malformed.
+    pub fn build(self) -> Result<HExpire, ArgumentError> {
+        Ok(HExpire {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            ttl: self
+                .ttl
+                .ok_or(ArgumentError::Missing)?
+                .parse()
+                .map_err(|_| ArgumentError::Integer)?,
+            fields: parse_fields(&self.fields.ok_or(ArgumentError::Missing)?)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::httl;
+    use std::thread;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_hexpire_reported_by_httl() {
+        let store = KvStore::new();
+        store.hset("hexpire:ttl", "a", "1").unwrap();
+
+        let cmd = Builder::new()
+            .key("hexpire:ttl")
+            .ttl("100")
+            .fields(&args(&["FIELDS", "2", "a", "missing"]))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.execute_on(&store),
+            Response::Array(vec![Response::Integer(1), Response::Integer(-2)])
+        );
+
+        let cmd = httl::Builder::new()
+            .key("hexpire:ttl")
+            .fields(&args(&["FIELDS", "1", "a"]))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.execute_on(&store),
+            Response::Array(vec![Response::Integer(100)])
+        );
+    }
+
+    #[test]
+    fn test_hexpire_field_expires_alone() {
+        let store = KvStore::new();
+        store.hset("hexpire:lazy", "short", "1").unwrap();
+        store.hset("hexpire:lazy", "long", "2").unwrap();
+        assert!(store
+            .hexpire("hexpire:lazy", "short", Duration::from_millis(10))
+            .unwrap());
+
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(store.hget("hexpire:lazy", "short"), Ok(None));
+        assert_eq!(store.hget("hexpire:lazy", "long"), Ok(Some("2".into())));
+        assert_eq!(
+            store.hgetall("hexpire:lazy"),
+            Ok(vec![("long".into(), "2".into())])
+        );
+    }
+
+    #[test]
+    fn test_hexpire_zero_deletes_field() {
+        let store = KvStore::new();
+        store.hset("hexpire:zero", "a", "1").unwrap();
+
+        let cmd = Builder::new()
+            .key("hexpire:zero")
+            .ttl("0")
+            .fields(&args(&["FIELDS", "1", "a"]))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.execute_on(&store),
+            Response::Array(vec![Response::Integer(2)])
+        );
+        assert!(!store.exists("hexpire:zero"));
+    }
+
+    #[test]
+    fn test_hexpire_wrong_type() {
+        let store = KvStore::new();
+        store.set("hexpire:string", "value");
+
+        let cmd = Builder::new()
+            .key("hexpire:string")
+            .ttl("10")
+            .fields(&args(&["FIELDS", "1", "a"]))
+            .build()
+            .unwrap();
+        assert!(matches!(cmd.execute_on(&store), Response::Error(_)));
+    }
+
+    #[test]
+    fn test_parse_fields_count_mismatch() {
+        assert!(matches!(
+            parse_fields(&args(&["FIELDS", "2", "a"])),
+            Err(ArgumentError::Syntax)
+        ));
+    }
+}
@@ -0,0 +1,96 @@
+/// This module contains the implementation of the `HGet` command.
+/// The `HGet` command returns the value of a field of a hash,
+/// or null if the hash or field does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HGet` command.
+pub struct HGet {
+    key: String,
+    field: String,
+}
+
+impl ExecuteOn for HGet {
+    /// Executes the `HGet` command and returns the value of the field.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.hget(&self.key, &self.field) {
+            Ok(value) => value.map_or(Response::Null, |value| Response::bs(&value)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `HGet` command.
+pub struct Builder {
+    key: Option<String>,
+    field: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            field: None,
+        }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the field to look up.
+    pub fn field(mut self, field: &str) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// Builds the `HGet` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided.
+    pub fn build(self) -> Result<HGet, ArgumentError> {
+        Ok(HGet {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            field: self.field.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hget(store: &KvStore, key: &str, field: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .field(field)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_hget() {
+        let store = KvStore::new();
+        store.hset("hget:hash", "a", "1").unwrap();
+
+        assert_eq!(hget(&store, "hget:hash", "a"), Response::bs("1"));
+        assert_eq!(hget(&store, "hget:hash", "missing"), Response::Null);
+        assert_eq!(hget(&store, "hget:missing", "a"), Response::Null);
+    }
+
+    #[test]
+    fn test_hget_wrong_type() {
+        let store = KvStore::new();
+        store.set("hget:string", "value");
+        assert!(matches!(
+            hget(&store, "hget:string", "a"),
+            Response::Error(_)
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+/// This module contains the implementation of the `HGetAll` command.
+/// The `HGetAll` command returns the fields of a hash and their values as a flat array
+/// of alternating fields and values, in no particular order,
+/// or an empty array if the hash does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HGetAll` command.
+pub struct HGetAll {
+    key: String,
+}
+
+impl ExecuteOn for HGetAll {
+    /// Executes the `HGetAll` command and returns the fields and values of the hash.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.hgetall(&self.key) {
+            Ok(pairs) => Response::arr(
+                pairs
+                    .into_iter()
+                    .flat_map(|(field, value)| [field, value])
+                    .collect(),
+            ),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `HGetAll` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `HGetAll` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<HGetAll, ArgumentError> {
+        Ok(HGetAll {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hgetall(store: &KvStore, key: &str) -> Response {
+        Builder::new().key(key).build().unwrap().execute_on(store)
+    }
+
+    #[test]
+    fn test_hgetall_pairs_stay_together() {
+        let store = KvStore::new();
+        for (field, value) in [("a", "1"), ("b", "2"), ("c", "3")] {
+            store.hset("hgetall:hash", field, value).unwrap();
+        }
+
+        // fields come in no particular order, but each is immediately followed by its value
+        let Response::Array(reply) = hgetall(&store, "hgetall:hash") else {
+            panic!("HGETALL should reply with an array");
+        };
+        assert_eq!(reply.len(), 6);
+        for pair in reply.chunks_exact(2) {
+            let (Response::BulkString(field), Response::BulkString(value)) = (&pair[0], &pair[1])
+            else {
+                panic!("HGETALL should reply with bulk strings");
+            };
+            assert_eq!(
+                value.as_slice(),
+                match field.as_slice() {
+                    b"a" => b"1",
+                    b"b" => b"2",
+                    _ => b"3",
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_hgetall_missing() {
+        let store = KvStore::new();
+        assert_eq!(hgetall(&store, "hgetall:missing"), Response::Array(vec![]));
+    }
+
+    #[test]
+    fn test_hgetall_wrong_type() {
+        let store = KvStore::new();
+        store.set("hgetall:string", "value");
+        assert!(matches!(
+            hgetall(&store, "hgetall:string"),
+            Response::Error(_)
+        ));
+    }
+}
@@ -0,0 +1,146 @@
+/// This module contains the implementation of the `HIncrBy` command.
+/// The `HIncrBy` command adds an integer increment to the number stored in a field of a hash,
+/// treating a missing field as `0`, and replies with the new value.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HIncrBy` command.
+pub struct HIncrBy {
+    key: String,
+    field: String,
+    increment: i64,
+}
+
+impl ExecuteOn for HIncrBy {
+    /// Executes the `HIncrBy` command and returns the new value as an integer.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.hincrby(&self.key, &self.field, self.increment) {
+            Ok(Some(value)) => Response::Integer(value),
+            Ok(None) => Response::from(ArgumentError::Integer),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `HIncrBy` command.
+pub struct Builder {
+    key: Option<String>,
+    field: Option<String>,
+    increment: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            field: None,
+            increment: None,
+        }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the field holding the number.
+    pub fn field(mut self, field: &str) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// Sets the amount to add.
+    pub fn increment(mut self, increment: &str) -> Self {
+        self.increment = Some(increment.into());
+        self
+    }
+
+    /// Builds the `HIncrBy` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::Integer` if the increment is not an integer.
+    pub fn build(self) -> Result<HIncrBy, ArgumentError> {
+        let increment = self
+            .increment
+            .ok_or(ArgumentError::Missing)?
+            .parse()
+            .map_err(|_| ArgumentError::Integer)?;
+
+        Ok(HIncrBy {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            field: self.field.ok_or(ArgumentError::Missing)?,
+            increment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hincrby(store: &KvStore, key: &str, field: &str, increment: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .field(field)
+            .increment(increment)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_hincrby_missing_field() {
+        let store = KvStore::new();
+        assert_eq!(
+            hincrby(&store, "hincrby:hash", "counter", "5"),
+            Response::Integer(5)
+        );
+        assert_eq!(
+            hincrby(&store, "hincrby:hash", "counter", "-7"),
+            Response::Integer(-2)
+        );
+        assert_eq!(store.hget("hincrby:hash", "counter"), Ok(Some("-2".into())));
+    }
+
+    #[test]
+    fn test_hincrby_not_an_integer() {
+        let store = KvStore::new();
+        store.hset("hincrby:text", "field", "abc").unwrap();
+        assert_eq!(
+            hincrby(&store, "hincrby:text", "field", "1"),
+            Response::err("ERR", "value is not an integer or out of range")
+        );
+        assert_eq!(store.hget("hincrby:text", "field"), Ok(Some("abc".into())));
+
+        store
+            .hset("hincrby:overflow", "field", &i64::MAX.to_string())
+            .unwrap();
+        assert_eq!(
+            hincrby(&store, "hincrby:overflow", "field", "1"),
+            Response::from(ArgumentError::Integer)
+        );
+
+        let result = Builder::new()
+            .key("hincrby:bad")
+            .field("field")
+            .increment("1.5")
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+        assert_eq!(store.hget("hincrby:bad", "field"), Ok(None));
+    }
+
+    #[test]
+    fn test_hincrby_wrong_type() {
+        let store = KvStore::new();
+        store.set("hincrby:string", "1");
+        assert!(matches!(
+            hincrby(&store, "hincrby:string", "field", "1"),
+            Response::Error(_)
+        ));
+    }
+}
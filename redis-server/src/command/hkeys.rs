@@ -0,0 +1,80 @@
+/// This module contains the implementation of the `HKeys` command.
+/// The `HKeys` command returns the fields of a hash in no particular order, though in the same
+/// order as `HVals`, or an empty array if the hash does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HKeys` command.
+pub struct HKeys {
+    key: String,
+}
+
+impl ExecuteOn for HKeys {
+    /// Executes the `HKeys` command and returns the fields of the hash.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.hkeys(&self.key) {
+            Ok(fields) => Response::arr(fields),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `HKeys` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `HKeys` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<HKeys, ArgumentError> {
+        Ok(HKeys {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hkeys(store: &KvStore, key: &str) -> Response {
+        Builder::new().key(key).build().unwrap().execute_on(store)
+    }
+
+    #[test]
+    fn test_hkeys() {
+        let store = KvStore::new();
+        store.hset("hkeys:hash", "a", "1").unwrap();
+        store.hset("hkeys:hash", "b", "2").unwrap();
+
+        let Response::Array(mut fields) = hkeys(&store, "hkeys:hash") else {
+            panic!("HKEYS should reply with an array");
+        };
+        fields.sort_by_key(ToString::to_string);
+        assert_eq!(fields, vec![Response::bs("a"), Response::bs("b")]);
+        assert_eq!(hkeys(&store, "hkeys:missing"), Response::Array(vec![]));
+    }
+
+    #[test]
+    fn test_hkeys_wrong_type() {
+        let store = KvStore::new();
+        store.set("hkeys:string", "value");
+        assert!(matches!(hkeys(&store, "hkeys:string"), Response::Error(_)));
+    }
+}
@@ -0,0 +1,74 @@
+/// This module contains the implementation of the `HLen` command.
+/// The `HLen` command returns the number of fields of a hash, or `0` if it does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HLen` command.
+pub struct HLen {
+    key: String,
+}
+
+impl ExecuteOn for HLen {
+    /// Executes the `HLen` command and returns the number of fields of the hash.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.hlen(&self.key) {
+            Ok(len) => Response::Integer(i64::try_from(len).unwrap_or(i64::MAX)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `HLen` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `HLen` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<HLen, ArgumentError> {
+        Ok(HLen {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hlen(store: &KvStore, key: &str) -> Response {
+        Builder::new().key(key).build().unwrap().execute_on(store)
+    }
+
+    #[test]
+    fn test_hlen() {
+        let store = KvStore::new();
+        store.hset("hlen:hash", "a", "1").unwrap();
+        store.hset("hlen:hash", "b", "2").unwrap();
+        assert_eq!(hlen(&store, "hlen:hash"), Response::Integer(2));
+        assert_eq!(hlen(&store, "hlen:missing"), Response::Integer(0));
+    }
+
+    #[test]
+    fn test_hlen_wrong_type() {
+        let store = KvStore::new();
+        store.set("hlen:string", "value");
+        assert!(matches!(hlen(&store, "hlen:string"), Response::Error(_)));
+    }
+}
@@ -0,0 +1,120 @@
+/// This module contains the implementation of the `HMGet` command.
+/// The `HMGet` command returns the values of several fields of a hash in the order given,
+/// with null for each field that does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HMGet` command.
+pub struct HMGet {
+    key: String,
+    fields: Vec<String>,
+}
+
+impl ExecuteOn for HMGet {
+    /// Executes the `HMGet` command and returns the values of the fields.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.hmget(&self.key, &self.fields) {
+            Ok(values) => Response::Array(
+                values
+                    .into_iter()
+                    .map(|value| {
+                        value.map_or(Response::Null, |value| Response::BulkString(value.into()))
+                    })
+                    .collect(),
+            ),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `HMGet` command.
+pub struct Builder {
+    key: Option<String>,
+    fields: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            fields: None,
+        }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the fields to look up.
+    pub fn fields(mut self, fields: &[String]) -> Self {
+        self.fields = Some(fields.to_vec());
+        self
+    }
+
+    /// Builds the `HMGet` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key or fields are not provided.
+    pub fn build(self) -> Result<HMGet, ArgumentError> {
+        let fields = self.fields.ok_or(ArgumentError::Missing)?;
+        if fields.is_empty() {
+            return Err(ArgumentError::Missing);
+        }
+
+        Ok(HMGet {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            fields,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hmget(store: &KvStore, key: &str, fields: &[&str]) -> Response {
+        let fields: Vec<String> = fields.iter().map(ToString::to_string).collect();
+        Builder::new()
+            .key(key)
+            .fields(&fields)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_hmget() {
+        let store = KvStore::new();
+        store.hset("hmget:hash", "a", "1").unwrap();
+        store.hset("hmget:hash", "c", "3").unwrap();
+
+        assert_eq!(
+            hmget(&store, "hmget:hash", &["a", "b", "c", "a"]),
+            Response::Array(vec![
+                Response::bs("1"),
+                Response::Null,
+                Response::bs("3"),
+                Response::bs("1"),
+            ])
+        );
+        assert_eq!(
+            hmget(&store, "hmget:missing", &["a", "b"]),
+            Response::Array(vec![Response::Null, Response::Null])
+        );
+    }
+
+    #[test]
+    fn test_hmget_wrong_type() {
+        let store = KvStore::new();
+        store.set("hmget:string", "value");
+        assert!(matches!(
+            hmget(&store, "hmget:string", &["a"]),
+            Response::Error(_)
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+/// This module contains the implementation of the `HSet` command.
+/// The `HSet` command sets one or more fields of a hash, creating the hash if needed,
+/// and replies with the number of fields that were added rather than updated.
+/// The legacy `HMSET` does the same, but replies with `OK`.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HSet` command, covering both `HSET` and `HMSET`.
+pub struct HSet {
+    key: String,
+    pairs: Vec<(String, String)>,
+    reply_ok: bool,
+}
+
+impl ExecuteOn for HSet {
+    /// Executes the `HSet` command and returns the number of new fields, or `OK` for `HMSET`.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let mut added = 0;
+        for (field, value) in &self.pairs {
+            match store.hset(&self.key, field, value) {
+                Ok(true) => added += 1,
+                Ok(false) => {}
+                Err(error) => return Response::from(error),
+            }
+        }
+
+        if self.reply_ok {
+            Response::ss("OK")
+        } else {
+            Response::Integer(added)
+        }
+    }
+}
+
+/// Builder for the `HSet` command.
+pub struct Builder {
+    key: Option<String>,
+    pairs: Option<Vec<String>>,
+    reply_ok: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `HSET`.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            pairs: None,
+            reply_ok: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `HMSET`.
+    pub const fn new_hmset() -> Self {
+        Self {
+            key: None,
+            pairs: None,
+            reply_ok: true,
+        }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the `field value...` arguments.
+    pub fn pairs(mut self, args: &[String]) -> Self {
+        self.pairs = Some(args.to_vec());
+        self
+    }
+
+    /// Builds the `HSet` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::Syntax` if a field is not followed by a value.
+    pub fn build(self) -> Result<HSet, ArgumentError> {
+        let args = self.pairs.ok_or(ArgumentError::Missing)?;
+        if args.is_empty() || args.len() % 2 != 0 {
+            return Err(ArgumentError::Syntax);
+        }
+
+        Ok(HSet {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            pairs: args
+                .chunks_exact(2)
+                .map(|pair| (pair[0].clone(), pair[1].clone()))
+                .collect(),
+            reply_ok: self.reply_ok,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::{get, hgetall};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_hset_multiple_fields() {
+        let store = KvStore::new();
+        let cmd = Builder::new()
+            .key("hset:multi")
+            .pairs(&args(&["a", "1", "b", "2"]))
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(2));
+
+        // only `c` is new, while `a` is updated
+        let cmd = Builder::new()
+            .key("hset:multi")
+            .pairs(&args(&["a", "3", "c", "4"]))
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+
+        let cmd = hgetall::Builder::new().key("hset:multi").build().unwrap();
+        let Response::Array(reply) = cmd.execute_on(&store) else {
+            panic!("HGETALL should reply with an array");
+        };
+        let mut pairs: Vec<_> = reply.chunks_exact(2).map(<[_]>::to_vec).collect();
+        pairs.sort_by_key(|pair| format!("{pair:?}"));
+        assert_eq!(
+            pairs,
+            vec![
+                vec![Response::bs("a"), Response::bs("3")],
+                vec![Response::bs("b"), Response::bs("2")],
+                vec![Response::bs("c"), Response::bs("4")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hmset() {
+        let store = KvStore::new();
+        let cmd = Builder::new_hmset()
+            .key("hset:hmset")
+            .pairs(&args(&["a", "1", "b", "2"]))
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::ss("OK"));
+        assert_eq!(store.hget("hset:hmset", "a"), Ok(Some("1".into())));
+        assert_eq!(store.hget("hset:hmset", "b"), Ok(Some("2".into())));
+    }
+
+    #[test]
+    fn test_hset_odd_arguments() {
+        let result = Builder::new()
+            .key("hset:odd")
+            .pairs(&args(&["a", "1", "b"]))
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Syntax)));
+    }
+
+    #[test]
+    fn test_hset_wrong_type() {
+        let store = KvStore::new();
+        store.set("hset:string", "value");
+
+        let cmd = Builder::new()
+            .key("hset:string")
+            .pairs(&args(&["a", "1"]))
+            .build()
+            .unwrap();
+        assert!(matches!(cmd.execute_on(&store), Response::Error(_)));
+
+        store.hset("hset:hash", "a", "1").unwrap();
+        let cmd = get::Builder::new().key("hset:hash").build().unwrap();
+        assert!(matches!(cmd.execute_on(&store), Response::Error(_)));
+    }
+}
@@ -0,0 +1,100 @@
+/// This module contains the implementation of the `HTtl` command.
+/// The `HTtl` command returns, for each given field of a hash, its remaining time to live
+/// in seconds, `-1` if the field has no expiry, or `-2` if the field does not exist.
+use crate::{
+    kvstore::{Expiry, KvStore},
+    response::types::Response,
+};
+
+use super::{
+    hexpire::parse_fields,
+    types::{ArgumentError, ExecuteOn},
+};
+
+/// Represents the `HTtl` command.
+pub struct HTtl {
+    key: String,
+    fields: Vec<String>,
+}
+
+impl ExecuteOn for HTtl {
+    /// Executes the `HTtl` command and returns the remaining time to live of each field.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let mut replies = Vec::with_capacity(self.fields.len());
+        for field in &self.fields {
+            replies.push(Response::Integer(match store.httl(&self.key, field) {
+                Ok(None) => -2,
+                Ok(Some(Expiry::Persistent)) => -1,
+                // round partial seconds up, so a live field never reports a TTL of 0
+                Ok(Some(Expiry::In(ttl))) => {
+                    i64::try_from(ttl.as_millis().div_ceil(1_000)).unwrap_or(i64::MAX)
+                }
+                Err(error) => return Response::from(error),
+            }));
+        }
+
+        Response::Array(replies)
+    }
+}
+
+/// Builder for the `HTtl` command.
+pub struct Builder {
+    key: Option<String>,
+    fields: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            fields: None,
+        }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the `FIELDS numfields field...` arguments.
+    pub fn fields(mut self, args: &[String]) -> Self {
+        self.fields = Some(args.to_vec());
+        self
+    }
+
+    /// Builds the `HTtl` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or the error of `parse_fields` if the fields are malformed.
+    pub fn build(self) -> Result<HTtl, ArgumentError> {
+        Ok(HTtl {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            fields: parse_fields(&self.fields.ok_or(ArgumentError::Missing)?)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_httl_persistent_and_missing() {
+        let store = KvStore::new();
+        store.hset("httl:persistent", "a", "1").unwrap();
+
+        let cmd = Builder::new()
+            .key("httl:persistent")
+            .fields(&["FIELDS".into(), "2".into(), "a".into(), "b".into()])
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.execute_on(&store),
+            Response::Array(vec![Response::Integer(-1), Response::Integer(-2)])
+        );
+    }
+}
@@ -0,0 +1,94 @@
+/// This module contains the implementation of the `HVals` command.
+/// The `HVals` command returns the values of a hash in no particular order, though in the same
+/// order as `HKeys`, or an empty array if the hash does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `HVals` command.
+pub struct HVals {
+    key: String,
+}
+
+impl ExecuteOn for HVals {
+    /// Executes the `HVals` command and returns the values of the hash.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.hvals(&self.key) {
+            Ok(values) => Response::arr(values),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `HVals` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key of the hash.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `HVals` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<HVals, ArgumentError> {
+        Ok(HVals {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hvals(store: &KvStore, key: &str) -> Response {
+        Builder::new().key(key).build().unwrap().execute_on(store)
+    }
+
+    #[test]
+    fn test_hvals() {
+        let store = KvStore::new();
+        store.hset("hvals:hash", "a", "1").unwrap();
+        store.hset("hvals:hash", "b", "2").unwrap();
+
+        let Response::Array(mut values) = hvals(&store, "hvals:hash") else {
+            panic!("HVALS should reply with an array");
+        };
+        values.sort_by_key(ToString::to_string);
+        assert_eq!(values, vec![Response::bs("1"), Response::bs("2")]);
+        assert_eq!(hvals(&store, "hvals:missing"), Response::Array(vec![]));
+    }
+
+    #[test]
+    fn test_hvals_follow_hkeys() {
+        let store = KvStore::new();
+        for (field, value) in [("a", "1"), ("b", "2"), ("c", "3")] {
+            store.hset("hvals:order", field, value).unwrap();
+        }
+
+        let fields = store.hkeys("hvals:order").unwrap();
+        let values = store.hvals("hvals:order").unwrap();
+        for (field, value) in fields.iter().zip(&values) {
+            assert_eq!(store.hget("hvals:order", field), Ok(Some(value.clone())));
+        }
+    }
+
+    #[test]
+    fn test_hvals_wrong_type() {
+        let store = KvStore::new();
+        store.set("hvals:string", "value");
+        assert!(matches!(hvals(&store, "hvals:string"), Response::Error(_)));
+    }
+}
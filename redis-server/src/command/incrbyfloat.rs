@@ -0,0 +1,146 @@
+/// This module contains the implementation of the `IncrByFloat` command.
+/// The `IncrByFloat` command adds a floating-point increment to the number stored at a key,
+/// treating a missing key as `0`, and replies with the new value.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `IncrByFloat` command.
+pub struct IncrByFloat {
+    key: String,
+    increment: f64,
+}
+
+impl ExecuteOn for IncrByFloat {
+    /// Executes the `IncrByFloat` command and returns the new value as a bulk string.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.incrbyfloat(&self.key, self.increment) {
+            Ok(Some(value)) => Response::BulkString(value.into()),
+            Ok(None) => Response::from(ArgumentError::Float),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `IncrByFloat` command.
+pub struct Builder {
+    key: Option<String>,
+    increment: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            increment: None,
+        }
+    }
+
+    /// Sets the key of the number.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the amount to add.
+    pub fn increment(mut self, increment: &str) -> Self {
+        self.increment = Some(increment.into());
+        self
+    }
+
+    /// Builds the `IncrByFloat` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::Float` if the increment is not a finite number.
+    pub fn build(self) -> Result<IncrByFloat, ArgumentError> {
+        let increment: f64 = self
+            .increment
+            .ok_or(ArgumentError::Missing)?
+            .parse()
+            .map_err(|_| ArgumentError::Float)?;
+        if !increment.is_finite() {
+            return Err(ArgumentError::Float);
+        }
+
+        Ok(IncrByFloat {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            increment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn incrbyfloat(store: &KvStore, key: &str, increment: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .increment(increment)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_incrbyfloat_missing_key() {
+        let store = KvStore::new();
+        assert_eq!(
+            incrbyfloat(&store, "incrbyfloat:missing", "10.5"),
+            Response::bs("10.5")
+        );
+        assert_eq!(
+            incrbyfloat(&store, "incrbyfloat:missing", "0.1"),
+            Response::bs("10.6")
+        );
+        assert_eq!(store.get("incrbyfloat:missing"), Ok(Some("10.6".into())));
+    }
+
+    #[test]
+    fn test_incrbyfloat_trims_trailing_zeros() {
+        let store = KvStore::new();
+        store.set("incrbyfloat:trim", "2.5");
+        assert_eq!(
+            incrbyfloat(&store, "incrbyfloat:trim", "0.5"),
+            Response::bs("3")
+        );
+        assert_eq!(
+            incrbyfloat(&store, "incrbyfloat:trim", "-5"),
+            Response::bs("-2")
+        );
+        store.set("incrbyfloat:exponent", "5.0e3");
+        assert_eq!(
+            incrbyfloat(&store, "incrbyfloat:exponent", "200"),
+            Response::bs("5200")
+        );
+    }
+
+    #[test]
+    fn test_incrbyfloat_not_a_float() {
+        let store = KvStore::new();
+        store.set("incrbyfloat:text", "abc");
+        assert_eq!(
+            incrbyfloat(&store, "incrbyfloat:text", "1"),
+            Response::err("ERR", "value is not a valid float")
+        );
+        assert_eq!(store.get("incrbyfloat:text"), Ok(Some("abc".into())));
+
+        assert_eq!(
+            incrbyfloat(&store, "incrbyfloat:overflow", "1.7e308"),
+            Response::bs(&1.7e308_f64.to_string())
+        );
+        assert_eq!(
+            incrbyfloat(&store, "incrbyfloat:overflow", "1.7e308"),
+            Response::from(ArgumentError::Float)
+        );
+
+        let result = Builder::new()
+            .key("incrbyfloat:bad")
+            .increment("inf")
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Float)));
+    }
+}
@@ -0,0 +1,187 @@
+/// This module contains the implementation of the `Info` command.
+/// The `Info` command returns information and statistics about the server as lines of
+/// `field:value` pairs, grouped under `# Section` headers.
+use std::fmt::Write;
+
+use crate::{
+    kvstore::{KvStore, DATABASES, DATABASE_COUNT},
+    response::types::Response,
+};
+
+use super::types::Execute;
+
+/// Represents the `Info` command.
+pub struct Info {
+    sections: Vec<String>,
+}
+
+impl Info {
+    /// Checks if a section was requested, where no sections means the default ones.
+    fn includes(&self, section: &str) -> bool {
+        self.sections.is_empty()
+            || self.sections.iter().any(|requested| {
+                ["all", "default", "everything", section]
+                    .iter()
+                    .any(|name| requested.eq_ignore_ascii_case(name))
+            })
+    }
+
+    /// Describes the requested sections for the given stores taken together,
+    /// which are the databases in order of their indexes.
+    fn execute_on<'a>(&self, stores: impl IntoIterator<Item = &'a KvStore> + Clone) -> Response {
+        let mut sections = Vec::new();
+        if self.includes("memory") {
+            sections.push(format!(
+                "# Memory\r\nused_memory:{}\r\n",
+                stores
+                    .clone()
+                    .into_iter()
+                    .map(KvStore::total_memory)
+                    .sum::<usize>()
+            ));
+        }
+        if self.includes("stats") {
+            sections.push(format!(
+                "# Stats\r\nexpired_keys:{}\r\nevicted_keys:{}\r\n",
+                stores
+                    .clone()
+                    .into_iter()
+                    .map(KvStore::expired_keys)
+                    .sum::<u64>(),
+                stores
+                    .clone()
+                    .into_iter()
+                    .map(KvStore::evicted_keys)
+                    .sum::<u64>()
+            ));
+        }
+        if self.includes("keyspace") {
+            let mut keyspace = String::from("# Keyspace\r\n");
+            for (index, store) in stores.into_iter().enumerate() {
+                // only databases holding keys are listed
+                let keys = store.len();
+                if keys > 0 {
+                    let _ = write!(keyspace, "db{index}:keys={keys}\r\n");
+                }
+            }
+            sections.push(keyspace);
+        }
+
+        // sections are separated by a blank line
+        Response::BulkString(sections.join("\r\n").into())
+    }
+}
+
+impl Execute for Info {
+    /// Executes the `Info` command and returns the requested sections as a bulk string,
+    /// covering every database of the server.
+    fn execute(self) -> Response {
+        self.execute_on((0..DATABASE_COUNT).map(|index| DATABASES.get(index)))
+    }
+}
+
+/// Builder for the `Info` command.
+pub struct Builder {
+    sections: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            sections: Vec::new(),
+        }
+    }
+
+    /// Sets the sections to describe, all of the default ones if none are given.
+    pub fn sections(mut self, sections: &[String]) -> Self {
+        self.sections = sections.to_vec();
+        self
+    }
+
+    /// Builds the `Info` command.
+    pub fn build(self) -> Info {
+        Info {
+            sections: self.sections,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn info(store: &KvStore, sections: &[&str]) -> Response {
+        let sections: Vec<String> = sections.iter().map(ToString::to_string).collect();
+        Builder::new()
+            .sections(&sections)
+            .build()
+            .execute_on([store])
+    }
+
+    #[test]
+    fn test_info_expired_keys() {
+        let store = KvStore::new();
+        store.set("info:short", "1");
+        store.expire("info:short", Duration::from_millis(1));
+        assert_eq!(
+            info(&store, &["stats"]),
+            Response::bs("# Stats\r\nexpired_keys:0\r\nevicted_keys:0\r\n")
+        );
+
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(store.get("info:short"), Ok(None));
+        assert_eq!(
+            info(&store, &["STATS"]),
+            Response::bs("# Stats\r\nexpired_keys:1\r\nevicted_keys:0\r\n")
+        );
+    }
+
+    #[test]
+    fn test_info_used_memory() {
+        let store = KvStore::new();
+        store.set("info:memory", "value");
+        assert_eq!(
+            info(&store, &["memory"]),
+            Response::BulkString(
+                format!(
+                    "# Memory\r\nused_memory:{}\r\n",
+                    store.memory_usage("info:memory").unwrap()
+                )
+                .into()
+            )
+        );
+
+        let Response::BulkString(info) = info(&store, &[]) else {
+            panic!("INFO should reply with a bulk string");
+        };
+        let info = String::from_utf8(info).unwrap();
+        assert!(info.contains("# Memory\r\nused_memory:"));
+        assert!(info.contains("\r\n\r\n# Stats\r\n"));
+    }
+
+    #[test]
+    fn test_info_keyspace() {
+        let store = KvStore::new();
+        assert_eq!(info(&store, &["keyspace"]), Response::bs("# Keyspace\r\n"));
+
+        store.set("info:keyspace", "1");
+        store.sadd("info:keyspace:set", &["a".into()]).unwrap();
+        let empty = KvStore::new();
+        assert_eq!(
+            Builder::new()
+                .sections(&["keyspace".into()])
+                .build()
+                .execute_on([&empty, &store]),
+            Response::bs("# Keyspace\r\ndb1:keys=2\r\n")
+        );
+    }
+
+    #[test]
+    fn test_info_unknown_section() {
+        let store = KvStore::new();
+        assert_eq!(info(&store, &["replication"]), Response::bs(""));
+        assert!(matches!(info(&store, &["all"]), Response::BulkString(info) if !info.is_empty()));
+    }
+}
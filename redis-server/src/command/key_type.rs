@@ -0,0 +1,74 @@
+/// This module contains the implementation of the `Type` command.
+/// The `Type` command returns the name of the type of the value stored at a key,
+/// such as `string` or `hash`, or `none` if the key does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `Type` command.
+pub struct Type {
+    key: String,
+}
+
+impl ExecuteOn for Type {
+    /// Executes the `Type` command and returns the type of the key.
+    fn execute_on(self, store: &KvStore) -> Response {
+        Response::ss(store.type_name(&self.key).unwrap_or("none"))
+    }
+}
+
+/// Builder for the `Type` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key to inspect.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `Type` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<Type, ArgumentError> {
+        Ok(Type {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_type(store: &KvStore, key: &str) -> String {
+        Builder::new()
+            .key(key)
+            .build()
+            .unwrap()
+            .execute_on(store)
+            .into()
+    }
+
+    #[test]
+    fn test_type() {
+        let store = KvStore::new();
+        store.set("type:string", "value");
+        store.hset("type:hash", "field", "value").unwrap();
+        store.sadd("type:set", &["member".into()]).unwrap();
+
+        assert_eq!(key_type(&store, "type:string"), "+string\r\n");
+        assert_eq!(key_type(&store, "type:hash"), "+hash\r\n");
+        assert_eq!(key_type(&store, "type:set"), "+set\r\n");
+        assert_eq!(key_type(&store, "type:missing"), "+none\r\n");
+    }
+}
@@ -0,0 +1,99 @@
+/// This module contains the implementation of the `LIndex` command.
+/// The `LIndex` command returns the element at an index of a list, where negative indexes count
+/// back from the tail, or null if the index is out of range or the key does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `LIndex` command.
+pub struct LIndex {
+    key: String,
+    index: i64,
+}
+
+impl ExecuteOn for LIndex {
+    /// Executes the `LIndex` command and returns the element at the index.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.lindex(&self.key, self.index) {
+            Ok(element) => element.map_or(Response::Null, |element| {
+                Response::BulkString(element.into())
+            }),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `LIndex` command.
+pub struct Builder {
+    key: Option<String>,
+    index: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            index: None,
+        }
+    }
+
+    /// Sets the key of the list.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the index of the element.
+    pub fn index(mut self, index: &str) -> Self {
+        self.index = Some(index.into());
+        self
+    }
+
+    /// Builds the `LIndex` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key or index is not provided,
+    /// or an `ArgumentError::Integer` if the index is not an integer.
+    pub fn build(self) -> Result<LIndex, ArgumentError> {
+        let index = self.index.ok_or(ArgumentError::Missing)?;
+
+        Ok(LIndex {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            index: index.parse().map_err(|_| ArgumentError::Integer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lindex(store: &KvStore, key: &str, index: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .index(index)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_lindex() {
+        let store = KvStore::new();
+        store
+            .push("lindex:list", &["a".into(), "b".into(), "c".into()], true)
+            .unwrap();
+        assert_eq!(lindex(&store, "lindex:list", "0"), Response::bs("a"));
+        assert_eq!(lindex(&store, "lindex:list", "2"), Response::bs("c"));
+        assert_eq!(lindex(&store, "lindex:list", "-1"), Response::bs("c"));
+        assert_eq!(lindex(&store, "lindex:list", "-3"), Response::bs("a"));
+        assert_eq!(lindex(&store, "lindex:list", "3"), Response::Null);
+        assert_eq!(lindex(&store, "lindex:list", "-4"), Response::Null);
+        assert_eq!(lindex(&store, "lindex:missing", "0"), Response::Null);
+
+        let result = Builder::new().key("lindex:list").index("one").build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
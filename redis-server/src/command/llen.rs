@@ -0,0 +1,74 @@
+/// This module contains the implementation of the `LLen` command.
+/// The `LLen` command returns the number of elements of a list, or `0` if it does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `LLen` command.
+pub struct LLen {
+    key: String,
+}
+
+impl ExecuteOn for LLen {
+    /// Executes the `LLen` command and returns the length of the list.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.llen(&self.key) {
+            Ok(len) => Response::Integer(i64::try_from(len).unwrap_or(i64::MAX)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `LLen` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key of the list.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `LLen` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<LLen, ArgumentError> {
+        Ok(LLen {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn llen(store: &KvStore, key: &str) -> Response {
+        Builder::new().key(key).build().unwrap().execute_on(store)
+    }
+
+    #[test]
+    fn test_llen() {
+        let store = KvStore::new();
+        store
+            .push("llen:list", &["a".into(), "b".into(), "c".into()], true)
+            .unwrap();
+        assert_eq!(llen(&store, "llen:list"), Response::Integer(3));
+        assert_eq!(llen(&store, "llen:missing"), Response::Integer(0));
+
+        store.set("llen:string", "value");
+        assert_eq!(
+            llen(&store, "llen:string"),
+            Response::from(crate::kvstore::WrongType)
+        );
+    }
+}
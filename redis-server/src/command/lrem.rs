@@ -0,0 +1,158 @@
+/// This module contains the implementation of the `LRem` command.
+/// The `LRem` command removes up to a number of occurrences of an element from a list,
+/// searching from the head if the count is positive or from the tail if it is negative,
+/// and removing every occurrence if it is `0`.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `LRem` command.
+pub struct LRem {
+    key: String,
+    count: i64,
+    element: String,
+}
+
+impl ExecuteOn for LRem {
+    /// Executes the `LRem` command and returns the number of elements removed.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.lrem(&self.key, self.count, &self.element) {
+            Ok(removed) => Response::Integer(i64::try_from(removed).unwrap_or(i64::MAX)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `LRem` command.
+pub struct Builder {
+    key: Option<String>,
+    count: Option<String>,
+    element: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            count: None,
+            element: None,
+        }
+    }
+
+    /// Sets the key of the list.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the most occurrences to remove, and from which end.
+    pub fn count(mut self, count: &str) -> Self {
+        self.count = Some(count.into());
+        self
+    }
+
+    /// Sets the element to remove.
+    pub fn element(mut self, element: &str) -> Self {
+        self.element = Some(element.into());
+        self
+    }
+
+    /// Builds the `LRem` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::Integer` if the count is not an integer.
+    pub fn build(self) -> Result<LRem, ArgumentError> {
+        let count = self.count.ok_or(ArgumentError::Missing)?;
+
+        Ok(LRem {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            count: count.parse().map_err(|_| ArgumentError::Integer)?,
+            element: self.element.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lrem(store: &KvStore, key: &str, count: &str, element: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .count(count)
+            .element(element)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    fn push(store: &KvStore, key: &str, elements: &[&str]) {
+        let elements: Vec<String> = elements.iter().map(ToString::to_string).collect();
+        store.push(key, &elements, true).unwrap();
+    }
+
+    fn elements(store: &KvStore, key: &str) -> Vec<Option<String>> {
+        let len = i64::try_from(store.llen(key).unwrap()).unwrap();
+        (0..len).map(|i| store.lindex(key, i).unwrap()).collect()
+    }
+
+    fn list(elements: &[&str]) -> Vec<Option<String>> {
+        elements
+            .iter()
+            .map(|element| Some(element.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lrem_from_head() {
+        let store = KvStore::new();
+        push(&store, "lrem:head", &["a", "b", "a", "c", "a"]);
+        assert_eq!(lrem(&store, "lrem:head", "2", "a"), Response::Integer(2));
+        assert_eq!(elements(&store, "lrem:head"), list(&["b", "c", "a"]));
+    }
+
+    #[test]
+    fn test_lrem_from_tail() {
+        let store = KvStore::new();
+        push(&store, "lrem:tail", &["a", "b", "a", "c", "a"]);
+        assert_eq!(lrem(&store, "lrem:tail", "-2", "a"), Response::Integer(2));
+        assert_eq!(elements(&store, "lrem:tail"), list(&["a", "b", "c"]));
+
+        assert_eq!(lrem(&store, "lrem:tail", "-5", "a"), Response::Integer(1));
+        assert_eq!(elements(&store, "lrem:tail"), list(&["b", "c"]));
+    }
+
+    #[test]
+    fn test_lrem_all() {
+        let store = KvStore::new();
+        push(&store, "lrem:all", &["a", "b", "a", "c", "a"]);
+        assert_eq!(lrem(&store, "lrem:all", "0", "a"), Response::Integer(3));
+        assert_eq!(elements(&store, "lrem:all"), list(&["b", "c"]));
+        assert_eq!(lrem(&store, "lrem:all", "0", "d"), Response::Integer(0));
+
+        assert_eq!(lrem(&store, "lrem:all", "0", "b"), Response::Integer(1));
+        assert_eq!(lrem(&store, "lrem:all", "1", "c"), Response::Integer(1));
+        assert!(!store.exists("lrem:all"));
+        assert_eq!(lrem(&store, "lrem:all", "0", "a"), Response::Integer(0));
+    }
+
+    #[test]
+    fn test_lrem_wrong_type() {
+        let store = KvStore::new();
+        store.set("lrem:string", "a");
+        assert!(matches!(
+            lrem(&store, "lrem:string", "0", "a"),
+            Response::Error(_)
+        ));
+
+        let result = Builder::new()
+            .key("lrem:string")
+            .count("all")
+            .element("a")
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
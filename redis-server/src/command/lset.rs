@@ -0,0 +1,128 @@
+/// This module contains the implementation of the `LSet` command.
+/// The `LSet` command overwrites the element at an index of a list, where negative indexes count
+/// back from the tail, failing if the index is out of range or the key does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `LSet` command.
+pub struct LSet {
+    key: String,
+    index: i64,
+    value: String,
+}
+
+impl ExecuteOn for LSet {
+    /// Executes the `LSet` command and returns `OK` if the element was overwritten.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.lset(&self.key, self.index, &self.value) {
+            Ok(Some(true)) => Response::ss("OK"),
+            Ok(Some(false)) => Response::err("ERR", "index out of range"),
+            Ok(None) => Response::err("ERR", "no such key"),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `LSet` command.
+pub struct Builder {
+    key: Option<String>,
+    index: Option<String>,
+    value: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            index: None,
+            value: None,
+        }
+    }
+
+    /// Sets the key of the list.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the index of the element.
+    pub fn index(mut self, index: &str) -> Self {
+        self.index = Some(index.into());
+        self
+    }
+
+    /// Sets the new value of the element.
+    pub fn value(mut self, value: &str) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Builds the `LSet` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key, index or value is not provided,
+    /// or an `ArgumentError::Integer` if the index is not an integer.
+    pub fn build(self) -> Result<LSet, ArgumentError> {
+        let index = self.index.ok_or(ArgumentError::Missing)?;
+
+        Ok(LSet {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            index: index.parse().map_err(|_| ArgumentError::Integer)?,
+            value: self.value.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lset(store: &KvStore, key: &str, index: &str, value: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .index(index)
+            .value(value)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_lset() {
+        let store = KvStore::new();
+        store
+            .push("lset:list", &["a".into(), "b".into(), "c".into()], true)
+            .unwrap();
+        assert_eq!(lset(&store, "lset:list", "0", "x"), Response::ss("OK"));
+        assert_eq!(lset(&store, "lset:list", "-1", "z"), Response::ss("OK"));
+        assert_eq!(
+            store.pop("lset:list", 3, false),
+            Ok(Some(vec!["x".into(), "b".into(), "z".into()]))
+        );
+    }
+
+    #[test]
+    fn test_lset_out_of_range() {
+        let store = KvStore::new();
+        store
+            .push("lset:range", &["a".into(), "b".into()], true)
+            .unwrap();
+        assert_eq!(
+            lset(&store, "lset:range", "2", "x"),
+            Response::err("ERR", "index out of range")
+        );
+        assert_eq!(
+            lset(&store, "lset:range", "-3", "x"),
+            Response::err("ERR", "index out of range")
+        );
+        assert_eq!(store.lindex("lset:range", 0), Ok(Some("a".into())));
+
+        assert_eq!(
+            lset(&store, "lset:missing", "0", "x"),
+            Response::err("ERR", "no such key")
+        );
+    }
+}
@@ -0,0 +1,151 @@
+/// This module contains the implementation of the `LTrim` command.
+/// The `LTrim` command trims a list to the elements within a range of indexes, both inclusive,
+/// where negative indexes count back from the tail, deleting the list if the range is empty.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `LTrim` command.
+pub struct LTrim {
+    key: String,
+    start: i64,
+    stop: i64,
+}
+
+impl ExecuteOn for LTrim {
+    /// Executes the `LTrim` command and returns `OK`.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.ltrim(&self.key, self.start, self.stop) {
+            Ok(()) => Response::ss("OK"),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `LTrim` command.
+pub struct Builder {
+    key: Option<String>,
+    start: Option<String>,
+    stop: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            start: None,
+            stop: None,
+        }
+    }
+
+    /// Sets the key of the list.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the index of the first element to keep.
+    pub fn start(mut self, start: &str) -> Self {
+        self.start = Some(start.into());
+        self
+    }
+
+    /// Sets the index of the last element to keep.
+    pub fn stop(mut self, stop: &str) -> Self {
+        self.stop = Some(stop.into());
+        self
+    }
+
+    /// Builds the `LTrim` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::Integer` if an index is not an integer.
+    pub fn build(self) -> Result<LTrim, ArgumentError> {
+        let start = self.start.ok_or(ArgumentError::Missing)?;
+        let stop = self.stop.ok_or(ArgumentError::Missing)?;
+
+        Ok(LTrim {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            start: start.parse().map_err(|_| ArgumentError::Integer)?,
+            stop: stop.parse().map_err(|_| ArgumentError::Integer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ltrim(store: &KvStore, key: &str, start: &str, stop: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .start(start)
+            .stop(stop)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    fn push(store: &KvStore, key: &str, elements: &[&str]) {
+        let elements: Vec<String> = elements.iter().map(ToString::to_string).collect();
+        store.push(key, &elements, true).unwrap();
+    }
+
+    fn elements(store: &KvStore, key: &str) -> Vec<String> {
+        let len = i64::try_from(store.llen(key).unwrap()).unwrap();
+        (0..len)
+            .filter_map(|i| store.lindex(key, i).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_ltrim() {
+        let store = KvStore::new();
+        push(&store, "ltrim:middle", &["a", "b", "c", "d", "e"]);
+        assert_eq!(ltrim(&store, "ltrim:middle", "1", "-2"), Response::ss("OK"));
+        assert_eq!(elements(&store, "ltrim:middle"), ["b", "c", "d"]);
+
+        assert_eq!(
+            ltrim(&store, "ltrim:middle", "-100", "100"),
+            Response::ss("OK")
+        );
+        assert_eq!(elements(&store, "ltrim:middle"), ["b", "c", "d"]);
+
+        assert_eq!(ltrim(&store, "ltrim:middle", "1", "1"), Response::ss("OK"));
+        assert_eq!(elements(&store, "ltrim:middle"), ["c"]);
+    }
+
+    #[test]
+    fn test_ltrim_empty_range() {
+        let store = KvStore::new();
+        push(&store, "ltrim:empty", &["a", "b", "c"]);
+        assert_eq!(ltrim(&store, "ltrim:empty", "5", "10"), Response::ss("OK"));
+        assert!(!store.exists("ltrim:empty"));
+
+        push(&store, "ltrim:empty", &["a", "b", "c"]);
+        assert_eq!(ltrim(&store, "ltrim:empty", "2", "1"), Response::ss("OK"));
+        assert!(!store.exists("ltrim:empty"));
+
+        assert_eq!(ltrim(&store, "ltrim:missing", "0", "1"), Response::ss("OK"));
+    }
+
+    #[test]
+    fn test_ltrim_wrong_type() {
+        let store = KvStore::new();
+        store.set("ltrim:string", "a");
+        assert!(matches!(
+            ltrim(&store, "ltrim:string", "0", "1"),
+            Response::Error(_)
+        ));
+
+        let result = Builder::new()
+            .key("ltrim:string")
+            .start("0")
+            .stop("end")
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
@@ -0,0 +1,152 @@
+/// This module contains the implementation of the `Memory` command,
+/// which reports how much memory the store takes up.
+use super::{
+    config::CommandBuildError,
+    types::{ArgumentError, ExecuteOn, SubcommandError},
+};
+
+use crate::{kvstore::KvStore, response::types::Response};
+
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct Memory {
+    subcommand: MemorySubcommand,
+    args: Vec<String>,
+}
+
+#[derive(Debug)]
+enum MemorySubcommand {
+    Usage,
+}
+
+impl FromStr for MemorySubcommand {
+    type Err = SubcommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "USAGE" => Ok(Self::Usage),
+            _ => Err(Self::Err::Unknown),
+        }
+    }
+}
+
+impl ExecuteOn for Memory {
+    fn execute_on(self, store: &KvStore) -> Response {
+        let Some(key) = self.args.first() else {
+            return Response::from(ArgumentError::Missing);
+        };
+
+        match &self.subcommand {
+            MemorySubcommand::Usage => store.memory_usage(key).map_or(Response::Null, |usage| {
+                Response::Integer(i64::try_from(usage).unwrap_or(i64::MAX))
+            }),
+        }
+    }
+}
+
+pub struct Builder {
+    args_raw: Option<Vec<String>>,
+}
+
+impl Builder {
+    pub const fn new() -> Self {
+        Self { args_raw: None }
+    }
+
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args_raw = Some(args);
+        self
+    }
+
+    pub fn build(self) -> Result<Memory, CommandBuildError> {
+        let Some(args) = self.args_raw else {
+            return Err(CommandBuildError::Subcommand(SubcommandError::Missing));
+        };
+
+        let subcommand = match args.first() {
+            Some(sub) => MemorySubcommand::from_str(sub)?,
+            None => return Err(CommandBuildError::Subcommand(SubcommandError::Missing)),
+        };
+
+        match &args[1..] {
+            [key] => Ok(Memory {
+                subcommand,
+                args: vec![key.clone()],
+            }),
+            // values are measured in full, so the number of elements to sample is only checked
+            [key, samples, count] if samples.eq_ignore_ascii_case("SAMPLES") => {
+                count
+                    .parse::<u64>()
+                    .map_err(|_| CommandBuildError::Argument(ArgumentError::Integer))?;
+                Ok(Memory {
+                    subcommand,
+                    args: vec![key.clone()],
+                })
+            }
+            [] => Err(CommandBuildError::Argument(ArgumentError::Missing)),
+            _ => Err(CommandBuildError::Argument(ArgumentError::Syntax)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory(args: &[&str]) -> Result<Memory, CommandBuildError> {
+        Builder::new()
+            .args(args.iter().map(ToString::to_string).collect())
+            .build()
+    }
+
+    fn usage(store: &KvStore, key: &str) -> i64 {
+        let Response::Integer(usage) = memory(&["USAGE", key]).unwrap().execute_on(store) else {
+            panic!("MEMORY USAGE should reply with an integer for an existing key");
+        };
+        usage
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let store = KvStore::new();
+        store.set("memory:small", "a");
+        store.set("memory:large", &"a".repeat(1000));
+        assert!(usage(&store, "memory:large") > usage(&store, "memory:small"));
+        assert_eq!(
+            usage(&store, "memory:large") - usage(&store, "memory:small"),
+            999
+        );
+
+        assert_eq!(
+            memory(&["usage", "memory:missing"])
+                .unwrap()
+                .execute_on(&store),
+            Response::Null
+        );
+    }
+
+    #[test]
+    fn test_memory_usage_samples() {
+        let store = KvStore::new();
+        store.set("memory:samples", "a");
+        assert_eq!(
+            memory(&["USAGE", "memory:samples", "SAMPLES", "5"])
+                .unwrap()
+                .execute_on(&store),
+            Response::Integer(usage(&store, "memory:samples"))
+        );
+        assert!(matches!(
+            memory(&["USAGE", "memory:samples", "SAMPLES", "many"]),
+            Err(CommandBuildError::Argument(ArgumentError::Integer))
+        ));
+        assert!(matches!(
+            memory(&["USAGE", "memory:samples", "extra"]),
+            Err(CommandBuildError::Argument(ArgumentError::Syntax))
+        ));
+        assert!(matches!(
+            memory(&["DOCTOR"]),
+            Err(CommandBuildError::Subcommand(SubcommandError::Unknown))
+        ));
+    }
+}
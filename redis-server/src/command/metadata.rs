@@ -0,0 +1,751 @@
+//! This module contains the documentation table of the implemented commands,
+//! used to answer introspection commands such as `COMMAND DOCS`.
+
+/// Describes an implemented command.
+pub struct Metadata {
+    /// The lowercase name of the command.
+    pub name: &'static str,
+    /// A short description of what the command does.
+    pub summary: &'static str,
+    /// The Redis version the command first appeared in.
+    pub since: &'static str,
+    /// The group the command belongs to, such as `string` or `pubsub`.
+    pub group: &'static str,
+    /// The number of arguments, including the command name itself.
+    /// A negative arity `-n` means at least `n` arguments.
+    pub arity: i64,
+    /// The largest number of arguments a command with a negative arity accepts, if bounded.
+    pub max_arity: Option<usize>,
+    /// The positions of the first and last key arguments, and the step between them.
+    /// All zero for commands that take no keys.
+    pub keys: (i64, i64, i64),
+}
+
+impl Metadata {
+    /// Creates a new `Metadata` entry.
+    const fn new(
+        name: &'static str,
+        summary: &'static str,
+        since: &'static str,
+        group: &'static str,
+        arity: i64,
+    ) -> Self {
+        Self {
+            name,
+            summary,
+            since,
+            group,
+            arity,
+            max_arity: None,
+            keys: (0, 0, 0),
+        }
+    }
+
+    /// Bounds the number of arguments of a command with a negative arity.
+    const fn max_arity(mut self, max_arity: usize) -> Self {
+        self.max_arity = Some(max_arity);
+        self
+    }
+
+    /// Checks whether the command accepts a number of arguments, including its name.
+    pub fn accepts(&self, len: usize) -> bool {
+        let arity = usize::try_from(self.arity.unsigned_abs()).unwrap_or(usize::MAX);
+        if self.arity >= 0 {
+            return len == arity;
+        }
+
+        len >= arity && self.max_arity.is_none_or(|max_arity| len <= max_arity)
+    }
+
+    /// Sets the positions of the key arguments of the command.
+    const fn keys(mut self, first: i64, last: i64, step: i64) -> Self {
+        self.keys = (first, last, step);
+        self
+    }
+}
+
+/// The metadata of every implemented command.
+pub const COMMANDS: &[Metadata] = &[
+    Metadata::new(
+        "ping",
+        "Returns the server's liveliness response.",
+        "1.0.0",
+        "connection",
+        -1,
+    )
+    .max_arity(2),
+    Metadata::new(
+        "echo",
+        "Returns the given string.",
+        "1.0.0",
+        "connection",
+        2,
+    ),
+    Metadata::new(
+        "config",
+        "A container for server configuration commands.",
+        "2.0.0",
+        "server",
+        -2,
+    )
+    .max_arity(3),
+    Metadata::new(
+        "exists",
+        "Determines whether a key exists.",
+        "1.0.0",
+        "generic",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "set",
+        "Sets the string value of a key.",
+        "1.0.0",
+        "string",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "get",
+        "Returns the string value of a key.",
+        "1.0.0",
+        "string",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "getrange",
+        "Returns a substring of the string stored at a key.",
+        "2.4.0",
+        "string",
+        4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "substr",
+        "Returns a substring from a string value.",
+        "1.0.0",
+        "string",
+        4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "setrange",
+        "Overwrites a part of a string value with another by an offset. Creates the key if it doesn't exist.",
+        "2.2.0",
+        "string",
+        4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "incrbyfloat",
+        "Increments the floating point value of a key by a number. Uses 0 as initial value if the key doesn't exist.",
+        "2.6.0",
+        "string",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "setbit",
+        "Sets or clears the bit at offset of the string value. Creates the key if it doesn't exist.",
+        "2.2.0",
+        "bitmap",
+        4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "getbit",
+        "Returns a bit value by offset.",
+        "2.2.0",
+        "bitmap",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "bitcount",
+        "Counts the number of set bits (population counting) in a string.",
+        "2.6.0",
+        "bitmap",
+        -2,
+    )
+    .max_arity(4)
+    .keys(1, 1, 1),
+    Metadata::new("del", "Deletes a key.", "1.0.0", "generic", 2).keys(1, 1, 1),
+    Metadata::new("rename", "Renames a key.", "1.0.0", "generic", 3).keys(1, 2, 1),
+    Metadata::new(
+        "renamenx",
+        "Renames a key only when the target key name doesn't exist.",
+        "1.0.0",
+        "generic",
+        3,
+    )
+    .keys(1, 2, 1),
+    Metadata::new("copy", "Copies the value of a key.", "6.2.0", "generic", -3)
+        .max_arity(4)
+        .keys(1, 2, 1),
+    Metadata::new(
+        "dump",
+        "Returns a serialized representation of the value stored at a key.",
+        "2.6.0",
+        "generic",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "restore",
+        "Creates a key from the serialized representation of a value.",
+        "2.6.0",
+        "generic",
+        -4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "expire",
+        "Sets the expiration time of a key in seconds.",
+        "1.0.0",
+        "generic",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "expireat",
+        "Sets the expiration time of a key to a Unix timestamp.",
+        "1.2.0",
+        "generic",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "pexpireat",
+        "Sets the expiration time of a key to a Unix milliseconds timestamp.",
+        "2.6.0",
+        "generic",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "ttl",
+        "Returns the expiration time in seconds of a key.",
+        "1.0.0",
+        "generic",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "pexpire",
+        "Sets the expiration time of a key in milliseconds.",
+        "2.6.0",
+        "generic",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "pttl",
+        "Returns the expiration time in milliseconds of a key.",
+        "2.6.0",
+        "generic",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "persist",
+        "Removes the expiration time of a key.",
+        "2.2.0",
+        "generic",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "info",
+        "Returns information and statistics about the server.",
+        "1.0.0",
+        "server",
+        -1,
+    ),
+    Metadata::new(
+        "randomkey",
+        "Returns a random key name from the database.",
+        "1.0.0",
+        "generic",
+        1,
+    ),
+    Metadata::new(
+        "type",
+        "Determines the type of value stored at a key.",
+        "1.0.0",
+        "generic",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hset",
+        "Creates or modifies the value of a field in a hash.",
+        "2.0.0",
+        "hash",
+        -4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hget",
+        "Returns the value of a field in a hash.",
+        "2.0.0",
+        "hash",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hincrby",
+        "Increments the integer value of a field in a hash by a number. Uses 0 as initial value if the field doesn't exist.",
+        "2.0.0",
+        "hash",
+        4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hexists",
+        "Determines whether a field exists in a hash.",
+        "2.0.0",
+        "hash",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hkeys",
+        "Returns all fields in a hash.",
+        "2.0.0",
+        "hash",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hvals",
+        "Returns all values in a hash.",
+        "2.0.0",
+        "hash",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hlen",
+        "Returns the number of fields in a hash.",
+        "2.0.0",
+        "hash",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hmget",
+        "Returns the values of all fields in a hash.",
+        "2.0.0",
+        "hash",
+        -3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hmset",
+        "Sets the values of multiple fields.",
+        "2.0.0",
+        "hash",
+        -4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hgetall",
+        "Returns all fields and values in a hash.",
+        "2.0.0",
+        "hash",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hdel",
+        "Deletes one or more fields and their values from a hash.",
+        "2.0.0",
+        "hash",
+        -3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "hexpire",
+        "Sets the expiration time of hash fields in seconds.",
+        "7.4.0",
+        "hash",
+        -6,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "httl",
+        "Returns the expiration time in seconds of hash fields.",
+        "7.4.0",
+        "hash",
+        -5,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "sadd",
+        "Adds one or more members to a set. Creates the key if it doesn't exist.",
+        "1.0.0",
+        "set",
+        -3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "srem",
+        "Removes one or more members from a set. Deletes the set if the last member was removed.",
+        "1.0.0",
+        "set",
+        -3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "smembers",
+        "Returns all members of a set.",
+        "1.0.0",
+        "set",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "sismember",
+        "Determines whether a member belongs to a set.",
+        "1.0.0",
+        "set",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "scard",
+        "Returns the number of members in a set.",
+        "1.0.0",
+        "set",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "sinter",
+        "Returns the intersect of multiple sets.",
+        "1.0.0",
+        "set",
+        -2,
+    )
+    .keys(1, -1, 1),
+    Metadata::new(
+        "sunion",
+        "Returns the union of multiple sets.",
+        "1.0.0",
+        "set",
+        -2,
+    )
+    .keys(1, -1, 1),
+    Metadata::new(
+        "sdiff",
+        "Returns the difference of multiple sets.",
+        "1.0.0",
+        "set",
+        -2,
+    )
+    .keys(1, -1, 1),
+    Metadata::new(
+        "sinterstore",
+        "Stores the intersect of multiple sets in a key.",
+        "1.0.0",
+        "set",
+        -3,
+    )
+    .keys(1, -1, 1),
+    Metadata::new(
+        "sunionstore",
+        "Stores the union of multiple sets in a key.",
+        "1.0.0",
+        "set",
+        -3,
+    )
+    .keys(1, -1, 1),
+    Metadata::new(
+        "sdiffstore",
+        "Stores the difference of multiple sets in a key.",
+        "1.0.0",
+        "set",
+        -3,
+    )
+    .keys(1, -1, 1),
+    Metadata::new(
+        "lpush",
+        "Prepends one or more elements to a list. Creates the key if it doesn't exist.",
+        "1.0.0",
+        "list",
+        -3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "rpush",
+        "Appends one or more elements to a list. Creates the key if it doesn't exist.",
+        "1.0.0",
+        "list",
+        -3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "lpop",
+        "Returns the first elements in a list after removing it. Deletes the list if the last element was popped.",
+        "1.0.0",
+        "list",
+        -2,
+    )
+    .max_arity(3)
+    .keys(1, 1, 1),
+    Metadata::new(
+        "rpop",
+        "Returns and removes the last elements of a list. Deletes the list if the last element was popped.",
+        "1.0.0",
+        "list",
+        -2,
+    )
+    .max_arity(3)
+    .keys(1, 1, 1),
+    Metadata::new(
+        "lindex",
+        "Returns an element from a list by its index.",
+        "1.0.0",
+        "list",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "llen",
+        "Returns the length of a list.",
+        "1.0.0",
+        "list",
+        2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "lset",
+        "Sets the value of an element in a list by its index.",
+        "1.0.0",
+        "list",
+        4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "lrem",
+        "Removes elements from a list. Deletes the list if the last element was removed.",
+        "1.0.0",
+        "list",
+        4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "ltrim",
+        "Removes elements from both ends a list. Deletes the list if all elements were trimmed.",
+        "1.0.0",
+        "list",
+        4,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "sort",
+        "Sorts the elements in a list or a set.",
+        "1.0.0",
+        "generic",
+        -2,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "move",
+        "Moves a key to another database.",
+        "1.0.0",
+        "generic",
+        3,
+    )
+    .keys(1, 1, 1),
+    Metadata::new(
+        "select",
+        "Changes the selected database.",
+        "1.0.0",
+        "connection",
+        2,
+    ),
+    Metadata::new(
+        "multi",
+        "Starts a transaction.",
+        "1.2.0",
+        "transactions",
+        1,
+    ),
+    Metadata::new(
+        "exec",
+        "Executes all commands in a transaction.",
+        "1.2.0",
+        "transactions",
+        1,
+    ),
+    Metadata::new(
+        "discard",
+        "Discards a transaction.",
+        "2.0.0",
+        "transactions",
+        1,
+    ),
+    Metadata::new(
+        "subscribe",
+        "Listens for messages published to channels.",
+        "2.0.0",
+        "pubsub",
+        -2,
+    ),
+    Metadata::new(
+        "psubscribe",
+        "Listens for messages published to channels that match one or more patterns.",
+        "2.0.0",
+        "pubsub",
+        -2,
+    ),
+    Metadata::new(
+        "unsubscribe",
+        "Stops listening to messages posted to channels.",
+        "2.0.0",
+        "pubsub",
+        -1,
+    ),
+    Metadata::new(
+        "punsubscribe",
+        "Stops listening to messages published to channels that match one or more patterns.",
+        "2.0.0",
+        "pubsub",
+        -1,
+    ),
+    Metadata::new(
+        "publish",
+        "Posts a message to a channel.",
+        "2.0.0",
+        "pubsub",
+        3,
+    ),
+    Metadata::new(
+        "client",
+        "A container for client connection commands.",
+        "2.4.0",
+        "connection",
+        -2,
+    ),
+    Metadata::new(
+        "debug",
+        "A container for debugging commands.",
+        "1.0.0",
+        "server",
+        -2,
+    ),
+    Metadata::new(
+        "object",
+        "A container for object introspection commands.",
+        "2.2.3",
+        "generic",
+        -2,
+    ),
+    Metadata::new(
+        "memory",
+        "A container for memory diagnostics commands.",
+        "4.0.0",
+        "server",
+        -2,
+    ),
+    Metadata::new(
+        "time",
+        "Returns the server time.",
+        "2.6.0",
+        "server",
+        1,
+    ),
+    Metadata::new(
+        "swapdb",
+        "Swaps two Redis databases.",
+        "4.0.0",
+        "server",
+        3,
+    ),
+    Metadata::new(
+        "wait",
+        "Blocks until the asynchronous replication of all preceding write commands sent by the connection is completed.",
+        "3.0.0",
+        "generic",
+        3,
+    ),
+    Metadata::new(
+        "command",
+        "Returns detailed information about all commands.",
+        "2.8.13",
+        "server",
+        -1,
+    ),
+];
+
+/// Looks up the metadata of a command by name, ignoring case.
+///
+/// # Parameters
+///
+/// - `name`: The name of the command.
+///
+/// # Returns
+///
+/// The metadata of the command, or `None` if the command is not implemented.
+pub fn lookup(name: &str) -> Option<&'static Metadata> {
+    COMMANDS
+        .iter()
+        .find(|metadata| metadata.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::types::CommandBuilder;
+
+    #[test]
+    fn test_commands_are_implemented() {
+        for metadata in COMMANDS {
+            assert!(
+                metadata.name.parse::<CommandBuilder>().is_ok(),
+                "{} is documented but not implemented",
+                metadata.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_implemented_commands_are_documented() {
+        // the names matched by `CommandBuilder::from_str`, read from its source
+        let source = include_str!("types.rs");
+        let from_str = &source[source.find("impl FromStr for CommandBuilder").unwrap()..];
+        let names: Vec<&str> = from_str[..from_str.find("\n}\n").unwrap()]
+            .lines()
+            .filter_map(|line| Some(line.trim().strip_prefix('"')?.split_once(" =>")?.0))
+            .flat_map(|names| names.split(" | "))
+            .map(|name| name.trim_matches('"'))
+            .collect();
+
+        assert!(names.len() > 50);
+        for name in names {
+            assert!(name.parse::<CommandBuilder>().is_ok());
+            assert!(
+                lookup(name).is_some(),
+                "{name} is implemented but not documented"
+            );
+        }
+    }
+
+    #[test]
+    fn test_accepts() {
+        let accepts = |name: &str, len: usize| lookup(name).unwrap().accepts(len);
+        assert!(accepts("get", 2));
+        assert!(!accepts("get", 1));
+        assert!(!accepts("get", 3));
+
+        assert!(accepts("hdel", 3));
+        assert!(accepts("hdel", 10));
+        assert!(!accepts("hdel", 2));
+
+        assert!(accepts("ping", 1));
+        assert!(accepts("ping", 2));
+        assert!(!accepts("ping", 3));
+    }
+}
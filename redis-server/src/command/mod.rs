@@ -1,8 +1,69 @@
+pub mod bitcount;
+pub mod client;
+#[allow(clippy::module_inception)]
+pub mod command;
 pub mod config;
+pub mod copy;
+pub mod debug;
 pub mod del;
+pub mod discard;
+pub mod dump;
 pub mod echo;
+pub mod exec;
 pub mod exists;
+pub mod expire;
+pub mod expireat;
 pub mod get;
+pub mod getbit;
+pub mod getrange;
+pub mod hdel;
+pub mod hexists;
+pub mod hexpire;
+pub mod hget;
+pub mod hgetall;
+pub mod hincrby;
+pub mod hkeys;
+pub mod hlen;
+pub mod hmget;
+pub mod hset;
+pub mod httl;
+pub mod hvals;
+pub mod incrbyfloat;
+pub mod info;
+pub mod key_type;
+pub mod lindex;
+pub mod llen;
+pub mod lrem;
+pub mod lset;
+pub mod ltrim;
+pub mod memory;
+pub mod metadata;
+pub mod r#move;
+pub mod multi;
+pub mod object;
+pub mod persist;
 pub mod ping;
+pub mod pop;
+pub mod publish;
+pub mod push;
+pub mod randomkey;
+pub mod rename;
+pub mod restore;
+pub mod sadd;
+pub mod scard;
+pub mod select;
 pub mod set;
+pub mod setbit;
+pub mod setop;
+pub mod setrange;
+pub mod sismember;
+pub mod smembers;
+pub mod sort;
+pub mod srem;
+pub mod subscribe;
+pub mod swapdb;
+pub mod time;
+pub mod ttl;
 pub mod types;
+pub mod unsubscribe;
+pub mod wait;
@@ -0,0 +1,137 @@
+/// This module contains the implementation of the `Move` command.
+/// The `Move` command moves a key, along with its expiry, from the client's database to another,
+/// replying with `1` if it was moved, or `0` if it does not exist or the other database holds it.
+use crate::{
+    kvstore::{self, DATABASES},
+    response::types::Response,
+    session::Session,
+};
+
+use super::types::{ArgumentError, ExecuteInSession};
+
+/// Represents the `Move` command.
+pub struct Move {
+    key: String,
+    db: i64,
+}
+
+impl ExecuteInSession for Move {
+    /// Executes the `Move` command and returns whether the key was moved.
+    fn execute_in(self, session: &mut Session) -> Response {
+        let Some(db) = kvstore::database_index(self.db) else {
+            return Response::err("ERR", "DB index is out of range");
+        };
+        if db == session.db() {
+            return Response::err("ERR", "source and destination objects are the same");
+        }
+
+        Response::Integer(
+            session
+                .store()
+                .move_key(&self.key, DATABASES.get(db))
+                .into(),
+        )
+    }
+}
+
+/// Builder for the `Move` command.
+pub struct Builder {
+    key: Option<String>,
+    db: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            db: None,
+        }
+    }
+
+    /// Sets the key to move.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the index of the database to move the key to.
+    pub fn db(mut self, db: &str) -> Self {
+        self.db = Some(db.into());
+        self
+    }
+
+    /// Builds the `Move` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::Integer` if the database index is not an integer.
+    pub fn build(self) -> Result<Move, ArgumentError> {
+        let db = self.db.ok_or(ArgumentError::Missing)?;
+
+        Ok(Move {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            db: db.parse().map_err(|_| ArgumentError::Integer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kvstore::Expiry;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    fn r#move(session: &mut Session, key: &str, db: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .db(db)
+            .build()
+            .unwrap()
+            .execute_in(session)
+    }
+
+    #[test]
+    fn test_move_to_other_database() {
+        let mut session = Session::new(mpsc::channel(16).0);
+        session.store().set("move:key", "value");
+        session.store().expire("move:key", Duration::from_secs(100));
+
+        assert_eq!(r#move(&mut session, "move:key", "1"), Response::Integer(1));
+        assert!(!DATABASES.get(0).exists("move:key"));
+        assert_eq!(DATABASES.get(1).get("move:key"), Ok(Some("value".into())));
+        assert!(matches!(
+            DATABASES.get(1).ttl("move:key"),
+            Some(Expiry::In(_))
+        ));
+
+        // the key is gone from the source, and the target holds it, so neither moves it again
+        assert_eq!(r#move(&mut session, "move:key", "1"), Response::Integer(0));
+        session.store().set("move:key", "other");
+        assert_eq!(r#move(&mut session, "move:key", "1"), Response::Integer(0));
+        assert_eq!(session.store().get("move:key"), Ok(Some("other".into())));
+    }
+
+    #[test]
+    fn test_move_invalid_database() {
+        let mut session = Session::new(mpsc::channel(16).0);
+        session.store().set("move:same", "value");
+
+        assert_eq!(
+            r#move(&mut session, "move:same", "0"),
+            Response::err("ERR", "source and destination objects are the same")
+        );
+        for db in ["16", "-1"] {
+            assert_eq!(
+                r#move(&mut session, "move:same", db),
+                Response::err("ERR", "DB index is out of range")
+            );
+        }
+        assert_eq!(session.store().get("move:same"), Ok(Some("value".into())));
+
+        let result = Builder::new().key("move:same").db("one").build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
@@ -0,0 +1,55 @@
+/// This module contains the implementation of the `Multi` command.
+/// The `Multi` command starts a transaction: the client's following commands are queued
+/// rather than executed, until `EXEC` executes them or `DISCARD` drops them.
+use crate::{response::types::Response, session::Session};
+
+use super::types::ExecuteInSession;
+
+/// Represents the `Multi` command.
+pub struct Multi;
+
+impl ExecuteInSession for Multi {
+    /// Executes the `Multi` command and returns `OK` once the transaction has started.
+    fn execute_in(self, session: &mut Session) -> Response {
+        if session.begin_transaction() {
+            Response::ss("OK")
+        } else {
+            Response::err("ERR", "MULTI calls can not be nested")
+        }
+    }
+}
+
+/// Builder for the `Multi` command.
+pub struct Builder;
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Builds the `Multi` command, which takes no arguments.
+    #[allow(clippy::unused_self)] // consumed like every other builder
+    pub const fn build(self) -> Multi {
+        Multi
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_multi_nested() {
+        let mut session = Session::new(mpsc::channel(16).0);
+
+        assert_eq!(Multi.execute_in(&mut session), Response::ss("OK"));
+        assert!(session.in_transaction());
+        assert_eq!(
+            Multi.execute_in(&mut session),
+            Response::err("ERR", "MULTI calls can not be nested")
+        );
+        assert!(session.in_transaction());
+    }
+}
@@ -0,0 +1,158 @@
+/// This module contains the implementation of the `Object` command,
+/// which inspects the internals of the value stored at a key.
+use super::{
+    config::CommandBuildError,
+    types::{ArgumentError, ExecuteOn, SubcommandError},
+};
+
+use crate::{kvstore::KvStore, response::types::Response};
+
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct Object {
+    subcommand: ObjectSubcommand,
+    args: Vec<String>,
+}
+
+#[derive(Debug)]
+enum ObjectSubcommand {
+    Encoding,
+    RefCount,
+    IdleTime,
+}
+
+impl FromStr for ObjectSubcommand {
+    type Err = SubcommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "ENCODING" => Ok(Self::Encoding),
+            "REFCOUNT" => Ok(Self::RefCount),
+            "IDLETIME" => Ok(Self::IdleTime),
+            _ => Err(Self::Err::Unknown),
+        }
+    }
+}
+
+impl ExecuteOn for Object {
+    fn execute_on(self, store: &KvStore) -> Response {
+        let Some(key) = self.args.first() else {
+            return Response::from(ArgumentError::Missing);
+        };
+
+        match &self.subcommand {
+            ObjectSubcommand::Encoding => store.encoding(key).map_or(Response::Null, Response::bs),
+            // values are never shared between keys
+            ObjectSubcommand::RefCount => {
+                if store.exists(key) {
+                    Response::Integer(1)
+                } else {
+                    Response::Null
+                }
+            }
+            ObjectSubcommand::IdleTime => store.idle_time(key).map_or_else(
+                || Response::err("ERR", "no such key"),
+                |idle| Response::Integer(i64::try_from(idle.as_secs()).unwrap_or(i64::MAX)),
+            ),
+        }
+    }
+}
+
+pub struct Builder {
+    args_raw: Option<Vec<String>>,
+}
+
+impl Builder {
+    pub const fn new() -> Self {
+        Self { args_raw: None }
+    }
+
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args_raw = Some(args);
+        self
+    }
+
+    pub fn build(self) -> Result<Object, CommandBuildError> {
+        let Some(args) = self.args_raw else {
+            return Err(CommandBuildError::Subcommand(SubcommandError::Missing));
+        };
+
+        let subcommand = match args.first() {
+            Some(sub) => ObjectSubcommand::from_str(sub)?,
+            None => return Err(CommandBuildError::Subcommand(SubcommandError::Missing)),
+        };
+
+        match &args[1..] {
+            [key] => Ok(Object {
+                subcommand,
+                args: vec![key.clone()],
+            }),
+            [] => Err(CommandBuildError::Argument(ArgumentError::Missing)),
+            _ => Err(CommandBuildError::Argument(ArgumentError::Syntax)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(args: &[&str]) -> Object {
+        Builder::new()
+            .args(args.iter().map(ToString::to_string).collect())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_object_encoding() {
+        let store = KvStore::new();
+        store.set("object:int", "12345");
+        store.set("object:embstr", "hello");
+        store.set("object:raw", &"x".repeat(64));
+
+        assert_eq!(
+            object(&["ENCODING", "object:int"]).execute_on(&store),
+            Response::bs("int")
+        );
+        assert_eq!(
+            object(&["ENCODING", "object:embstr"]).execute_on(&store),
+            Response::bs("embstr")
+        );
+        assert_eq!(
+            object(&["ENCODING", "object:raw"]).execute_on(&store),
+            Response::bs("raw")
+        );
+        assert_eq!(
+            object(&["ENCODING", "object:missing"]).execute_on(&store),
+            Response::Null
+        );
+    }
+
+    #[test]
+    fn test_object_refcount() {
+        let store = KvStore::new();
+        store.set("object:refcount", "value");
+
+        assert_eq!(
+            object(&["REFCOUNT", "object:refcount"]).execute_on(&store),
+            Response::Integer(1)
+        );
+    }
+
+    #[test]
+    fn test_object_idletime() {
+        let store = KvStore::new();
+        store.set("object:idletime", "value");
+
+        assert_eq!(
+            object(&["IDLETIME", "object:idletime"]).execute_on(&store),
+            Response::Integer(0)
+        );
+        assert_eq!(
+            object(&["IDLETIME", "object:missing"]).execute_on(&store),
+            Response::err("ERR", "no such key")
+        );
+    }
+}
@@ -0,0 +1,81 @@
+/// This module contains the implementation of the `Persist` command.
+/// The `Persist` command removes the expiry of a key, making it permanent.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `Persist` command.
+pub struct Persist {
+    key: String,
+}
+
+impl ExecuteOn for Persist {
+    /// Executes the `Persist` command and returns `1` if an expiry was removed, `0` otherwise.
+    fn execute_on(self, store: &KvStore) -> Response {
+        Response::Integer(store.persist(&self.key).into())
+    }
+}
+
+/// Builder for the `Persist` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key for the `Persist` command.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `Persist` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<Persist, ArgumentError> {
+        Ok(Persist {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::{expire, ttl};
+
+    #[test]
+    fn test_persist_removes_expiry() {
+        let store = KvStore::new();
+        store.set("persist:key", "value");
+
+        let cmd = expire::Builder::new()
+            .key("persist:key")
+            .ttl("100")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+
+        let cmd = Builder::new().key("persist:key").build().unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+
+        let cmd = ttl::Builder::new().key("persist:key").build().unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(-1));
+
+        let cmd = Builder::new().key("persist:key").build().unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(0));
+    }
+
+    #[test]
+    fn test_persist_missing_key() {
+        let store = KvStore::new();
+        let cmd = Builder::new().key("persist:missing").build().unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(0));
+    }
+}
@@ -2,9 +2,11 @@
 /// The `Ping` command is used to check if the server is alive.
 /// It returns a response with the message "PONG" if the server is alive.
 /// If a custom message is provided, it returns the custom message instead.
-use super::types::Execute;
+/// A subscribed client is replied to in the pub/sub framing instead,
+/// with an array of "pong" and the message, empty if none was provided.
+use super::types::ExecuteInSession;
 
-use crate::response::types::Response;
+use crate::{response::types::Response, session::Session};
 
 use std::fmt;
 
@@ -13,9 +15,17 @@ pub struct Ping {
     message: Option<String>,
 }
 
-impl Execute for Ping {
-    /// Executes the `Ping` command and returns the response.
-    fn execute(self) -> Response {
+impl ExecuteInSession for Ping {
+    /// Executes the `Ping` command and returns the response,
+    /// framed as a pub/sub message if the session is subscribed to anything.
+    fn execute_in(self, session: &mut Session) -> Response {
+        if session.subscription_count() > 0 {
+            return Response::Array(vec![
+                Response::bs("pong"),
+                Response::BulkString(self.message.unwrap_or_default().into()),
+            ]);
+        }
+
         self.message.as_ref().map_or_else(
             || Response::SimpleString("PONG".into()),
             |s| Response::ss(s),
@@ -60,3 +70,49 @@ impl Builder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_ping() {
+        let mut session = Session::new(mpsc::channel(16).0);
+        assert_eq!(
+            Builder::new().build().execute_in(&mut session),
+            Response::ss("PONG")
+        );
+        assert_eq!(
+            Builder::new()
+                .message("hello")
+                .build()
+                .execute_in(&mut session),
+            Response::ss("hello")
+        );
+    }
+
+    #[test]
+    fn test_ping_subscribed() {
+        let mut session = Session::new(mpsc::channel(16).0);
+        session.subscribe("ping:channel");
+
+        assert_eq!(
+            String::from(Builder::new().build().execute_in(&mut session)),
+            "*2\r\n$4\r\npong\r\n$0\r\n\r\n"
+        );
+        assert_eq!(
+            Builder::new()
+                .message("hello")
+                .build()
+                .execute_in(&mut session),
+            Response::arr(vec!["pong".into(), "hello".into()])
+        );
+
+        session.unsubscribe("ping:channel");
+        assert_eq!(
+            Builder::new().build().execute_in(&mut session),
+            Response::ss("PONG")
+        );
+    }
+}
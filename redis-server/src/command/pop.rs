@@ -0,0 +1,178 @@
+/// This module contains the implementation of the `Pop` command.
+/// `LPOP` removes and returns elements from the head of a list, and `RPOP` from its tail,
+/// removing the list once it is empty.
+///
+/// Without a count, a single element is returned, or null if the key does not exist.
+/// With a count, an array of up to that many elements is returned,
+/// or a null array if the key does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `Pop` command, covering both `LPOP` and `RPOP`.
+pub struct Pop {
+    key: String,
+    count: Option<usize>,
+    tail: bool,
+}
+
+impl ExecuteOn for Pop {
+    /// Executes the `Pop` command and returns the popped elements.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let popped = match store.pop(&self.key, self.count.unwrap_or(1), self.tail) {
+            Ok(popped) => popped,
+            Err(error) => return Response::from(error),
+        };
+
+        match (popped, self.count) {
+            (Some(elements), Some(_)) => Response::arr(elements),
+            (Some(elements), None) => elements
+                .into_iter()
+                .next()
+                .map_or(Response::Null, |element| {
+                    Response::BulkString(element.into())
+                }),
+            (None, Some(_)) => Response::NullArray,
+            (None, None) => Response::Null,
+        }
+    }
+}
+
+/// Builder for the `Pop` command.
+pub struct Builder {
+    key: Option<String>,
+    count: Option<String>,
+    tail: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `LPOP`.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            count: None,
+            tail: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `RPOP`.
+    pub const fn new_tail() -> Self {
+        Self {
+            key: None,
+            count: None,
+            tail: true,
+        }
+    }
+
+    /// Sets the key of the list.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the number of elements to pop.
+    pub fn count(mut self, count: &str) -> Self {
+        self.count = Some(count.into());
+        self
+    }
+
+    /// Builds the `Pop` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided,
+    /// an `ArgumentError::Integer` if the count is not an integer,
+    /// or an `ArgumentError::Positive` if it is negative.
+    pub fn build(self) -> Result<Pop, ArgumentError> {
+        let count = match self.count {
+            Some(count) => {
+                let count: i64 = count.parse().map_err(|_| ArgumentError::Integer)?;
+                Some(usize::try_from(count).map_err(|_| ArgumentError::Positive)?)
+            }
+            None => None,
+        };
+
+        Ok(Pop {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            count,
+            tail: self.tail,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(store: &KvStore, key: &str, elements: &[&str]) {
+        let elements: Vec<String> = elements.iter().map(ToString::to_string).collect();
+        store.push(key, &elements, true).unwrap();
+    }
+
+    fn pop(store: &KvStore, builder: Builder, key: &str, count: Option<&str>) -> Response {
+        let builder = builder.key(key);
+        match count {
+            Some(count) => builder.count(count),
+            None => builder,
+        }
+        .build()
+        .unwrap()
+        .execute_on(store)
+    }
+
+    #[test]
+    fn test_pop_last_element_deletes_key() {
+        let store = KvStore::new();
+        push(&store, "pop:last", &["a", "b"]);
+        assert_eq!(
+            pop(&store, Builder::new(), "pop:last", None),
+            Response::bs("a")
+        );
+        assert_eq!(
+            pop(&store, Builder::new_tail(), "pop:last", None),
+            Response::bs("b")
+        );
+        assert!(!store.exists("pop:last"));
+        assert_eq!(
+            pop(&store, Builder::new(), "pop:last", None),
+            Response::Null
+        );
+    }
+
+    #[test]
+    fn test_pop_count_larger_than_list() {
+        let store = KvStore::new();
+        push(&store, "pop:count", &["a", "b", "c"]);
+        assert_eq!(
+            pop(&store, Builder::new_tail(), "pop:count", Some("2")),
+            Response::arr(vec!["c".into(), "b".into()])
+        );
+        assert_eq!(
+            pop(&store, Builder::new(), "pop:count", Some("10")),
+            Response::arr(vec!["a".into()])
+        );
+        assert!(!store.exists("pop:count"));
+        assert_eq!(
+            pop(&store, Builder::new(), "pop:count", Some("10")),
+            Response::NullArray
+        );
+    }
+
+    #[test]
+    fn test_pop_zero_and_negative_count() {
+        let store = KvStore::new();
+        push(&store, "pop:zero", &["a"]);
+        assert_eq!(
+            pop(&store, Builder::new(), "pop:zero", Some("0")),
+            Response::Array(Vec::new())
+        );
+        assert_eq!(store.llen("pop:zero"), Ok(1));
+
+        let result = Builder::new().key("pop:zero").count("-1").build();
+        assert!(matches!(result, Err(ArgumentError::Positive)));
+        assert_eq!(
+            Response::from(ArgumentError::Positive),
+            Response::err("ERR", "value is out of range, must be positive")
+        );
+    }
+}
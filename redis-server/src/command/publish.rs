@@ -0,0 +1,60 @@
+/// This module contains the implementation of the `Publish` command.
+/// The `Publish` command delivers a message to all clients subscribed to a channel,
+/// either directly or through a matching pattern, and returns the number of receivers.
+use crate::{pubsub::PUBSUB, response::types::Response};
+
+use super::types::{ArgumentError, Execute};
+
+/// Represents the `Publish` command.
+pub struct Publish {
+    channel: String,
+    message: String,
+}
+
+impl Execute for Publish {
+    /// Executes the `Publish` command and returns the number of clients that received the message.
+    fn execute(self) -> Response {
+        let receivers = PUBSUB.publish(&self.channel, &self.message);
+        Response::Integer(i64::try_from(receivers).unwrap_or(i64::MAX))
+    }
+}
+
+/// Builder for the `Publish` command.
+pub struct Builder {
+    channel: Option<String>,
+    message: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            channel: None,
+            message: None,
+        }
+    }
+
+    /// Sets the channel to publish to.
+    pub fn channel(mut self, channel: &str) -> Self {
+        self.channel = Some(channel.into());
+        self
+    }
+
+    /// Sets the message to publish.
+    pub fn message(mut self, message: &str) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Builds the `Publish` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the channel or message is not provided.
+    pub fn build(self) -> Result<Publish, ArgumentError> {
+        Ok(Publish {
+            channel: self.channel.ok_or(ArgumentError::Missing)?,
+            message: self.message.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
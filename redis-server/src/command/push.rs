@@ -0,0 +1,122 @@
+/// This module contains the implementation of the `Push` command.
+/// `LPUSH` pushes elements onto the head of a list, and `RPUSH` onto its tail,
+/// creating the list if needed, and both reply with the length of the list afterwards.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `Push` command, covering both `LPUSH` and `RPUSH`.
+pub struct Push {
+    key: String,
+    elements: Vec<String>,
+    tail: bool,
+}
+
+impl ExecuteOn for Push {
+    /// Executes the `Push` command and returns the length of the list.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.push(&self.key, &self.elements, self.tail) {
+            Ok(len) => Response::Integer(i64::try_from(len).unwrap_or(i64::MAX)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `Push` command.
+pub struct Builder {
+    key: Option<String>,
+    elements: Option<Vec<String>>,
+    tail: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `LPUSH`.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            elements: None,
+            tail: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `RPUSH`.
+    pub const fn new_tail() -> Self {
+        Self {
+            key: None,
+            elements: None,
+            tail: true,
+        }
+    }
+
+    /// Sets the key of the list.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the elements to push, in the order they are pushed.
+    pub fn elements(mut self, elements: &[String]) -> Self {
+        self.elements = Some(elements.to_vec());
+        self
+    }
+
+    /// Builds the `Push` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key or elements are not provided.
+    pub fn build(self) -> Result<Push, ArgumentError> {
+        let elements = self.elements.ok_or(ArgumentError::Missing)?;
+        if elements.is_empty() {
+            return Err(ArgumentError::Missing);
+        }
+
+        Ok(Push {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            elements,
+            tail: self.tail,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(store: &KvStore, builder: Builder, key: &str, elements: &[&str]) -> Response {
+        let elements: Vec<String> = elements.iter().map(ToString::to_string).collect();
+        builder
+            .key(key)
+            .elements(&elements)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_push() {
+        let store = KvStore::new();
+        assert_eq!(
+            push(&store, Builder::new(), "push:list", &["b", "a"]),
+            Response::Integer(2)
+        );
+        assert_eq!(
+            push(&store, Builder::new_tail(), "push:list", &["c", "d"]),
+            Response::Integer(4)
+        );
+        assert_eq!(
+            store.pop("push:list", 4, false),
+            Ok(Some(vec!["a".into(), "b".into(), "c".into(), "d".into()]))
+        );
+    }
+
+    #[test]
+    fn test_push_wrong_type() {
+        let store = KvStore::new();
+        store.set("push:string", "value");
+        assert_eq!(
+            push(&store, Builder::new(), "push:string", &["a"]),
+            Response::from(crate::kvstore::WrongType)
+        );
+    }
+}
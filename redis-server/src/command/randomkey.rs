@@ -0,0 +1,62 @@
+/// This module contains the implementation of the `RandomKey` command.
+/// The `RandomKey` command returns a random key from the key-value store,
+/// or null if the store is empty.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::ExecuteOn;
+
+/// Represents the `RandomKey` command.
+pub struct RandomKey;
+
+impl ExecuteOn for RandomKey {
+    /// Executes the `RandomKey` command and returns a random key.
+    fn execute_on(self, store: &KvStore) -> Response {
+        store
+            .random_key()
+            .map_or(Response::Null, |key| Response::bs(&key))
+    }
+}
+
+/// Builder for the `RandomKey` command.
+pub struct Builder;
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Builds the `RandomKey` command, which takes no arguments.
+    #[allow(clippy::unused_self)] // consumed like every other builder
+    pub const fn build(self) -> RandomKey {
+        RandomKey
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_randomkey() {
+        let store = KvStore::new();
+        store.set("a", "1");
+        store.set("b", "2");
+        store.sadd("c", &["3".into()]).unwrap();
+        store.set("expired", "4");
+        store.expire("expired", Duration::ZERO);
+
+        for _ in 0..20 {
+            let Response::BulkString(key) = RandomKey.execute_on(&store) else {
+                panic!("RANDOMKEY should reply with a key");
+            };
+            assert!([&b"a"[..], b"b", b"c"].contains(&key.as_slice()));
+        }
+    }
+
+    #[test]
+    fn test_randomkey_empty() {
+        assert_eq!(RandomKey.execute_on(&KvStore::new()), Response::Null);
+    }
+}
@@ -0,0 +1,154 @@
+/// This module contains the implementation of the `Rename` command.
+/// `RENAME` moves a key to a new name, overwriting the destination if it exists.
+/// `RENAMENX` does the same, but only if the destination does not exist yet.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `Rename` command, covering both `RENAME` and `RENAMENX`.
+pub struct Rename {
+    key: String,
+    new_key: String,
+    nx: bool,
+}
+
+impl ExecuteOn for Rename {
+    /// Executes the `Rename` command by moving the value of the key to the new key.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.rename(&self.key, &self.new_key, !self.nx) {
+            None => Response::err("", "no such key"),
+            Some(renamed) if self.nx => Response::Integer(renamed.into()),
+            Some(_) => Response::ss("OK"),
+        }
+    }
+}
+
+/// Builder for the `Rename` command.
+pub struct Builder {
+    key: Option<String>,
+    new_key: Option<String>,
+    nx: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `RENAME`.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            new_key: None,
+            nx: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `RENAMENX`.
+    pub const fn new_nx() -> Self {
+        Self {
+            key: None,
+            new_key: None,
+            nx: true,
+        }
+    }
+
+    /// Sets the key to rename.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the new name for the key.
+    pub fn new_key(mut self, new_key: &str) -> Self {
+        self.new_key = Some(new_key.into());
+        self
+    }
+
+    /// Builds the `Rename` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if either key is not provided.
+    pub fn build(self) -> Result<Rename, ArgumentError> {
+        Ok(Rename {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            new_key: self.new_key.ok_or(ArgumentError::Missing)?,
+            nx: self.nx,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_missing_source() {
+        let store = KvStore::new();
+        let cmd = Builder::new()
+            .key("rename:missing")
+            .new_key("rename:missing:dest")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::err("", "no such key"));
+        assert!(!store.exists("rename:missing:dest"));
+    }
+
+    #[test]
+    fn test_rename_overwrites_destination() {
+        let store = KvStore::new();
+        store.set("rename:src", "value");
+        store.set("rename:dest", "old");
+
+        let cmd = Builder::new()
+            .key("rename:src")
+            .new_key("rename:dest")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::ss("OK"));
+        assert!(!store.exists("rename:src"));
+        assert_eq!(store.get("rename:dest"), Ok(Some("value".into())));
+    }
+
+    #[test]
+    fn test_rename_same_key() {
+        let store = KvStore::new();
+        store.set("rename:same", "value");
+
+        let cmd = Builder::new()
+            .key("rename:same")
+            .new_key("rename:same")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::ss("OK"));
+        assert_eq!(store.get("rename:same"), Ok(Some("value".into())));
+    }
+
+    #[test]
+    fn test_renamenx_collision() {
+        let store = KvStore::new();
+        store.set("renamenx:src", "value");
+        store.set("renamenx:dest", "old");
+
+        let cmd = Builder::new_nx()
+            .key("renamenx:src")
+            .new_key("renamenx:dest")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(0));
+        assert_eq!(store.get("renamenx:src"), Ok(Some("value".into())));
+        assert_eq!(store.get("renamenx:dest"), Ok(Some("old".into())));
+    }
+
+    #[test]
+    fn test_renamenx_success() {
+        let store = KvStore::new();
+        store.set("renamenx:fresh:src", "value");
+
+        let cmd = Builder::new_nx()
+            .key("renamenx:fresh:src")
+            .new_key("renamenx:fresh:dest")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+        assert!(!store.exists("renamenx:fresh:src"));
+        assert_eq!(store.get("renamenx:fresh:dest"), Ok(Some("value".into())));
+    }
+}
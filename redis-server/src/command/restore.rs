@@ -0,0 +1,214 @@
+/// This module contains the implementation of the `Restore` command.
+/// The `Restore` command recreates a key from a value serialized by `DUMP`,
+/// with a time to live in milliseconds, or none if it is `0`.
+/// An existing key is only overwritten when the `REPLACE` option is given.
+use std::time::Duration;
+
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::{
+    dump,
+    types::{ArgumentError, ExecuteOn},
+};
+
+/// Represents the `Restore` command.
+pub struct Restore {
+    key: String,
+    ttl: Option<Duration>,
+    serialized: String,
+    replace: bool,
+}
+
+impl ExecuteOn for Restore {
+    /// Executes the `Restore` command and returns `OK` once the key is restored.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let Some(payload) = dump::decode(&self.serialized) else {
+            return Response::err("ERR", "DUMP payload version or checksum are wrong");
+        };
+
+        match store.restore_value(&self.key, &payload, self.ttl, self.replace) {
+            Some(true) => Response::ss("OK"),
+            Some(false) => Response::err("BUSYKEY", "Target key name already exists."),
+            None => Response::err("ERR", "Bad data format"),
+        }
+    }
+}
+
+/// Builder for the `Restore` command.
+pub struct Builder {
+    key: Option<String>,
+    ttl: Option<String>,
+    serialized: Option<String>,
+    options: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            ttl: None,
+            serialized: None,
+            options: Vec::new(),
+        }
+    }
+
+    /// Sets the key to restore.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the time to live of the key in milliseconds, `0` meaning none.
+    pub fn ttl(mut self, ttl: &str) -> Self {
+        self.ttl = Some(ttl.into());
+        self
+    }
+
+    /// Sets the value serialized by `DUMP`.
+    pub fn serialized(mut self, serialized: &str) -> Self {
+        self.serialized = Some(serialized.into());
+        self
+    }
+
+    /// Sets the trailing options for the `Restore` command.
+    pub fn options(mut self, options: &[String]) -> Self {
+        self.options = options.to_vec();
+        self
+    }
+
+    /// Builds the `Restore` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if an argument is not provided,
+    /// an `ArgumentError::Integer` if the time to live is not an integer,
+    /// an `ArgumentError::Positive` if it is negative,
+    /// or an `ArgumentError::Syntax` if an option other than `REPLACE` is given.
+    pub fn build(self) -> Result<Restore, ArgumentError> {
+        let mut replace = false;
+        for option in &self.options {
+            match option.to_uppercase().as_str() {
+                "REPLACE" => replace = true,
+                _ => return Err(ArgumentError::Syntax),
+            }
+        }
+
+        let ttl: i64 = self
+            .ttl
+            .ok_or(ArgumentError::Missing)?
+            .parse()
+            .map_err(|_| ArgumentError::Integer)?;
+        let ttl = u64::try_from(ttl).map_err(|_| ArgumentError::Positive)?;
+
+        Ok(Restore {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            ttl: (ttl > 0).then(|| Duration::from_millis(ttl)),
+            serialized: self.serialized.ok_or(ArgumentError::Missing)?,
+            replace,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kvstore::Expiry;
+
+    fn dump(store: &KvStore, key: &str) -> String {
+        let Response::BulkString(serialized) = dump::Builder::new()
+            .key(key)
+            .build()
+            .unwrap()
+            .execute_on(store)
+        else {
+            panic!("DUMP should reply with the serialized value");
+        };
+        String::from_utf8(serialized).unwrap()
+    }
+
+    fn restore(
+        store: &KvStore,
+        key: &str,
+        ttl: &str,
+        serialized: &str,
+        options: &[&str],
+    ) -> Response {
+        let options: Vec<String> = options.iter().map(ToString::to_string).collect();
+        Builder::new()
+            .key(key)
+            .ttl(ttl)
+            .serialized(serialized)
+            .options(&options)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_dump_del_restore() {
+        let store = KvStore::new();
+        store.set("restore:string", "value");
+        store.hset("restore:hash", "field", "1").unwrap();
+        store
+            .push("restore:list", &["a".into(), "b".into()], true)
+            .unwrap();
+
+        for key in ["restore:string", "restore:hash", "restore:list"] {
+            let serialized = dump(&store, key);
+            assert!(store.del(key));
+            assert_eq!(
+                restore(&store, key, "0", &serialized, &[]),
+                Response::ss("OK")
+            );
+        }
+        assert_eq!(store.get("restore:string"), Ok(Some("value".into())));
+        assert_eq!(store.ttl("restore:string"), Some(Expiry::Persistent));
+        assert_eq!(store.hget("restore:hash", "field"), Ok(Some("1".into())));
+        assert_eq!(
+            store.pop("restore:list", 2, false),
+            Ok(Some(vec!["a".into(), "b".into()]))
+        );
+    }
+
+    #[test]
+    fn test_restore_existing_key() {
+        let store = KvStore::new();
+        store.set("restore:existing", "old");
+        store.set("restore:source", "new");
+        let serialized = dump(&store, "restore:source");
+
+        assert_eq!(
+            restore(&store, "restore:existing", "0", &serialized, &[]),
+            Response::err("BUSYKEY", "Target key name already exists.")
+        );
+        assert_eq!(
+            restore(
+                &store,
+                "restore:existing",
+                "100000",
+                &serialized,
+                &["REPLACE"]
+            ),
+            Response::ss("OK")
+        );
+        assert_eq!(store.get("restore:existing"), Ok(Some("new".into())));
+        assert!(matches!(store.ttl("restore:existing"), Some(Expiry::In(_))));
+    }
+
+    #[test]
+    fn test_restore_invalid() {
+        let store = KvStore::new();
+        assert_eq!(
+            restore(&store, "restore:invalid", "0", "00", &[]),
+            Response::err("ERR", "DUMP payload version or checksum are wrong")
+        );
+
+        let result = Builder::new()
+            .key("restore:invalid")
+            .ttl("-1")
+            .serialized("00")
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Positive)));
+    }
+}
@@ -0,0 +1,104 @@
+/// This module contains the implementation of the `SAdd` command.
+/// The `SAdd` command adds members to a set, creating the set if needed,
+/// and replies with the number of members that were not already in the set.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `SAdd` command.
+pub struct SAdd {
+    key: String,
+    members: Vec<String>,
+}
+
+impl ExecuteOn for SAdd {
+    /// Executes the `SAdd` command and returns the number of added members.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.sadd(&self.key, &self.members) {
+            Ok(added) => Response::Integer(i64::try_from(added).unwrap_or(i64::MAX)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `SAdd` command.
+pub struct Builder {
+    key: Option<String>,
+    members: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            members: None,
+        }
+    }
+
+    /// Sets the key of the set.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the members to add.
+    pub fn members(mut self, members: &[String]) -> Self {
+        self.members = Some(members.to_vec());
+        self
+    }
+
+    /// Builds the `SAdd` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key or members are not provided.
+    pub fn build(self) -> Result<SAdd, ArgumentError> {
+        let members = self.members.ok_or(ArgumentError::Missing)?;
+        if members.is_empty() {
+            return Err(ArgumentError::Missing);
+        }
+
+        Ok(SAdd {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            members,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sadd(store: &KvStore, key: &str, members: &[&str]) -> Response {
+        let members: Vec<String> = members.iter().map(ToString::to_string).collect();
+        Builder::new()
+            .key(key)
+            .members(&members)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_sadd() {
+        let store = KvStore::new();
+        assert_eq!(
+            sadd(&store, "sadd:set", &["a", "b", "a"]),
+            Response::Integer(2)
+        );
+        assert_eq!(sadd(&store, "sadd:set", &["a"]), Response::Integer(0));
+        assert_eq!(sadd(&store, "sadd:set", &["b", "c"]), Response::Integer(1));
+        assert_eq!(store.scard("sadd:set"), Ok(3));
+    }
+
+    #[test]
+    fn test_sadd_wrong_type() {
+        let store = KvStore::new();
+        store.set("sadd:string", "value");
+        assert!(matches!(
+            sadd(&store, "sadd:string", &["a"]),
+            Response::Error(_)
+        ));
+    }
+}
@@ -0,0 +1,74 @@
+/// This module contains the implementation of the `SCard` command.
+/// The `SCard` command returns the number of members of a set, or `0` if it does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `SCard` command.
+pub struct SCard {
+    key: String,
+}
+
+impl ExecuteOn for SCard {
+    /// Executes the `SCard` command and returns the cardinality of the set.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.scard(&self.key) {
+            Ok(len) => Response::Integer(i64::try_from(len).unwrap_or(i64::MAX)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `SCard` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key of the set.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `SCard` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<SCard, ArgumentError> {
+        Ok(SCard {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scard(store: &KvStore, key: &str) -> Response {
+        Builder::new().key(key).build().unwrap().execute_on(store)
+    }
+
+    #[test]
+    fn test_scard() {
+        let store = KvStore::new();
+        store.sadd("scard:set", &["a".into(), "b".into()]).unwrap();
+
+        assert_eq!(scard(&store, "scard:set"), Response::Integer(2));
+        assert_eq!(scard(&store, "scard:missing"), Response::Integer(0));
+    }
+
+    #[test]
+    fn test_scard_wrong_type() {
+        let store = KvStore::new();
+        store.set("scard:string", "value");
+        assert!(matches!(scard(&store, "scard:string"), Response::Error(_)));
+    }
+}
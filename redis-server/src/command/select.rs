@@ -0,0 +1,101 @@
+/// This module contains the implementation of the `Select` command.
+/// The `Select` command switches the client to another database, numbered from `0`,
+/// which its following commands are executed against.
+use crate::{kvstore, response::types::Response, session::Session};
+
+use super::types::{ArgumentError, ExecuteInSession};
+
+/// Represents the `Select` command.
+pub struct Select {
+    index: i64,
+}
+
+impl ExecuteInSession for Select {
+    /// Executes the `Select` command and returns `OK` once the client has switched databases.
+    fn execute_in(self, session: &mut Session) -> Response {
+        match kvstore::database_index(self.index) {
+            Some(index) => {
+                session.select(index);
+                Response::ss("OK")
+            }
+            None => Response::err("ERR", "DB index is out of range"),
+        }
+    }
+}
+
+/// Builder for the `Select` command.
+pub struct Builder {
+    index: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { index: None }
+    }
+
+    /// Sets the index of the database to switch to.
+    pub fn index(mut self, index: &str) -> Self {
+        self.index = Some(index.into());
+        self
+    }
+
+    /// Builds the `Select` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the index is not provided,
+    /// or an `ArgumentError::Integer` if it is not an integer.
+    pub fn build(self) -> Result<Select, ArgumentError> {
+        let index = self.index.ok_or(ArgumentError::Missing)?;
+
+        Ok(Select {
+            index: index.parse().map_err(|_| ArgumentError::Integer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kvstore::DATABASES;
+    use tokio::sync::mpsc;
+
+    fn select(session: &mut Session, index: &str) -> Response {
+        Builder::new()
+            .index(index)
+            .build()
+            .unwrap()
+            .execute_in(session)
+    }
+
+    #[test]
+    fn test_select() {
+        let mut session = Session::new(mpsc::channel(16).0);
+
+        assert_eq!(select(&mut session, "2"), Response::ss("OK"));
+        assert_eq!(session.db(), 2);
+        session.store().set("select:key", "value");
+        assert_eq!(DATABASES.get(2).get("select:key"), Ok(Some("value".into())));
+        assert!(!DATABASES.get(0).exists("select:key"));
+
+        assert_eq!(select(&mut session, "0"), Response::ss("OK"));
+        assert!(!session.store().exists("select:key"));
+    }
+
+    #[test]
+    fn test_select_out_of_range() {
+        let mut session = Session::new(mpsc::channel(16).0);
+
+        for index in ["16", "-1"] {
+            assert_eq!(
+                select(&mut session, index),
+                Response::err("ERR", "DB index is out of range")
+            );
+        }
+        assert_eq!(session.db(), 0);
+
+        let result = Builder::new().index("zero").build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::response::types::Response;
+use crate::{kvstore::KvStore, response::types::Response};
 
-use super::types::{ArgumentError, Execute};
+use super::types::{ArgumentError, ExecuteOn};
 
 /// Represents a command to set a key-value pair in the key-value store.
 pub struct Set {
@@ -8,11 +8,11 @@ pub struct Set {
     value: String,
 }
 
-impl Execute for Set {
+impl ExecuteOn for Set {
     /// Executes the set command by storing the key-value pair in the key-value store.
     /// Returns a response indicating the success of the operation.
-    fn execute(self) -> Response {
-        crate::kvstore::KV_STORE.set(&self.key, &self.value);
+    fn execute_on(self, store: &KvStore) -> Response {
+        store.set(&self.key, &self.value);
         Response::ss("OK")
     }
 }
@@ -0,0 +1,167 @@
+/// This module contains the implementation of the `SetBit` command.
+/// The `SetBit` command sets or clears a bit of a string, growing it with zero bytes as needed,
+/// and replies with the previous value of the bit.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// The number of bits in the largest string, like Redis's `proto-max-bulk-len`.
+pub const MAX_BITS: usize = 512 * 1024 * 1024 * 8;
+
+/// Represents the `SetBit` command.
+pub struct SetBit {
+    key: String,
+    offset: usize,
+    bit: bool,
+}
+
+impl ExecuteOn for SetBit {
+    /// Executes the `SetBit` command and returns the previous value of the bit.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.setbit(&self.key, self.offset, self.bit) {
+            Ok(previous) => Response::Integer(previous.into()),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `SetBit` command.
+pub struct Builder {
+    key: Option<String>,
+    offset: Option<String>,
+    bit: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            offset: None,
+            bit: None,
+        }
+    }
+
+    /// Sets the key of the string.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the offset of the bit.
+    pub fn offset(mut self, offset: &str) -> Self {
+        self.offset = Some(offset.into());
+        self
+    }
+
+    /// Sets the value to set the bit to, `0` or `1`.
+    pub fn bit(mut self, bit: &str) -> Self {
+        self.bit = Some(bit.into());
+        self
+    }
+
+    /// Builds the `SetBit` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// an `ArgumentError::BitOffset` if the offset is not a non-negative integer
+    /// or the string would grow beyond 512 MB,
+    /// or an `ArgumentError::Bit` if the value is neither `0` nor `1`.
+    pub fn build(self) -> Result<SetBit, ArgumentError> {
+        let offset = self
+            .offset
+            .ok_or(ArgumentError::Missing)?
+            .parse()
+            .ok()
+            .filter(|&offset| offset < MAX_BITS)
+            .ok_or(ArgumentError::BitOffset)?;
+        let bit = match self.bit.ok_or(ArgumentError::Missing)?.as_str() {
+            "0" => false,
+            "1" => true,
+            _ => return Err(ArgumentError::Bit),
+        };
+
+        Ok(SetBit {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            offset,
+            bit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setbit(store: &KvStore, key: &str, offset: &str, bit: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .offset(offset)
+            .bit(bit)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_setbit() {
+        let store = KvStore::new();
+        assert_eq!(
+            setbit(&store, "setbit:bits", "7", "1"),
+            Response::Integer(0)
+        );
+        assert_eq!(store.get("setbit:bits"), Ok(Some("\x01".into())));
+        assert_eq!(
+            setbit(&store, "setbit:bits", "7", "1"),
+            Response::Integer(1)
+        );
+
+        assert_eq!(
+            setbit(&store, "setbit:bits", "100", "1"),
+            Response::Integer(0)
+        );
+        assert_eq!(
+            store.get("setbit:bits"),
+            Ok(Some(format!("\x01{}\x08", "\0".repeat(11)).into()))
+        );
+
+        assert_eq!(
+            setbit(&store, "setbit:bits", "7", "0"),
+            Response::Integer(1)
+        );
+        assert!(!store.getbit("setbit:bits", 7).unwrap());
+        assert!(store.getbit("setbit:bits", 100).unwrap());
+    }
+
+    #[test]
+    fn test_setbit_high_bit() {
+        let store = KvStore::new();
+        // the byte is 0x80, which is not valid UTF-8 on its own
+        assert_eq!(
+            setbit(&store, "setbit:high", "0", "1"),
+            Response::Integer(0)
+        );
+        assert_eq!(store.get("setbit:high"), Ok(Some(vec![0x80])));
+        assert!(store.getbit("setbit:high", 0).unwrap());
+        assert!((1..24).all(|offset| !store.getbit("setbit:high", offset).unwrap()));
+        assert_eq!(store.bitcount("setbit:high", None), Ok(1));
+    }
+
+    #[test]
+    fn test_setbit_invalid() {
+        let setbit = |offset: &str, bit: &str| {
+            Builder::new()
+                .key("setbit:invalid")
+                .offset(offset)
+                .bit(bit)
+                .build()
+        };
+        assert!(matches!(setbit("-1", "1"), Err(ArgumentError::BitOffset)));
+        assert!(matches!(
+            setbit("4294967296", "1"),
+            Err(ArgumentError::BitOffset)
+        ));
+        assert!(matches!(setbit("0", "2"), Err(ArgumentError::Bit)));
+    }
+}
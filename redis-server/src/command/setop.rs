@@ -0,0 +1,315 @@
+/// This module contains the implementation of the `SetOp` command.
+/// `SINTER`, `SUNION`, and `SDIFF` reply with the intersection, union, and difference
+/// of the sets stored at the given keys in sorted order, treating missing keys as empty sets.
+/// Their `*STORE` variants instead store the result at a destination key
+/// and reply with its cardinality.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// The set algebra computed by a `SetOp` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// `SINTER`: the members present in every set.
+    Inter,
+    /// `SUNION`: the members present in any set.
+    Union,
+    /// `SDIFF`: the members of the first set that are in none of the others.
+    Diff,
+}
+
+/// Represents the `SetOp` command, covering `SINTER`, `SUNION`, and `SDIFF`,
+/// along with their `*STORE` variants.
+pub struct SetOp {
+    destination: Option<String>,
+    keys: Vec<String>,
+    operation: Operation,
+}
+
+impl ExecuteOn for SetOp {
+    /// Executes the `SetOp` command and returns the computed members,
+    /// or their number if they were stored.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let result = match self.operation {
+            Operation::Inter => store.sinter(&self.keys),
+            Operation::Union => store.sunion(&self.keys),
+            Operation::Diff => store.sdiff(&self.keys),
+        };
+
+        match (result, self.destination) {
+            (Ok(members), None) => Response::arr(members),
+            (Ok(members), Some(destination)) => {
+                let len = store.store_set(&destination, members);
+                Response::Integer(i64::try_from(len).unwrap_or(i64::MAX))
+            }
+            (Err(error), _) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `SetOp` command.
+pub struct Builder {
+    destination: Option<String>,
+    keys: Option<Vec<String>>,
+    operation: Operation,
+    store: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `SINTER`.
+    pub const fn new() -> Self {
+        Self {
+            destination: None,
+            keys: None,
+            operation: Operation::Inter,
+            store: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `SUNION`.
+    pub const fn new_union() -> Self {
+        Self {
+            destination: None,
+            keys: None,
+            operation: Operation::Union,
+            store: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `SDIFF`.
+    pub const fn new_diff() -> Self {
+        Self {
+            destination: None,
+            keys: None,
+            operation: Operation::Diff,
+            store: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `SINTERSTORE`.
+    pub const fn new_store() -> Self {
+        Self::new().stored()
+    }
+
+    /// Creates a new `Builder` instance for `SUNIONSTORE`.
+    pub const fn new_union_store() -> Self {
+        Self::new_union().stored()
+    }
+
+    /// Creates a new `Builder` instance for `SDIFFSTORE`.
+    pub const fn new_diff_store() -> Self {
+        Self::new_diff().stored()
+    }
+
+    /// Makes the builder require a destination to store the result at.
+    const fn stored(mut self) -> Self {
+        self.store = true;
+        self
+    }
+
+    /// Sets the key to store the result at.
+    pub fn destination(mut self, destination: &str) -> Self {
+        self.destination = Some(destination.into());
+        self
+    }
+
+    /// Sets the keys of the sets.
+    pub fn keys(mut self, keys: &[String]) -> Self {
+        self.keys = Some(keys.to_vec());
+        self
+    }
+
+    /// Builds the `SetOp` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if no keys are provided,
+    /// or a `*STORE` variant is not given a destination.
+    pub fn build(self) -> Result<SetOp, ArgumentError> {
+        let keys = self.keys.ok_or(ArgumentError::Missing)?;
+        if keys.is_empty() || (self.store && self.destination.is_none()) {
+            return Err(ArgumentError::Missing);
+        }
+
+        Ok(SetOp {
+            destination: self.destination,
+            keys,
+            operation: self.operation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setop(store: &KvStore, builder: Builder, keys: &[&str]) -> Response {
+        let keys: Vec<String> = keys.iter().map(ToString::to_string).collect();
+        builder.keys(&keys).build().unwrap().execute_on(store)
+    }
+
+    fn members(members: &[&str]) -> Response {
+        Response::Array(members.iter().map(|member| Response::bs(member)).collect())
+    }
+
+    fn sadd(store: &KvStore, key: &str, members: &[&str]) {
+        let members: Vec<String> = members.iter().map(ToString::to_string).collect();
+        store.sadd(key, &members).unwrap();
+    }
+
+    #[test]
+    fn test_sinter() {
+        let store = KvStore::new();
+        sadd(&store, "setop:inter:a", &["a", "b", "c", "d"]);
+        sadd(&store, "setop:inter:b", &["d", "c", "e"]);
+        sadd(&store, "setop:inter:c", &["c", "d", "a"]);
+
+        assert_eq!(
+            setop(
+                &store,
+                Builder::new(),
+                &["setop:inter:a", "setop:inter:b", "setop:inter:c"]
+            ),
+            members(&["c", "d"])
+        );
+        assert_eq!(
+            setop(
+                &store,
+                Builder::new(),
+                &["setop:inter:a", "setop:inter:missing", "setop:inter:c"]
+            ),
+            members(&[])
+        );
+    }
+
+    #[test]
+    fn test_sunion() {
+        let store = KvStore::new();
+        sadd(&store, "setop:union:a", &["c", "a", "b"]);
+        sadd(&store, "setop:union:b", &["b", "d", "c"]);
+
+        assert_eq!(
+            setop(
+                &store,
+                Builder::new_union(),
+                &["setop:union:a", "setop:union:b", "setop:union:missing"]
+            ),
+            members(&["a", "b", "c", "d"])
+        );
+    }
+
+    #[test]
+    fn test_sdiff() {
+        let store = KvStore::new();
+        sadd(&store, "setop:diff:a", &["a", "b", "c", "d"]);
+        sadd(&store, "setop:diff:b", &["c"]);
+        sadd(&store, "setop:diff:c", &["a", "e"]);
+
+        assert_eq!(
+            setop(
+                &store,
+                Builder::new_diff(),
+                &[
+                    "setop:diff:a",
+                    "setop:diff:b",
+                    "setop:diff:missing",
+                    "setop:diff:c"
+                ]
+            ),
+            members(&["b", "d"])
+        );
+        assert_eq!(
+            setop(
+                &store,
+                Builder::new_diff(),
+                &["setop:diff:missing", "setop:diff:a"]
+            ),
+            members(&[])
+        );
+    }
+
+    #[test]
+    fn test_setop_store() {
+        let store = KvStore::new();
+        sadd(&store, "setop:store:a", &["a", "b", "c"]);
+        sadd(&store, "setop:store:b", &["b", "c", "d"]);
+        store.set("setop:store:dest", "value");
+
+        let stored = |builder: Builder, keys: &[&str]| {
+            let keys: Vec<String> = keys.iter().map(ToString::to_string).collect();
+            builder
+                .destination("setop:store:dest")
+                .keys(&keys)
+                .build()
+                .unwrap()
+                .execute_on(&store)
+        };
+
+        assert_eq!(
+            stored(Builder::new_store(), &["setop:store:a", "setop:store:b"]),
+            Response::Integer(2)
+        );
+        assert_eq!(
+            store.smembers("setop:store:dest"),
+            Ok(vec!["b".into(), "c".into()])
+        );
+
+        assert_eq!(
+            stored(
+                Builder::new_union_store(),
+                &["setop:store:a", "setop:store:b"]
+            ),
+            Response::Integer(4)
+        );
+        assert_eq!(store.scard("setop:store:dest"), Ok(4));
+
+        // the destination may also be one of the keys
+        assert_eq!(
+            stored(
+                Builder::new_diff_store(),
+                &["setop:store:dest", "setop:store:b"]
+            ),
+            Response::Integer(1)
+        );
+        assert_eq!(store.smembers("setop:store:dest"), Ok(vec!["a".into()]));
+
+        assert_eq!(
+            stored(
+                Builder::new_store(),
+                &["setop:store:a", "setop:store:missing"]
+            ),
+            Response::Integer(0)
+        );
+        assert!(!store.exists("setop:store:dest"));
+    }
+
+    #[test]
+    fn test_setop_wrong_type() {
+        let store = KvStore::new();
+        sadd(&store, "setop:wrongtype:set", &["a"]);
+        store.hset("setop:wrongtype:hash", "a", "1").unwrap();
+
+        for builder in [Builder::new(), Builder::new_union(), Builder::new_diff()] {
+            assert!(matches!(
+                setop(
+                    &store,
+                    builder,
+                    &["setop:wrongtype:set", "setop:wrongtype:hash"]
+                ),
+                Response::Error(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_build_without_keys() {
+        assert!(matches!(
+            Builder::new().keys(&[]).build(),
+            Err(ArgumentError::Missing)
+        ));
+        assert!(matches!(
+            Builder::new_store().keys(&["setop:a".into()]).build(),
+            Err(ArgumentError::Missing)
+        ));
+    }
+}
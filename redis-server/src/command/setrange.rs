@@ -0,0 +1,160 @@
+/// This module contains the implementation of the `SetRange` command.
+/// The `SetRange` command overwrites part of a string from a byte offset onwards,
+/// padding it with zero bytes if needed, and replies with the length of the string afterwards.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// The largest length a string may grow to, like Redis's `proto-max-bulk-len`.
+const MAX_LEN: usize = 512 * 1024 * 1024;
+
+/// Represents the `SetRange` command.
+pub struct SetRange {
+    key: String,
+    offset: usize,
+    value: String,
+}
+
+impl ExecuteOn for SetRange {
+    /// Executes the `SetRange` command and returns the length of the string.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.setrange(&self.key, self.offset, self.value.as_bytes()) {
+            Ok(len) => Response::Integer(i64::try_from(len).unwrap_or(i64::MAX)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `SetRange` command.
+pub struct Builder {
+    key: Option<String>,
+    offset: Option<String>,
+    value: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            offset: None,
+            value: None,
+        }
+    }
+
+    /// Sets the key of the string.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the byte offset to write at.
+    pub fn offset(mut self, offset: &str) -> Self {
+        self.offset = Some(offset.into());
+        self
+    }
+
+    /// Sets the value to write.
+    pub fn value(mut self, value: &str) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Builds the `SetRange` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::Integer` if the offset is not a non-negative integer
+    /// or the string would grow beyond 512 MB.
+    pub fn build(self) -> Result<SetRange, ArgumentError> {
+        let offset: usize = self
+            .offset
+            .ok_or(ArgumentError::Missing)?
+            .parse()
+            .map_err(|_| ArgumentError::Integer)?;
+        let value = self.value.ok_or(ArgumentError::Missing)?;
+        if offset.saturating_add(value.len()) > MAX_LEN {
+            return Err(ArgumentError::Integer);
+        }
+
+        Ok(SetRange {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            offset,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setrange(store: &KvStore, key: &str, offset: &str, value: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .offset(offset)
+            .value(value)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_setrange_overwrite() {
+        let store = KvStore::new();
+        store.set("setrange:overwrite", "Hello World");
+        assert_eq!(
+            setrange(&store, "setrange:overwrite", "6", "Redis"),
+            Response::Integer(11)
+        );
+        assert_eq!(
+            store.get("setrange:overwrite"),
+            Ok(Some("Hello Redis".into()))
+        );
+    }
+
+    #[test]
+    fn test_setrange_extends() {
+        let store = KvStore::new();
+        store.set("setrange:extend", "ab");
+        assert_eq!(
+            setrange(&store, "setrange:extend", "4", "cd"),
+            Response::Integer(6)
+        );
+        assert_eq!(store.get("setrange:extend"), Ok(Some("ab\0\0cd".into())));
+
+        assert_eq!(
+            setrange(&store, "setrange:missing", "1", "x"),
+            Response::Integer(2)
+        );
+        assert_eq!(store.get("setrange:missing"), Ok(Some("\0x".into())));
+        assert_eq!(
+            setrange(&store, "setrange:empty", "5", ""),
+            Response::Integer(0)
+        );
+        assert!(!store.exists("setrange:empty"));
+    }
+
+    #[test]
+    fn test_setrange_splits_character() {
+        let store = KvStore::new();
+        // overwriting the second byte of "é" leaves its first byte as it was
+        store.set("setrange:split", "é");
+        assert_eq!(
+            setrange(&store, "setrange:split", "1", "x"),
+            Response::Integer(2)
+        );
+        assert_eq!(store.get("setrange:split"), Ok(Some(vec![0xc3, b'x'])));
+    }
+
+    #[test]
+    fn test_setrange_out_of_range() {
+        let result = Builder::new()
+            .key("setrange:huge")
+            .offset("536870912")
+            .value("x")
+            .build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
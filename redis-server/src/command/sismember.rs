@@ -0,0 +1,94 @@
+/// This module contains the implementation of the `SIsMember` command.
+/// The `SIsMember` command replies with `1` if a member belongs to a set, or `0` otherwise.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `SIsMember` command.
+pub struct SIsMember {
+    key: String,
+    member: String,
+}
+
+impl ExecuteOn for SIsMember {
+    /// Executes the `SIsMember` command and returns whether the member belongs to the set.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.sismember(&self.key, &self.member) {
+            Ok(found) => Response::Integer(i64::from(found)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `SIsMember` command.
+pub struct Builder {
+    key: Option<String>,
+    member: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            member: None,
+        }
+    }
+
+    /// Sets the key of the set.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the member to look up.
+    pub fn member(mut self, member: &str) -> Self {
+        self.member = Some(member.into());
+        self
+    }
+
+    /// Builds the `SIsMember` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided.
+    pub fn build(self) -> Result<SIsMember, ArgumentError> {
+        Ok(SIsMember {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            member: self.member.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sismember(store: &KvStore, key: &str, member: &str) -> Response {
+        Builder::new()
+            .key(key)
+            .member(member)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_sismember() {
+        let store = KvStore::new();
+        store.sadd("sismember:set", &["a".into()]).unwrap();
+
+        assert_eq!(
+            sismember(&store, "sismember:set", "a"),
+            Response::Integer(1)
+        );
+        assert_eq!(
+            sismember(&store, "sismember:set", "b"),
+            Response::Integer(0)
+        );
+        assert_eq!(
+            sismember(&store, "sismember:missing", "a"),
+            Response::Integer(0)
+        );
+    }
+}
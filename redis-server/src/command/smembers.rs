@@ -0,0 +1,86 @@
+/// This module contains the implementation of the `SMembers` command.
+/// The `SMembers` command returns the members of a set in sorted order,
+/// or an empty array if the set does not exist.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `SMembers` command.
+pub struct SMembers {
+    key: String,
+}
+
+impl ExecuteOn for SMembers {
+    /// Executes the `SMembers` command and returns the members of the set.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.smembers(&self.key) {
+            Ok(members) => Response::arr(members),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `SMembers` command.
+pub struct Builder {
+    key: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self { key: None }
+    }
+
+    /// Sets the key of the set.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `SMembers` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<SMembers, ArgumentError> {
+        Ok(SMembers {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smembers(store: &KvStore, key: &str) -> Response {
+        Builder::new().key(key).build().unwrap().execute_on(store)
+    }
+
+    #[test]
+    fn test_smembers() {
+        let store = KvStore::new();
+        store
+            .sadd("smembers:set", &["b".into(), "a".into()])
+            .unwrap();
+
+        assert_eq!(
+            smembers(&store, "smembers:set"),
+            Response::Array(vec![Response::bs("a"), Response::bs("b")])
+        );
+        assert_eq!(
+            smembers(&store, "smembers:missing"),
+            Response::Array(vec![])
+        );
+    }
+
+    #[test]
+    fn test_smembers_wrong_type() {
+        let store = KvStore::new();
+        store.hset("smembers:hash", "a", "1").unwrap();
+        assert!(matches!(
+            smembers(&store, "smembers:hash"),
+            Response::Error(_)
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+/// This module contains the implementation of the `Sort` command.
+/// The `Sort` command returns the elements of a list or the members of a set sorted numerically,
+/// or lexicographically with `ALPHA`, in ascending order unless `DESC` is given,
+/// optionally windowed with `LIMIT offset count`.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `Sort` command.
+pub struct Sort {
+    key: String,
+    alpha: bool,
+    desc: bool,
+    /// The number of sorted elements to skip, and the most to return after them.
+    limit: Option<(usize, usize)>,
+}
+
+impl Sort {
+    /// Sorts elements as numbers, breaking ties between equal numbers lexicographically.
+    ///
+    /// # Returns
+    ///
+    /// The sorted elements, or `None` if any element is not a number.
+    fn sort_numeric(elements: Vec<String>) -> Option<Vec<String>> {
+        let mut scored = elements
+            .into_iter()
+            .map(|element| Some((element.parse::<f64>().ok()?, element)))
+            .collect::<Option<Vec<_>>>()?;
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+        Some(scored.into_iter().map(|(_, element)| element).collect())
+    }
+}
+
+impl ExecuteOn for Sort {
+    /// Executes the `Sort` command and returns the sorted elements.
+    fn execute_on(self, store: &KvStore) -> Response {
+        let mut elements = match store.elements(&self.key) {
+            Ok(elements) => elements,
+            Err(error) => return Response::from(error),
+        };
+
+        let mut sorted = if self.alpha {
+            elements.sort_unstable();
+            elements
+        } else {
+            let Some(sorted) = Self::sort_numeric(elements) else {
+                return Response::err("ERR", "One or more scores can't be converted into double");
+            };
+            sorted
+        };
+        if self.desc {
+            sorted.reverse();
+        }
+        if let Some((offset, count)) = self.limit {
+            sorted = sorted.into_iter().skip(offset).take(count).collect();
+        }
+
+        Response::arr(sorted)
+    }
+}
+
+/// Builder for the `Sort` command.
+pub struct Builder {
+    key: Option<String>,
+    options: Vec<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            options: Vec::new(),
+        }
+    }
+
+    /// Sets the key of the list or set.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the trailing `ALPHA`, `ASC`, `DESC`, and `LIMIT offset count` options.
+    pub fn options(mut self, options: &[String]) -> Self {
+        self.options = options.to_vec();
+        self
+    }
+
+    /// Builds the `Sort` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided,
+    /// an `ArgumentError::Syntax` if an option is unknown or `LIMIT` lacks its arguments,
+    /// or an `ArgumentError::Integer` if the arguments of `LIMIT` are not integers.
+    pub fn build(self) -> Result<Sort, ArgumentError> {
+        let mut alpha = false;
+        let mut desc = false;
+        let mut limit = None;
+
+        let mut options = self.options.iter();
+        while let Some(option) = options.next() {
+            match option.to_uppercase().as_str() {
+                "ALPHA" => alpha = true,
+                "ASC" => desc = false,
+                "DESC" => desc = true,
+                "LIMIT" => {
+                    let (Some(offset), Some(count)) = (options.next(), options.next()) else {
+                        return Err(ArgumentError::Syntax);
+                    };
+                    let offset: i64 = offset.parse().map_err(|_| ArgumentError::Integer)?;
+                    let count: i64 = count.parse().map_err(|_| ArgumentError::Integer)?;
+                    // a negative offset starts at the first element,
+                    // and a negative count takes every element after the offset
+                    limit = Some((
+                        usize::try_from(offset).unwrap_or_default(),
+                        usize::try_from(count).unwrap_or(usize::MAX),
+                    ));
+                }
+                _ => return Err(ArgumentError::Syntax),
+            }
+        }
+
+        Ok(Sort {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            alpha,
+            desc,
+            limit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sort(store: &KvStore, key: &str, options: &[&str]) -> Response {
+        let options: Vec<String> = options.iter().map(ToString::to_string).collect();
+        Builder::new()
+            .key(key)
+            .options(&options)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    fn elements(elements: &[&str]) -> Response {
+        Response::Array(
+            elements
+                .iter()
+                .map(|element| Response::bs(element))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_sort_numeric() {
+        let store = KvStore::new();
+        let list: Vec<String> = ["10", "2", "-1.5", "3", "2"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        store.push("sort:numbers", &list, true).unwrap();
+        assert_eq!(
+            sort(&store, "sort:numbers", &[]),
+            elements(&["-1.5", "2", "2", "3", "10"])
+        );
+        assert_eq!(
+            sort(&store, "sort:numbers", &["DESC"]),
+            elements(&["10", "3", "2", "2", "-1.5"])
+        );
+
+        store
+            .sadd("sort:set", &["3".into(), "1".into(), "2".into()])
+            .unwrap();
+        assert_eq!(
+            sort(&store, "sort:set", &["asc"]),
+            elements(&["1", "2", "3"])
+        );
+        assert_eq!(sort(&store, "sort:missing", &[]), elements(&[]));
+    }
+
+    #[test]
+    fn test_sort_alpha() {
+        let store = KvStore::new();
+        store
+            .push("sort:words", &["b".into(), "c".into(), "a".into()], true)
+            .unwrap();
+        assert_eq!(
+            sort(&store, "sort:words", &["ALPHA", "DESC"]),
+            elements(&["c", "b", "a"])
+        );
+        assert_eq!(
+            sort(&store, "sort:words", &[]),
+            Response::err("ERR", "One or more scores can't be converted into double")
+        );
+    }
+
+    #[test]
+    fn test_sort_limit() {
+        let store = KvStore::new();
+        let list: Vec<String> = (1..=10).rev().map(|i| i.to_string()).collect();
+        store.push("sort:limit", &list, true).unwrap();
+        assert_eq!(
+            sort(&store, "sort:limit", &["LIMIT", "2", "3"]),
+            elements(&["3", "4", "5"])
+        );
+        assert_eq!(
+            sort(&store, "sort:limit", &["DESC", "LIMIT", "8", "5"]),
+            elements(&["2", "1"])
+        );
+        assert_eq!(
+            sort(&store, "sort:limit", &["LIMIT", "-1", "-1", "DESC"]),
+            elements(&["10", "9", "8", "7", "6", "5", "4", "3", "2", "1"])
+        );
+        assert_eq!(
+            sort(&store, "sort:limit", &["LIMIT", "20", "1"]),
+            elements(&[])
+        );
+    }
+
+    #[test]
+    fn test_sort_invalid() {
+        let store = KvStore::new();
+        store.hset("sort:hash", "a", "1").unwrap();
+        assert!(matches!(sort(&store, "sort:hash", &[]), Response::Error(_)));
+
+        let build = |options: &[&str]| {
+            let options: Vec<String> = options.iter().map(ToString::to_string).collect();
+            Builder::new().key("sort:invalid").options(&options).build()
+        };
+        assert!(matches!(build(&["LIMIT", "1"]), Err(ArgumentError::Syntax)));
+        assert!(matches!(
+            build(&["LIMIT", "one", "1"]),
+            Err(ArgumentError::Integer)
+        ));
+        assert!(matches!(
+            build(&["BY", "weight_*"]),
+            Err(ArgumentError::Syntax)
+        ));
+    }
+}
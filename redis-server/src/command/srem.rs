@@ -0,0 +1,96 @@
+/// This module contains the implementation of the `SRem` command.
+/// The `SRem` command removes members from a set, removing the set once no members remain,
+/// and replies with the number of members that were removed.
+use crate::{kvstore::KvStore, response::types::Response};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `SRem` command.
+pub struct SRem {
+    key: String,
+    members: Vec<String>,
+}
+
+impl ExecuteOn for SRem {
+    /// Executes the `SRem` command and returns the number of removed members.
+    fn execute_on(self, store: &KvStore) -> Response {
+        match store.srem(&self.key, &self.members) {
+            Ok(removed) => Response::Integer(i64::try_from(removed).unwrap_or(i64::MAX)),
+            Err(error) => Response::from(error),
+        }
+    }
+}
+
+/// Builder for the `SRem` command.
+pub struct Builder {
+    key: Option<String>,
+    members: Option<Vec<String>>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            members: None,
+        }
+    }
+
+    /// Sets the key of the set.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets the members to remove.
+    pub fn members(mut self, members: &[String]) -> Self {
+        self.members = Some(members.to_vec());
+        self
+    }
+
+    /// Builds the `SRem` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key or members are not provided.
+    pub fn build(self) -> Result<SRem, ArgumentError> {
+        let members = self.members.ok_or(ArgumentError::Missing)?;
+        if members.is_empty() {
+            return Err(ArgumentError::Missing);
+        }
+
+        Ok(SRem {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            members,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn srem(store: &KvStore, key: &str, members: &[&str]) -> Response {
+        let members: Vec<String> = members.iter().map(ToString::to_string).collect();
+        Builder::new()
+            .key(key)
+            .members(&members)
+            .build()
+            .unwrap()
+            .execute_on(store)
+    }
+
+    #[test]
+    fn test_srem() {
+        let store = KvStore::new();
+        store.sadd("srem:set", &["a".into(), "b".into()]).unwrap();
+
+        assert_eq!(
+            srem(&store, "srem:set", &["a", "missing"]),
+            Response::Integer(1)
+        );
+        assert_eq!(srem(&store, "srem:set", &["b"]), Response::Integer(1));
+        assert!(!store.exists("srem:set"));
+        assert_eq!(srem(&store, "srem:set", &["a"]), Response::Integer(0));
+    }
+}
@@ -0,0 +1,163 @@
+/// This module contains the implementation of the `Subscribe` command.
+/// `SUBSCRIBE` registers the client to receive messages published to the given channels,
+/// while `PSUBSCRIBE` does the same for all channels matching the given glob patterns.
+/// One confirmation is sent per channel or pattern, carrying the running subscription count.
+use crate::{pubsub, response::types::Response, session::Session};
+
+use super::types::{ArgumentError, ExecuteInSession};
+
+/// Represents the `Subscribe` command, covering both `SUBSCRIBE` and `PSUBSCRIBE`.
+pub struct Subscribe {
+    channels: Vec<String>,
+    pattern: bool,
+}
+
+impl ExecuteInSession for Subscribe {
+    /// Executes the `Subscribe` command, subscribing the session to each channel in turn.
+    fn execute_in(self, session: &mut Session) -> Response {
+        Response::Multi(
+            self.channels
+                .iter()
+                .map(|channel| {
+                    if self.pattern {
+                        let count = session.psubscribe(channel);
+                        pubsub::confirmation("psubscribe", Some(channel), count)
+                    } else {
+                        let count = session.subscribe(channel);
+                        pubsub::confirmation("subscribe", Some(channel), count)
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Builder for the `Subscribe` command.
+pub struct Builder {
+    channels: Vec<String>,
+    pattern: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `SUBSCRIBE`.
+    pub const fn new() -> Self {
+        Self {
+            channels: Vec::new(),
+            pattern: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `PSUBSCRIBE`.
+    pub const fn new_pattern() -> Self {
+        Self {
+            channels: Vec::new(),
+            pattern: true,
+        }
+    }
+
+    /// Sets the channels, or patterns, to subscribe to.
+    pub fn channels(mut self, channels: &[String]) -> Self {
+        self.channels = channels.to_vec();
+        self
+    }
+
+    /// Builds the `Subscribe` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if no channels are provided.
+    pub fn build(self) -> Result<Subscribe, ArgumentError> {
+        if self.channels.is_empty() {
+            return Err(ArgumentError::Missing);
+        }
+
+        Ok(Subscribe {
+            channels: self.channels,
+            pattern: self.pattern,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::{publish, types::Execute};
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_subscribe_multiple_channels() {
+        let (sender, mut receiver) = mpsc::channel(16);
+        let mut session = Session::new(sender);
+
+        let channels: Vec<String> = vec!["sub:ch1".into(), "sub:ch2".into(), "sub:ch3".into()];
+        let cmd = Builder::new().channels(&channels).build().unwrap();
+        assert_eq!(
+            cmd.execute_in(&mut session),
+            Response::Multi(vec![
+                pubsub::confirmation("subscribe", Some("sub:ch1"), 1),
+                pubsub::confirmation("subscribe", Some("sub:ch2"), 2),
+                pubsub::confirmation("subscribe", Some("sub:ch3"), 3),
+            ])
+        );
+
+        for channel in &channels {
+            let cmd = publish::Builder::new()
+                .channel(channel)
+                .message("hello")
+                .build()
+                .unwrap();
+            assert_eq!(cmd.execute(), Response::Integer(1));
+            assert_eq!(
+                receiver.try_recv().unwrap(),
+                Response::Array(vec![
+                    Response::bs("message"),
+                    Response::bs(channel),
+                    Response::bs("hello"),
+                ])
+            );
+        }
+    }
+
+    #[test]
+    fn test_psubscribe_matches_channel() {
+        let (sender, mut receiver) = mpsc::channel(16);
+        let mut session = Session::new(sender);
+
+        let cmd = Builder::new_pattern()
+            .channels(&["psub:news.*".into()])
+            .build()
+            .unwrap();
+        assert_eq!(
+            cmd.execute_in(&mut session),
+            Response::Multi(vec![pubsub::confirmation(
+                "psubscribe",
+                Some("psub:news.*"),
+                1
+            )])
+        );
+
+        let cmd = publish::Builder::new()
+            .channel("psub:news.tech")
+            .message("rust")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute(), Response::Integer(1));
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            Response::Array(vec![
+                Response::bs("pmessage"),
+                Response::bs("psub:news.*"),
+                Response::bs("psub:news.tech"),
+                Response::bs("rust"),
+            ])
+        );
+
+        let cmd = publish::Builder::new()
+            .channel("psub:sports.tech")
+            .message("rust")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute(), Response::Integer(0));
+        assert!(receiver.try_recv().is_err());
+    }
+}
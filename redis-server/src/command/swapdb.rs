@@ -0,0 +1,120 @@
+/// This module contains the implementation of the `SwapDb` command.
+/// The `SwapDb` command swaps the contents of two databases, replying with `OK`.
+///
+/// The swap is seen by every client at once, so clients that have selected one of the databases
+/// see the contents of the other from then on.
+use crate::{
+    kvstore::{database_index, DATABASES},
+    response::types::Response,
+};
+
+use super::types::{ArgumentError, Execute};
+
+/// Represents the `SwapDb` command.
+pub struct SwapDb {
+    index1: i64,
+    index2: i64,
+}
+
+impl Execute for SwapDb {
+    /// Executes the `SwapDb` command and returns `OK` once the databases are swapped.
+    fn execute(self) -> Response {
+        match (database_index(self.index1), database_index(self.index2)) {
+            (Some(index1), Some(index2)) => {
+                DATABASES.swap(index1, index2);
+                Response::ss("OK")
+            }
+            _ => Response::err("ERR", "DB index is out of range"),
+        }
+    }
+}
+
+/// Builder for the `SwapDb` command.
+pub struct Builder {
+    index1: Option<String>,
+    index2: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            index1: None,
+            index2: None,
+        }
+    }
+
+    /// Sets the index of the first database.
+    pub fn index1(mut self, index1: &str) -> Self {
+        self.index1 = Some(index1.into());
+        self
+    }
+
+    /// Sets the index of the second database.
+    pub fn index2(mut self, index2: &str) -> Self {
+        self.index2 = Some(index2.into());
+        self
+    }
+
+    /// Builds the `SwapDb` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if either index is not provided,
+    /// or an `ArgumentError::Integer` if either is not an integer.
+    pub fn build(self) -> Result<SwapDb, ArgumentError> {
+        let index1 = self.index1.ok_or(ArgumentError::Missing)?;
+        let index2 = self.index2.ok_or(ArgumentError::Missing)?;
+
+        Ok(SwapDb {
+            index1: index1.parse().map_err(|_| ArgumentError::Integer)?,
+            index2: index2.parse().map_err(|_| ArgumentError::Integer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swapdb(index1: &str, index2: &str) -> Response {
+        Builder::new()
+            .index1(index1)
+            .index2(index2)
+            .build()
+            .unwrap()
+            .execute()
+    }
+
+    #[test]
+    fn test_swapdb() {
+        DATABASES.get(14).set("swapdb:first", "14");
+        DATABASES.get(15).set("swapdb:second", "15");
+
+        assert_eq!(swapdb("14", "15"), Response::ss("OK"));
+        assert_eq!(DATABASES.get(15).get("swapdb:first"), Ok(Some("14".into())));
+        assert!(!DATABASES.get(14).exists("swapdb:first"));
+        assert_eq!(
+            DATABASES.get(14).get("swapdb:second"),
+            Ok(Some("15".into()))
+        );
+        assert!(!DATABASES.get(15).exists("swapdb:second"));
+
+        // swapping a database with itself leaves it as it is
+        assert_eq!(swapdb("14", "14"), Response::ss("OK"));
+        assert!(DATABASES.get(14).exists("swapdb:second"));
+    }
+
+    #[test]
+    fn test_swapdb_out_of_range() {
+        for (index1, index2) in [("0", "16"), ("16", "0"), ("-1", "0")] {
+            assert_eq!(
+                swapdb(index1, index2),
+                Response::err("ERR", "DB index is out of range")
+            );
+        }
+
+        let result = Builder::new().index1("0").index2("one").build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
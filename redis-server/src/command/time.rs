@@ -0,0 +1,63 @@
+/// This module contains the implementation of the `Time` command.
+/// The `Time` command returns the server's wall-clock time as an array of the seconds
+/// since the unix epoch and the microseconds elapsed in the current second.
+use crate::response::types::Response;
+
+use super::types::Execute;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Represents the `Time` command.
+pub struct Time;
+
+impl Execute for Time {
+    /// Executes the `Time` command and returns the current time.
+    fn execute(self) -> Response {
+        // a clock set before the epoch is reported as the epoch itself
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        Response::arr(vec![
+            now.as_secs().to_string(),
+            now.subsec_micros().to_string(),
+        ])
+    }
+}
+
+/// Builder for the `Time` command.
+pub struct Builder;
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Builds the `Time` command, which takes no arguments.
+    #[allow(clippy::unused_self)] // consumed like every other builder
+    pub const fn build(self) -> Time {
+        Time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time() {
+        let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let Response::Array(reply) = Builder::new().build().execute() else {
+            panic!("TIME should reply with an array");
+        };
+
+        let [Response::BulkString(secs), Response::BulkString(micros)] = reply.as_slice() else {
+            panic!("TIME should reply with two bulk strings");
+        };
+        let secs: u64 = std::str::from_utf8(secs).unwrap().parse().unwrap();
+        let micros: u32 = std::str::from_utf8(micros).unwrap().parse().unwrap();
+        assert!(secs >= before.as_secs());
+        assert!(micros < 1_000_000);
+    }
+}
@@ -0,0 +1,116 @@
+/// This module contains the implementation of the `Ttl` command.
+/// The `Ttl` command returns the remaining time to live of a key in seconds (`TTL`)
+/// or milliseconds (`PTTL`), `-1` if the key has no expiry, or `-2` if the key does not exist.
+use crate::{
+    kvstore::{Expiry, KvStore},
+    response::types::Response,
+};
+
+use super::types::{ArgumentError, ExecuteOn};
+
+/// Represents the `Ttl` command, covering both `TTL` and `PTTL`.
+pub struct Ttl {
+    key: String,
+    millis: bool,
+}
+
+impl ExecuteOn for Ttl {
+    /// Executes the `Ttl` command and returns the remaining time to live.
+    fn execute_on(self, store: &KvStore) -> Response {
+        Response::Integer(match store.ttl(&self.key) {
+            None => -2,
+            Some(Expiry::Persistent) => -1,
+            Some(Expiry::In(ttl)) => {
+                let millis = ttl.as_millis();
+                // round partial seconds up, so a live key never reports a TTL of 0
+                i64::try_from(if self.millis {
+                    millis
+                } else {
+                    millis.div_ceil(1_000)
+                })
+                .unwrap_or(i64::MAX)
+            }
+        })
+    }
+}
+
+/// Builder for the `Ttl` command.
+pub struct Builder {
+    key: Option<String>,
+    millis: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `TTL`.
+    pub const fn new() -> Self {
+        Self {
+            key: None,
+            millis: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `PTTL`.
+    pub const fn new_millis() -> Self {
+        Self {
+            key: None,
+            millis: true,
+        }
+    }
+
+    /// Sets the key for the `Ttl` command.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Builds the `Ttl` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if the key is not provided.
+    pub fn build(self) -> Result<Ttl, ArgumentError> {
+        Ok(Ttl {
+            key: self.key.ok_or(ArgumentError::Missing)?,
+            millis: self.millis,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::expire;
+
+    #[test]
+    fn test_ttl_missing_and_persistent() {
+        let store = KvStore::new();
+        let cmd = Builder::new().key("ttl:missing").build().unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(-2));
+
+        store.set("ttl:persistent", "value");
+        let cmd = Builder::new().key("ttl:persistent").build().unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(-1));
+    }
+
+    #[test]
+    fn test_pexpire_keeps_millisecond_precision() {
+        let store = KvStore::new();
+        store.set("ttl:precise", "value");
+
+        let cmd = expire::Builder::new_millis()
+            .key("ttl:precise")
+            .ttl("1500")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(1));
+
+        let cmd = Builder::new_millis().key("ttl:precise").build().unwrap();
+        let Response::Integer(pttl) = cmd.execute_on(&store) else {
+            panic!("PTTL should return an integer");
+        };
+        assert!((1_400..=1_500).contains(&pttl), "unexpected PTTL {pttl}");
+
+        let cmd = Builder::new().key("ttl:precise").build().unwrap();
+        assert_eq!(cmd.execute_on(&store), Response::Integer(2));
+    }
+}
@@ -3,15 +3,46 @@ use crate::request::types::Request;
 use deserialize::parse_commands;
 use std::{error::Error, fmt, str::FromStr};
 
-use crate::response::types::Response;
+use crate::{
+    kvstore::KvStore,
+    response::{encode::encode, types::Response},
+    session::Session,
+};
 
-use super::{config, del, echo, exists, get, ping, set};
+use super::{
+    bitcount, client, command, config, copy, debug, del, discard, dump, echo, exec, exists, expire,
+    expireat, get, getbit, getrange, hdel, hexists, hexpire, hget, hgetall, hincrby, hkeys, hlen,
+    hmget, hset, httl, hvals, incrbyfloat, info, key_type, lindex, llen, lrem, lset, ltrim, memory,
+    multi, object, persist, ping, pop, publish, push, r#move, randomkey, rename, restore, sadd,
+    scard, select, set, setbit, setop, setrange, sismember, smembers, sort, srem, subscribe,
+    swapdb, time, ttl, unsubscribe, wait,
+};
 
 // TODO: make this trait required for all commands via a derive macro
 pub trait Execute {
     fn execute(self) -> Response;
 }
 
+/// Implemented by commands that read or write the keys of a database,
+/// which they are given instead of looking it up themselves.
+pub trait ExecuteOn {
+    fn execute_on(self, store: &KvStore) -> Response;
+}
+
+/// Implemented by commands that depend on or modify the state of the client's session.
+pub trait ExecuteInSession {
+    fn execute_in(self, session: &mut Session) -> Response;
+
+    /// Executes the command and appends its reply to `out`,
+    /// so that the replies to a batch of commands are written into a single buffer.
+    fn execute_into(self, session: &mut Session, out: &mut Vec<u8>)
+    where
+        Self: Sized,
+    {
+        encode(&self.execute_in(session), out);
+    }
+}
+
 pub enum CommandBuilder {
     Ping(ping::Builder),
     Echo(echo::Builder),
@@ -20,22 +51,122 @@ pub enum CommandBuilder {
     Set(set::Builder),
     Get(get::Builder),
     Del(del::Builder),
-    // LPush,
-    // RPush,
+    Rename(rename::Builder),
+    RenameNx(rename::Builder),
+    Copy(copy::Builder),
+    Expire(expire::Builder),
+    Ttl(ttl::Builder),
+    Persist(persist::Builder),
+    PExpire(expire::Builder),
+    PTtl(ttl::Builder),
+    Subscribe(subscribe::Builder),
+    PSubscribe(subscribe::Builder),
+    Unsubscribe(unsubscribe::Builder),
+    PUnsubscribe(unsubscribe::Builder),
+    Publish(publish::Builder),
+    Command(command::Builder),
+    Client(client::Builder),
+    HExpire(hexpire::Builder),
+    HTtl(httl::Builder),
+    Debug(debug::Builder),
+    Object(object::Builder),
+    HSet(hset::Builder),
+    HMSet(hset::Builder),
+    HGet(hget::Builder),
+    HGetAll(hgetall::Builder),
+    HDel(hdel::Builder),
+    Type(key_type::Builder),
+    SAdd(sadd::Builder),
+    SRem(srem::Builder),
+    SMembers(smembers::Builder),
+    SIsMember(sismember::Builder),
+    SCard(scard::Builder),
+    Wait(wait::Builder),
+    GetRange(getrange::Builder),
+    SetRange(setrange::Builder),
+    RandomKey(randomkey::Builder),
+    IncrByFloat(incrbyfloat::Builder),
+    LPush(push::Builder),
+    RPush(push::Builder),
+    LPop(pop::Builder),
+    RPop(pop::Builder),
+    LLen(llen::Builder),
+    ExpireAt(expireat::Builder),
+    PExpireAt(expireat::Builder),
+    Multi(multi::Builder),
+    Exec(exec::Builder),
+    Discard(discard::Builder),
+    Dump(dump::Builder),
+    Restore(restore::Builder),
+    SetBit(setbit::Builder),
+    GetBit(getbit::Builder),
+    BitCount(bitcount::Builder),
+    LIndex(lindex::Builder),
+    LSet(lset::Builder),
+    SInter(setop::Builder),
+    SUnion(setop::Builder),
+    SDiff(setop::Builder),
+    HIncrBy(hincrby::Builder),
+    Info(info::Builder),
+    SInterStore(setop::Builder),
+    SUnionStore(setop::Builder),
+    SDiffStore(setop::Builder),
+    Memory(memory::Builder),
+    Time(time::Builder),
+    LRem(lrem::Builder),
+    LTrim(ltrim::Builder),
+    HExists(hexists::Builder),
+    HKeys(hkeys::Builder),
+    HVals(hvals::Builder),
+    HLen(hlen::Builder),
+    HMGet(hmget::Builder),
+    Sort(sort::Builder),
+    Move(r#move::Builder),
+    SwapDb(swapdb::Builder),
+    Select(select::Builder),
     // Save,
 }
 
 #[derive(Debug)]
 pub enum CommandError {
-    Unknown,
+    Unknown { name: String, args: Vec<String> },
+    WrongArity { name: String },
+}
+
+impl CommandError {
+    /// Attaches the arguments the offending command was sent with, for reporting.
+    pub fn with_args(self, args: &[String]) -> Self {
+        match self {
+            Self::Unknown { name, .. } => Self::Unknown {
+                name,
+                args: args.to_vec(),
+            },
+            Self::WrongArity { .. } => self,
+        }
+    }
 }
 
 impl Error for CommandError {}
 
 impl fmt::Display for CommandError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Self::Unknown => write!(f, "unknown command"),
+        match self {
+            Self::Unknown { name, args } => {
+                write!(f, "unknown command '{name}', with args beginning with: ")?;
+                // like Redis, only echo as many arguments as fit in a short preview
+                let mut preview_len = 0;
+                for arg in args {
+                    preview_len += arg.len();
+                    if preview_len >= 128 {
+                        break;
+                    }
+                    write!(f, "'{arg}' ")?;
+                }
+                Ok(())
+            }
+            Self::WrongArity { name } => {
+                write!(f, "wrong number of arguments for '{name}' command")
+            }
         }
     }
 }
@@ -52,10 +183,84 @@ impl FromStr for CommandBuilder {
             "SET" => Ok(Self::Set(set::Builder::new())),
             "GET" => Ok(Self::Get(get::Builder::new())),
             "DEL" => Ok(Self::Del(del::Builder::new())),
-            // "LPUSH" => Ok(Self::LPush),
-            // "RPUSH" => Ok(Self::RPush),
+            "RENAME" => Ok(Self::Rename(rename::Builder::new())),
+            "RENAMENX" => Ok(Self::RenameNx(rename::Builder::new_nx())),
+            "COPY" => Ok(Self::Copy(copy::Builder::new())),
+            "EXPIRE" => Ok(Self::Expire(expire::Builder::new())),
+            "TTL" => Ok(Self::Ttl(ttl::Builder::new())),
+            "PEXPIRE" => Ok(Self::PExpire(expire::Builder::new_millis())),
+            "PTTL" => Ok(Self::PTtl(ttl::Builder::new_millis())),
+            "PERSIST" => Ok(Self::Persist(persist::Builder::new())),
+            "SUBSCRIBE" => Ok(Self::Subscribe(subscribe::Builder::new())),
+            "PSUBSCRIBE" => Ok(Self::PSubscribe(subscribe::Builder::new_pattern())),
+            "UNSUBSCRIBE" => Ok(Self::Unsubscribe(unsubscribe::Builder::new())),
+            "PUNSUBSCRIBE" => Ok(Self::PUnsubscribe(unsubscribe::Builder::new_pattern())),
+            "PUBLISH" => Ok(Self::Publish(publish::Builder::new())),
+            "COMMAND" => Ok(Self::Command(command::Builder::new())),
+            "CLIENT" => Ok(Self::Client(client::Builder::new())),
+            "HEXPIRE" => Ok(Self::HExpire(hexpire::Builder::new())),
+            "HTTL" => Ok(Self::HTtl(httl::Builder::new())),
+            "DEBUG" => Ok(Self::Debug(debug::Builder::new())),
+            "OBJECT" => Ok(Self::Object(object::Builder::new())),
+            "HSET" => Ok(Self::HSet(hset::Builder::new())),
+            "HMSET" => Ok(Self::HMSet(hset::Builder::new_hmset())),
+            "HGET" => Ok(Self::HGet(hget::Builder::new())),
+            "HGETALL" => Ok(Self::HGetAll(hgetall::Builder::new())),
+            "HDEL" => Ok(Self::HDel(hdel::Builder::new())),
+            "TYPE" => Ok(Self::Type(key_type::Builder::new())),
+            "SADD" => Ok(Self::SAdd(sadd::Builder::new())),
+            "SREM" => Ok(Self::SRem(srem::Builder::new())),
+            "SMEMBERS" => Ok(Self::SMembers(smembers::Builder::new())),
+            "SISMEMBER" => Ok(Self::SIsMember(sismember::Builder::new())),
+            "SCARD" => Ok(Self::SCard(scard::Builder::new())),
+            "WAIT" => Ok(Self::Wait(wait::Builder::new())),
+            "GETRANGE" | "SUBSTR" => Ok(Self::GetRange(getrange::Builder::new())),
+            "SETRANGE" => Ok(Self::SetRange(setrange::Builder::new())),
+            "RANDOMKEY" => Ok(Self::RandomKey(randomkey::Builder::new())),
+            "INCRBYFLOAT" => Ok(Self::IncrByFloat(incrbyfloat::Builder::new())),
+            "LPUSH" => Ok(Self::LPush(push::Builder::new())),
+            "RPUSH" => Ok(Self::RPush(push::Builder::new_tail())),
+            "LPOP" => Ok(Self::LPop(pop::Builder::new())),
+            "RPOP" => Ok(Self::RPop(pop::Builder::new_tail())),
+            "LLEN" => Ok(Self::LLen(llen::Builder::new())),
+            "EXPIREAT" => Ok(Self::ExpireAt(expireat::Builder::new())),
+            "PEXPIREAT" => Ok(Self::PExpireAt(expireat::Builder::new_millis())),
+            "MULTI" => Ok(Self::Multi(multi::Builder::new())),
+            "EXEC" => Ok(Self::Exec(exec::Builder::new())),
+            "DISCARD" => Ok(Self::Discard(discard::Builder::new())),
+            "DUMP" => Ok(Self::Dump(dump::Builder::new())),
+            "RESTORE" => Ok(Self::Restore(restore::Builder::new())),
+            "SETBIT" => Ok(Self::SetBit(setbit::Builder::new())),
+            "GETBIT" => Ok(Self::GetBit(getbit::Builder::new())),
+            "BITCOUNT" => Ok(Self::BitCount(bitcount::Builder::new())),
+            "LINDEX" => Ok(Self::LIndex(lindex::Builder::new())),
+            "LSET" => Ok(Self::LSet(lset::Builder::new())),
+            "SINTER" => Ok(Self::SInter(setop::Builder::new())),
+            "SUNION" => Ok(Self::SUnion(setop::Builder::new_union())),
+            "SDIFF" => Ok(Self::SDiff(setop::Builder::new_diff())),
+            "HINCRBY" => Ok(Self::HIncrBy(hincrby::Builder::new())),
+            "INFO" => Ok(Self::Info(info::Builder::new())),
+            "SINTERSTORE" => Ok(Self::SInterStore(setop::Builder::new_store())),
+            "SUNIONSTORE" => Ok(Self::SUnionStore(setop::Builder::new_union_store())),
+            "SDIFFSTORE" => Ok(Self::SDiffStore(setop::Builder::new_diff_store())),
+            "MEMORY" => Ok(Self::Memory(memory::Builder::new())),
+            "TIME" => Ok(Self::Time(time::Builder::new())),
+            "LREM" => Ok(Self::LRem(lrem::Builder::new())),
+            "LTRIM" => Ok(Self::LTrim(ltrim::Builder::new())),
+            "HEXISTS" => Ok(Self::HExists(hexists::Builder::new())),
+            "HKEYS" => Ok(Self::HKeys(hkeys::Builder::new())),
+            "HVALS" => Ok(Self::HVals(hvals::Builder::new())),
+            "HLEN" => Ok(Self::HLen(hlen::Builder::new())),
+            "HMGET" => Ok(Self::HMGet(hmget::Builder::new())),
+            "SORT" => Ok(Self::Sort(sort::Builder::new())),
+            "MOVE" => Ok(Self::Move(r#move::Builder::new())),
+            "SWAPDB" => Ok(Self::SwapDb(swapdb::Builder::new())),
+            "SELECT" => Ok(Self::Select(select::Builder::new())),
             // "SAVE" => Ok(Self::Save),
-            _ => Err(Self::Err::Unknown),
+            _ => Err(Self::Err::Unknown {
+                name: s.into(),
+                args: Vec::new(),
+            }),
         }
     }
 }
@@ -80,6 +285,12 @@ impl fmt::Display for SubcommandError {
 #[derive(Debug)]
 pub enum ArgumentError {
     Missing,
+    Syntax,
+    Integer,
+    Float,
+    Positive,
+    BitOffset,
+    Bit,
 }
 
 impl Error for ArgumentError {}
@@ -88,6 +299,12 @@ impl fmt::Display for ArgumentError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Self::Missing => write!(f, "missing argument"),
+            Self::Syntax => write!(f, "syntax error"),
+            Self::Integer => write!(f, "value is not an integer or out of range"),
+            Self::Float => write!(f, "value is not a valid float"),
+            Self::Positive => write!(f, "value is out of range, must be positive"),
+            Self::BitOffset => write!(f, "bit offset is not an integer or out of range"),
+            Self::Bit => write!(f, "bit is not an integer or out of range"),
         }
     }
 }
@@ -100,29 +317,153 @@ pub enum Command {
     Set(set::Set),
     Get(get::Get),
     Del(del::Del),
-    // LPush,
-    // RPush,
+    Rename(rename::Rename),
+    Copy(copy::Copy),
+    Expire(expire::Expire),
+    Ttl(ttl::Ttl),
+    Persist(persist::Persist),
+    Subscribe(subscribe::Subscribe),
+    Unsubscribe(unsubscribe::Unsubscribe),
+    Publish(publish::Publish),
+    Command(command::Command),
+    Client(client::Client),
+    HExpire(hexpire::HExpire),
+    HTtl(httl::HTtl),
+    Debug(debug::Debug),
+    Object(object::Object),
+    HSet(hset::HSet),
+    HGet(hget::HGet),
+    HGetAll(hgetall::HGetAll),
+    HDel(hdel::HDel),
+    Type(key_type::Type),
+    SAdd(sadd::SAdd),
+    SRem(srem::SRem),
+    SMembers(smembers::SMembers),
+    SIsMember(sismember::SIsMember),
+    SCard(scard::SCard),
+    Wait(wait::Wait),
+    GetRange(getrange::GetRange),
+    SetRange(setrange::SetRange),
+    RandomKey(randomkey::RandomKey),
+    IncrByFloat(incrbyfloat::IncrByFloat),
+    Push(push::Push),
+    Pop(pop::Pop),
+    LLen(llen::LLen),
+    ExpireAt(expireat::ExpireAt),
+    Multi(multi::Multi),
+    Exec(exec::Exec),
+    Discard(discard::Discard),
+    Dump(dump::Dump),
+    Restore(restore::Restore),
+    SetBit(setbit::SetBit),
+    GetBit(getbit::GetBit),
+    BitCount(bitcount::BitCount),
+    LIndex(lindex::LIndex),
+    LSet(lset::LSet),
+    SetOp(setop::SetOp),
+    HIncrBy(hincrby::HIncrBy),
+    Info(info::Info),
+    Memory(memory::Memory),
+    Time(time::Time),
+    LRem(lrem::LRem),
+    LTrim(ltrim::LTrim),
+    HExists(hexists::HExists),
+    HKeys(hkeys::HKeys),
+    HVals(hvals::HVals),
+    HLen(hlen::HLen),
+    HMGet(hmget::HMGet),
+    Sort(sort::Sort),
+    Move(r#move::Move),
+    SwapDb(swapdb::SwapDb),
+    Select(select::Select),
     // Save,
 }
 
-impl TryFrom<Request> for Vec<Command> {
-    type Error = Response;
-
-    fn try_from(request: Request) -> Result<Self, Self::Error> {
+impl From<Request> for Vec<Result<Command, Response>> {
+    fn from(request: Request) -> Self {
         parse_commands(&request)
     }
 }
 
-impl Execute for Command {
-    fn execute(self) -> Response {
+impl ExecuteInSession for Command {
+    /// Executes the command, or queues it if the client is in a transaction,
+    /// unless it is one of the commands that control the transaction.
+    fn execute_in(self, session: &mut Session) -> Response {
+        if session.in_transaction()
+            && !matches!(self, Self::Multi(_) | Self::Exec(_) | Self::Discard(_))
+        {
+            session.queue(self);
+            return Response::ss("QUEUED");
+        }
+
+        let store = session.store();
         match self {
-            Self::Ping(cmd) => cmd.execute(),
+            Self::Ping(cmd) => cmd.execute_in(session),
             Self::Echo(cmd) => cmd.execute(),
-            Self::Get(cmd) => cmd.execute(),
-            Self::Set(cmd) => cmd.execute(),
-            Self::Exists(cmd) => cmd.execute(),
-            Self::Del(cmd) => cmd.execute(),
+            Self::Get(cmd) => cmd.execute_on(store),
+            Self::Set(cmd) => cmd.execute_on(store),
+            Self::Exists(cmd) => cmd.execute_on(store),
+            Self::Del(cmd) => cmd.execute_on(store),
             Self::Config(cmd) => cmd.execute(),
+            Self::Rename(cmd) => cmd.execute_on(store),
+            Self::Copy(cmd) => cmd.execute_on(store),
+            Self::Expire(cmd) => cmd.execute_on(store),
+            Self::Ttl(cmd) => cmd.execute_on(store),
+            Self::Persist(cmd) => cmd.execute_on(store),
+            Self::Subscribe(cmd) => cmd.execute_in(session),
+            Self::Unsubscribe(cmd) => cmd.execute_in(session),
+            Self::Publish(cmd) => cmd.execute(),
+            Self::Command(cmd) => cmd.execute(),
+            Self::Client(cmd) => cmd.execute_in(session),
+            Self::HExpire(cmd) => cmd.execute_on(store),
+            Self::HTtl(cmd) => cmd.execute_on(store),
+            Self::Debug(cmd) => cmd.execute_in(session),
+            Self::Object(cmd) => cmd.execute_on(store),
+            Self::HSet(cmd) => cmd.execute_on(store),
+            Self::HGet(cmd) => cmd.execute_on(store),
+            Self::HGetAll(cmd) => cmd.execute_on(store),
+            Self::HDel(cmd) => cmd.execute_on(store),
+            Self::Type(cmd) => cmd.execute_on(store),
+            Self::SAdd(cmd) => cmd.execute_on(store),
+            Self::SRem(cmd) => cmd.execute_on(store),
+            Self::SMembers(cmd) => cmd.execute_on(store),
+            Self::SIsMember(cmd) => cmd.execute_on(store),
+            Self::SCard(cmd) => cmd.execute_on(store),
+            Self::Wait(cmd) => cmd.execute(),
+            Self::GetRange(cmd) => cmd.execute_on(store),
+            Self::SetRange(cmd) => cmd.execute_on(store),
+            Self::RandomKey(cmd) => cmd.execute_on(store),
+            Self::IncrByFloat(cmd) => cmd.execute_on(store),
+            Self::Push(cmd) => cmd.execute_on(store),
+            Self::Pop(cmd) => cmd.execute_on(store),
+            Self::LLen(cmd) => cmd.execute_on(store),
+            Self::ExpireAt(cmd) => cmd.execute_on(store),
+            Self::Multi(cmd) => cmd.execute_in(session),
+            Self::Exec(cmd) => cmd.execute_in(session),
+            Self::Discard(cmd) => cmd.execute_in(session),
+            Self::Dump(cmd) => cmd.execute_on(store),
+            Self::Restore(cmd) => cmd.execute_on(store),
+            Self::SetBit(cmd) => cmd.execute_on(store),
+            Self::GetBit(cmd) => cmd.execute_on(store),
+            Self::BitCount(cmd) => cmd.execute_on(store),
+            Self::LIndex(cmd) => cmd.execute_on(store),
+            Self::LSet(cmd) => cmd.execute_on(store),
+            Self::SetOp(cmd) => cmd.execute_on(store),
+            Self::HIncrBy(cmd) => cmd.execute_on(store),
+            Self::Info(cmd) => cmd.execute(),
+            Self::Memory(cmd) => cmd.execute_on(store),
+            Self::Time(cmd) => cmd.execute(),
+            Self::LRem(cmd) => cmd.execute_on(store),
+            Self::LTrim(cmd) => cmd.execute_on(store),
+            Self::HExists(cmd) => cmd.execute_on(store),
+            Self::HKeys(cmd) => cmd.execute_on(store),
+            Self::HVals(cmd) => cmd.execute_on(store),
+            Self::HLen(cmd) => cmd.execute_on(store),
+            Self::HMGet(cmd) => cmd.execute_on(store),
+            Self::Sort(cmd) => cmd.execute_on(store),
+            Self::Move(cmd) => cmd.execute_in(session),
+            Self::SwapDb(cmd) => cmd.execute(),
+            Self::Select(cmd) => cmd.execute_in(session),
         }
     }
 }
@@ -0,0 +1,138 @@
+/// This module contains the implementation of the `Unsubscribe` command.
+/// `UNSUBSCRIBE` removes the client from the given channels, and `PUNSUBSCRIBE` from the given
+/// glob patterns. Without arguments, the client is removed from all of its channels or patterns.
+use crate::{pubsub, response::types::Response, session::Session};
+
+use super::types::ExecuteInSession;
+
+/// Represents the `Unsubscribe` command, covering both `UNSUBSCRIBE` and `PUNSUBSCRIBE`.
+pub struct Unsubscribe {
+    channels: Vec<String>,
+    pattern: bool,
+}
+
+impl ExecuteInSession for Unsubscribe {
+    /// Executes the `Unsubscribe` command, unsubscribing the session from each channel in turn.
+    fn execute_in(self, session: &mut Session) -> Response {
+        let kind = if self.pattern {
+            "punsubscribe"
+        } else {
+            "unsubscribe"
+        };
+
+        let channels = match (self.channels.is_empty(), self.pattern) {
+            (false, _) => self.channels,
+            (true, false) => session.channels(),
+            (true, true) => session.patterns(),
+        };
+
+        if channels.is_empty() {
+            return Response::Multi(vec![pubsub::confirmation(
+                kind,
+                None,
+                session.subscription_count(),
+            )]);
+        }
+
+        Response::Multi(
+            channels
+                .iter()
+                .map(|channel| {
+                    let count = if self.pattern {
+                        session.punsubscribe(channel)
+                    } else {
+                        session.unsubscribe(channel)
+                    };
+                    pubsub::confirmation(kind, Some(channel), count)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Builder for the `Unsubscribe` command.
+pub struct Builder {
+    channels: Vec<String>,
+    pattern: bool,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance for `UNSUBSCRIBE`.
+    pub const fn new() -> Self {
+        Self {
+            channels: Vec::new(),
+            pattern: false,
+        }
+    }
+
+    /// Creates a new `Builder` instance for `PUNSUBSCRIBE`.
+    pub const fn new_pattern() -> Self {
+        Self {
+            channels: Vec::new(),
+            pattern: true,
+        }
+    }
+
+    /// Sets the channels, or patterns, to unsubscribe from.
+    pub fn channels(mut self, channels: &[String]) -> Self {
+        self.channels = channels.to_vec();
+        self
+    }
+
+    /// Builds the `Unsubscribe` command.
+    pub fn build(self) -> Unsubscribe {
+        Unsubscribe {
+            channels: self.channels,
+            pattern: self.pattern,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::{publish, types::Execute};
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_unsubscribe_all() {
+        let (sender, mut receiver) = mpsc::channel(16);
+        let mut session = Session::new(sender);
+        session.subscribe("unsub:ch1");
+        session.psubscribe("unsub:*");
+
+        let cmd = Builder::new().build();
+        assert_eq!(
+            cmd.execute_in(&mut session),
+            Response::Multi(vec![pubsub::confirmation(
+                "unsubscribe",
+                Some("unsub:ch1"),
+                1
+            )])
+        );
+
+        let cmd = Builder::new().build();
+        assert_eq!(
+            cmd.execute_in(&mut session),
+            Response::Multi(vec![pubsub::confirmation("unsubscribe", None, 1)])
+        );
+
+        let cmd = Builder::new_pattern().channels(&["unsub:*".into()]).build();
+        assert_eq!(
+            cmd.execute_in(&mut session),
+            Response::Multi(vec![pubsub::confirmation(
+                "punsubscribe",
+                Some("unsub:*"),
+                0
+            )])
+        );
+
+        let cmd = publish::Builder::new()
+            .channel("unsub:ch1")
+            .message("hello")
+            .build()
+            .unwrap();
+        assert_eq!(cmd.execute(), Response::Integer(0));
+        assert!(receiver.try_recv().is_err());
+    }
+}
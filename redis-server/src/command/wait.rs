@@ -0,0 +1,81 @@
+/// This module contains the implementation of the `Wait` command.
+/// The `Wait` command blocks until writes are acknowledged by a number of replicas.
+/// Since the server has no replicas, it replies with `0` right away.
+use crate::response::types::Response;
+
+use super::types::{ArgumentError, Execute};
+
+/// Represents the `Wait` command.
+pub struct Wait;
+
+impl Execute for Wait {
+    /// Executes the `Wait` command and returns the number of acknowledging replicas.
+    fn execute(self) -> Response {
+        Response::Integer(0)
+    }
+}
+
+/// Builder for the `Wait` command.
+pub struct Builder {
+    numreplicas: Option<String>,
+    timeout: Option<String>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` instance.
+    pub const fn new() -> Self {
+        Self {
+            numreplicas: None,
+            timeout: None,
+        }
+    }
+
+    /// Sets the number of replicas to wait for.
+    pub fn numreplicas(mut self, numreplicas: &str) -> Self {
+        self.numreplicas = Some(numreplicas.into());
+        self
+    }
+
+    /// Sets the timeout in milliseconds.
+    pub fn timeout(mut self, timeout: &str) -> Self {
+        self.timeout = Some(timeout.into());
+        self
+    }
+
+    /// Builds the `Wait` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentError::Missing` if any argument is not provided,
+    /// or an `ArgumentError::Integer` if an argument is not a non-negative integer.
+    pub fn build(self) -> Result<Wait, ArgumentError> {
+        for arg in [self.numreplicas, self.timeout] {
+            arg.ok_or(ArgumentError::Missing)?
+                .parse::<u64>()
+                .map_err(|_| ArgumentError::Integer)?;
+        }
+
+        Ok(Wait)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wait() {
+        let cmd = Builder::new()
+            .numreplicas("0")
+            .timeout("100")
+            .build()
+            .unwrap();
+        assert_eq!(String::from(cmd.execute()), ":0\r\n");
+    }
+
+    #[test]
+    fn test_wait_not_integer() {
+        let result = Builder::new().numreplicas("one").timeout("100").build();
+        assert!(matches!(result, Err(ArgumentError::Integer)));
+    }
+}
@@ -0,0 +1,452 @@
+/// This module contains the configuration of the server, gathered from the command line
+/// and the environment in one place, and kept up to date for `CONFIG GET`.
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    net::{IpAddr, Ipv4Addr},
+    sync::{LazyLock, RwLock},
+    time::Duration,
+};
+
+use tracing::Level;
+
+use crate::glob;
+
+/// The options that can be set from the command line as `--name value`,
+/// or from the environment as `REDIS_NAME`.
+const OPTIONS: &[&str] = &[
+    "port",
+    "bind",
+    "server-heartbeat",
+    "timeout",
+    "request-timeout",
+    "tcp-nodelay",
+    "tcp-keepalive",
+    "maxclients",
+    "client-output-buffer-limit",
+    "client-query-buffer-limit",
+    "hz",
+    "loglevel",
+];
+
+/// The parameters reported by `CONFIG GET` besides the options, which cannot be set.
+const REPORTED_ONLY: &[&str] = &["save", "appendonly"];
+
+/// Options of Redis that the server does not implement, which are refused rather than ignored,
+/// since running without a password or persistence that was asked for would go unnoticed.
+const UNSUPPORTED: &[&str] = &["appendonly", "requirepass"];
+
+/// Represents the configuration of the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    /// The port to listen on.
+    pub port: u16,
+    /// The address to listen on.
+    pub bind: IpAddr,
+    /// How long a subscribed connection may idle before the server sends it a heartbeat, if at all.
+    pub heartbeat: Option<Duration>,
+    /// How long a connection may idle before the server closes it, if at all.
+    pub timeout: Option<Duration>,
+    /// How long a client may take to send a request or to read a reply, if limited.
+    pub request_timeout: Option<Duration>,
+    /// Whether small writes are sent right away, rather than batched by Nagle's algorithm.
+    pub tcp_nodelay: bool,
+    /// How long a connection may idle before TCP keepalive probes are sent, if at all.
+    pub tcp_keepalive: Option<Duration>,
+    /// The number of clients that may be connected at once,
+    /// beyond which new connections wait for a slot to free up.
+    pub maxclients: usize,
+    /// The number of messages that may be pending delivery to a client,
+    /// beyond which the client is disconnected.
+    pub output_buffer_limit: usize,
+    /// The number of bytes of incomplete requests that may be buffered for a client,
+    /// beyond which the client is disconnected.
+    pub query_buffer_limit: usize,
+    /// How many times a second expired keys are sampled and removed in the background.
+    pub hz: u32,
+    /// The most verbose level logged, unless overridden by `RUST_LOG`.
+    pub loglevel: Level,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            port: 6379,
+            bind: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            heartbeat: None,
+            timeout: None,
+            request_timeout: None,
+            tcp_nodelay: true,
+            tcp_keepalive: Some(Duration::from_mins(5)),
+            maxclients: 10_000,
+            output_buffer_limit: 1_024,
+            query_buffer_limit: 1_024 * 1_024 * 1_024,
+            hz: 10,
+            loglevel: Level::INFO,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Builds the configuration from command line arguments and environment variables.
+    ///
+    /// Each option is taken from the arguments if given there, from the environment otherwise,
+    /// and falls back to its default if given in neither.
+    ///
+    /// # Parameters
+    ///
+    /// - `args`: The command line arguments, without the program name.
+    /// - `env`: The environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` naming the offending option if an argument is not a known option,
+    /// has no value, or has an invalid value, or if an unsupported option is given
+    /// either as an argument or in the environment.
+    pub fn from_args_and_env(
+        args: &[String],
+        env: &HashMap<String, String>,
+    ) -> Result<Self, ConfigError> {
+        if let Some(option) = UNSUPPORTED
+            .iter()
+            .find(|option| env.contains_key(&env_var(option)))
+        {
+            return Err(ConfigError::Unsupported((*option).into()));
+        }
+
+        let mut values: Vec<(&str, &str)> = OPTIONS
+            .iter()
+            .filter_map(|option| Some((*option, env.get(&env_var(option))?.as_str())))
+            .collect();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if let Some(option) = arg
+                .strip_prefix("--")
+                .filter(|name| UNSUPPORTED.contains(name))
+            {
+                return Err(ConfigError::Unsupported(option.into()));
+            }
+            let option = arg
+                .strip_prefix("--")
+                .and_then(|name| OPTIONS.iter().find(|option| **option == name))
+                .ok_or_else(|| ConfigError::UnknownOption(arg.clone()))?;
+            let value = args
+                .next()
+                .ok_or_else(|| ConfigError::MissingValue((*option).into()))?;
+            values.push((option, value));
+        }
+
+        let mut config = Self::default();
+        for (option, value) in values {
+            config.set(option, value)?;
+        }
+        Ok(config)
+    }
+
+    /// Sets an option from its textual value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the option is unknown or the value is invalid for it.
+    pub fn set(&mut self, option: &str, value: &str) -> Result<(), ConfigError> {
+        let invalid = || ConfigError::InvalidValue {
+            option: option.into(),
+            value: value.into(),
+        };
+
+        match option {
+            "port" => self.port = value.parse().map_err(|_| invalid())?,
+            "bind" => self.bind = value.parse().map_err(|_| invalid())?,
+            "server-heartbeat" => {
+                let seconds: u64 = value.parse().map_err(|_| invalid())?;
+                self.heartbeat = (seconds > 0).then(|| Duration::from_secs(seconds));
+            }
+            "timeout" => {
+                let seconds: u64 = value.parse().map_err(|_| invalid())?;
+                self.timeout = (seconds > 0).then(|| Duration::from_secs(seconds));
+            }
+            "request-timeout" => {
+                let seconds: u64 = value.parse().map_err(|_| invalid())?;
+                self.request_timeout = (seconds > 0).then(|| Duration::from_secs(seconds));
+            }
+            "tcp-nodelay" => {
+                self.tcp_nodelay = match value {
+                    "yes" => true,
+                    "no" => false,
+                    _ => return Err(invalid()),
+                }
+            }
+            "tcp-keepalive" => {
+                let seconds: u64 = value.parse().map_err(|_| invalid())?;
+                self.tcp_keepalive = (seconds > 0).then(|| Duration::from_secs(seconds));
+            }
+            "maxclients" => {
+                self.maxclients = value
+                    .parse()
+                    .ok()
+                    .filter(|maxclients| *maxclients > 0)
+                    .ok_or_else(invalid)?;
+            }
+            "client-output-buffer-limit" => {
+                self.output_buffer_limit = value
+                    .parse()
+                    .ok()
+                    .filter(|limit| *limit > 0)
+                    .ok_or_else(invalid)?;
+            }
+            "client-query-buffer-limit" => {
+                self.query_buffer_limit = value
+                    .parse()
+                    .ok()
+                    .filter(|limit| *limit > 0)
+                    .ok_or_else(invalid)?;
+            }
+            "hz" => {
+                self.hz = value
+                    .parse()
+                    .ok()
+                    .filter(|hz| (1..=500).contains(hz))
+                    .ok_or_else(invalid)?;
+            }
+            "loglevel" => self.loglevel = value.parse().map_err(|_| invalid())?,
+            _ => return Err(ConfigError::UnknownOption(option.into())),
+        }
+        Ok(())
+    }
+
+    /// Retrieves the textual value of an option, as reported by `CONFIG GET`.
+    ///
+    /// # Returns
+    ///
+    /// The value of the option, or `None` if the option is unknown.
+    pub fn get(&self, option: &str) -> Option<String> {
+        Some(match option {
+            "port" => self.port.to_string(),
+            "bind" => self.bind.to_string(),
+            "server-heartbeat" => self
+                .heartbeat
+                .map_or(0, |heartbeat| heartbeat.as_secs())
+                .to_string(),
+            "timeout" => self
+                .timeout
+                .map_or(0, |timeout| timeout.as_secs())
+                .to_string(),
+            "request-timeout" => self
+                .request_timeout
+                .map_or(0, |timeout| timeout.as_secs())
+                .to_string(),
+            "tcp-nodelay" => if self.tcp_nodelay { "yes" } else { "no" }.into(),
+            "tcp-keepalive" => self
+                .tcp_keepalive
+                .map_or(0, |keepalive| keepalive.as_secs())
+                .to_string(),
+            "maxclients" => self.maxclients.to_string(),
+            "client-output-buffer-limit" => self.output_buffer_limit.to_string(),
+            "client-query-buffer-limit" => self.query_buffer_limit.to_string(),
+            "hz" => self.hz.to_string(),
+            "loglevel" => self.loglevel.as_str().to_lowercase(),
+            // neither snapshotting nor the append-only file is supported
+            "save" => String::new(),
+            "appendonly" => "no".into(),
+            _ => return None,
+        })
+    }
+
+    /// Retrieves the parameters matching a glob pattern along with their values,
+    /// as reported by `CONFIG GET`.
+    ///
+    /// # Returns
+    ///
+    /// The name and value of each matching parameter, empty if none match.
+    pub fn get_matching(&self, pattern: &str) -> Vec<(String, String)> {
+        let pattern = pattern.to_lowercase();
+        OPTIONS
+            .iter()
+            .chain(REPORTED_ONLY)
+            .filter(|parameter| glob::matches(&pattern, parameter))
+            .filter_map(|parameter| Some(((*parameter).to_string(), self.get(parameter)?)))
+            .collect()
+    }
+}
+
+/// Returns the environment variable an option falls back to, such as `REDIS_PORT` for `port`.
+fn env_var(option: &str) -> String {
+    format!("REDIS_{}", option.to_uppercase().replace('-', "_"))
+}
+
+/// Represents the errors that can occur while building the configuration.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    UnknownOption(String),
+    Unsupported(String),
+    MissingValue(String),
+    InvalidValue { option: String, value: String },
+}
+
+impl Error for ConfigError {}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownOption(option) => write!(f, "unknown option '{option}'"),
+            Self::Unsupported(option) => write!(f, "option '{option}' is not supported"),
+            Self::MissingValue(option) => write!(f, "missing value for option '{option}'"),
+            Self::InvalidValue { option, value } => {
+                write!(f, "invalid value '{value}' for option '{option}'")
+            }
+        }
+    }
+}
+
+/// The configuration the server is running with.
+pub static SERVER_CONFIG: LazyLock<RwLock<ServerConfig>> =
+    LazyLock::new(|| RwLock::new(ServerConfig::default()));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_from_args_and_env_valid() {
+        let config = ServerConfig::from_args_and_env(
+            &args(&[
+                "--port",
+                "7000",
+                "--bind",
+                "0.0.0.0",
+                "--server-heartbeat",
+                "30",
+                "--timeout",
+                "90",
+                "--request-timeout",
+                "10",
+                "--tcp-nodelay",
+                "no",
+                "--tcp-keepalive",
+                "0",
+                "--maxclients",
+                "2",
+                "--client-output-buffer-limit",
+                "64",
+                "--client-query-buffer-limit",
+                "4096",
+                "--hz",
+                "50",
+                "--loglevel",
+                "debug",
+            ]),
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            ServerConfig {
+                port: 7000,
+                bind: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                heartbeat: Some(Duration::from_secs(30)),
+                timeout: Some(Duration::from_secs(90)),
+                request_timeout: Some(Duration::from_secs(10)),
+                tcp_nodelay: false,
+                tcp_keepalive: None,
+                maxclients: 2,
+                output_buffer_limit: 64,
+                query_buffer_limit: 4_096,
+                hz: 50,
+                loglevel: Level::DEBUG,
+            }
+        );
+        assert_eq!(config.get("appendonly"), Some("no".into()));
+        assert_eq!(config.get("loglevel"), Some("debug".into()));
+    }
+
+    #[test]
+    fn test_from_args_and_env_invalid_port() {
+        let result = ServerConfig::from_args_and_env(&args(&["--port", "70000"]), &HashMap::new());
+        assert_eq!(
+            result,
+            Err(ConfigError::InvalidValue {
+                option: "port".into(),
+                value: "70000".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_args_and_env_unknown_option() {
+        let result = ServerConfig::from_args_and_env(&args(&["--colour", "red"]), &HashMap::new());
+        assert_eq!(result, Err(ConfigError::UnknownOption("--colour".into())));
+
+        let result = ServerConfig::from_args_and_env(&args(&["--port"]), &HashMap::new());
+        assert_eq!(result, Err(ConfigError::MissingValue("port".into())));
+
+        let result =
+            ServerConfig::from_args_and_env(&args(&["--loglevel", "loud"]), &HashMap::new());
+        assert_eq!(
+            result,
+            Err(ConfigError::InvalidValue {
+                option: "loglevel".into(),
+                value: "loud".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_args_and_env_precedence() {
+        let env = HashMap::from([
+            ("REDIS_PORT".to_string(), "7001".to_string()),
+            ("REDIS_TIMEOUT".to_string(), "45".to_string()),
+        ]);
+
+        let config = ServerConfig::from_args_and_env(&[], &env).unwrap();
+        assert_eq!(config.port, 7001);
+        assert_eq!(config.timeout, Some(Duration::from_secs(45)));
+
+        let config = ServerConfig::from_args_and_env(&args(&["--port", "7002"]), &env).unwrap();
+        assert_eq!(config.port, 7002);
+        assert_eq!(config.timeout, Some(Duration::from_secs(45)));
+    }
+
+    #[test]
+    fn test_from_args_and_env_unsupported() {
+        let result =
+            ServerConfig::from_args_and_env(&args(&["--requirepass", "secret"]), &HashMap::new());
+        assert_eq!(result, Err(ConfigError::Unsupported("requirepass".into())));
+
+        let env = HashMap::from([("REDIS_APPENDONLY".to_string(), "yes".to_string())]);
+        let result = ServerConfig::from_args_and_env(&[], &env);
+        assert_eq!(result, Err(ConfigError::Unsupported("appendonly".into())));
+
+        // nor can the password be read back
+        assert_eq!(ServerConfig::default().get("requirepass"), None);
+    }
+
+    #[test]
+    fn test_get_matching() {
+        let config = ServerConfig::default();
+        let all = config.get_matching("*");
+        assert_eq!(all.len(), OPTIONS.len() + REPORTED_ONLY.len());
+        for (parameter, value) in &all {
+            assert_eq!(config.get(parameter).as_ref(), Some(value));
+        }
+
+        assert_eq!(
+            config.get_matching("TCP-*"),
+            vec![
+                ("tcp-nodelay".to_string(), "yes".to_string()),
+                ("tcp-keepalive".to_string(), "300".to_string()),
+            ]
+        );
+        assert_eq!(
+            config.get_matching("port"),
+            vec![("port".to_string(), "6379".to_string())]
+        );
+        assert_eq!(config.get_matching("maxmemory*"), vec![]);
+    }
+}
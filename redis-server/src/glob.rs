@@ -0,0 +1,111 @@
+//! This module provides Redis-style glob matching, as used by `PSUBSCRIBE`.
+//!
+//! Supported syntax:
+//! - `*` matches any sequence of characters, including none.
+//! - `?` matches exactly one character.
+//! - `[abc]`, `[a-z]`, and `[^abc]` match one character from (or not from) a set.
+//! - `\x` matches the character `x` literally.
+
+/// Checks if a string matches a glob pattern.
+///
+/// # Parameters
+///
+/// - `pattern`: The glob pattern to match against.
+/// - `s`: The string to match.
+///
+/// # Returns
+///
+/// `true` if the whole string matches the pattern, `false` otherwise.
+pub fn matches(pattern: &str, s: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+    matches_from(&pattern, &s)
+}
+
+fn matches_from(pattern: &[char], s: &[char]) -> bool {
+    match pattern.first() {
+        None => s.is_empty(),
+        Some('*') => {
+            let rest = &pattern[1..];
+            if rest.first() == Some(&'*') {
+                return matches_from(rest, s);
+            }
+            (0..=s.len()).any(|i| matches_from(rest, &s[i..]))
+        }
+        Some('?') => !s.is_empty() && matches_from(&pattern[1..], &s[1..]),
+        Some('[') => match (s.first(), match_class(&pattern[1..], s.first())) {
+            (Some(_), Some((matched, rest))) => matched && matches_from(rest, &s[1..]),
+            // an unterminated set is matched literally
+            _ => s.first() == Some(&'[') && matches_from(&pattern[1..], &s[1..]),
+        },
+        Some('\\') if pattern.len() > 1 => {
+            s.first() == Some(&pattern[1]) && matches_from(&pattern[2..], &s[1..])
+        }
+        Some(c) => s.first() == Some(c) && matches_from(&pattern[1..], &s[1..]),
+    }
+}
+
+/// Matches a character against a set, starting right after the opening `[`.
+///
+/// # Returns
+///
+/// Whether the character is matched by the set, along with the pattern after the closing `]`,
+/// or `None` if the set is not terminated.
+fn match_class<'a>(pattern: &'a [char], c: Option<&char>) -> Option<(bool, &'a [char])> {
+    let (negate, mut i) = match pattern.first() {
+        Some('^') => (true, 1),
+        _ => (false, 0),
+    };
+
+    let mut matched = false;
+    loop {
+        match pattern.get(i)? {
+            ']' => break,
+            '\\' => {
+                i += 1;
+                matched |= c == Some(pattern.get(i)?);
+            }
+            start if pattern.get(i + 1) == Some(&'-') && pattern.get(i + 2) != Some(&']') => {
+                let end = pattern.get(i + 2)?;
+                let (low, high) = if start <= end {
+                    (start, end)
+                } else {
+                    (end, start)
+                };
+                matched |= c.is_some_and(|c| low <= c && c <= high);
+                i += 2;
+            }
+            other => matched |= c == Some(other),
+        }
+        i += 1;
+    }
+
+    Some((matched != negate, &pattern[i + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_wildcards() {
+        assert!(matches("news.*", "news.tech"));
+        assert!(matches("news.*", "news."));
+        assert!(!matches("news.*", "sports.tech"));
+        assert!(matches("h?llo", "hello"));
+        assert!(!matches("h?llo", "hllo"));
+        assert!(matches("*", ""));
+    }
+
+    #[test]
+    fn test_matches_sets_and_escapes() {
+        assert!(matches("h[ae]llo", "hallo"));
+        assert!(!matches("h[ae]llo", "hillo"));
+        assert!(matches("h[^e]llo", "hallo"));
+        assert!(!matches("h[^e]llo", "hello"));
+        assert!(matches("h[a-c]llo", "hbllo"));
+        assert!(matches("h\\*llo", "h*llo"));
+        assert!(!matches("h\\*llo", "hello"));
+        assert!(matches("h[llo", "h[llo"));
+    }
+}
@@ -1,9 +1,155 @@
-use dashmap::DashMap;
-use once_cell::sync::Lazy;
+use dashmap::{mapref::one::RefMut, DashMap};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::{BuildHasher, RandomState},
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        LazyLock, PoisonError, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Represents a value stored under a key, or a field of a hash, along with its optional expiry.
+#[derive(Clone)]
+struct Entry<V> {
+    value: V,
+    expires_at: Option<Instant>,
+    /// When the entry was last read or written, to tell how long it has been idle.
+    accessed_at: Instant,
+}
+
+/// Represents the fields of a hash, each of which may expire on its own.
+type Hash = HashMap<String, Entry<String>>;
+
+/// Represents the members of a set.
+type Set = HashSet<String>;
+
+/// Represents the elements of a list, from head to tail.
+type List = VecDeque<String>;
+
+/// Represents the kinds of values that can be stored under a key.
+///
+/// Strings are kept as raw bytes, since `SETRANGE` and `SETBIT` may leave them
+/// holding anything, not only valid UTF-8.
+#[derive(Clone)]
+enum Value {
+    String(Vec<u8>),
+    Hash(Hash),
+    Set(Set),
+    List(List),
+}
+
+impl<V> Entry<V> {
+    /// Creates a new `Entry` without an expiry, accessed just now.
+    fn new(value: V) -> Self {
+        Self {
+            value,
+            expires_at: None,
+            accessed_at: Instant::now(),
+        }
+    }
+
+    /// Checks if the entry has outlived its expiry.
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Instant::now())
+    }
+}
+
+/// Represents the expiry state of an existing key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Expiry {
+    /// The key has no expiry.
+    Persistent,
+    /// The key expires after the remaining duration.
+    In(Duration),
+}
+
+impl Value {
+    /// Returns the name of the type of the value, as reported by `TYPE`.
+    const fn type_name(&self) -> &'static str {
+        match self {
+            Self::String(_) => "string",
+            Self::Hash(_) => "hash",
+            Self::Set(_) => "set",
+            Self::List(_) => "list",
+        }
+    }
+
+    /// Returns the name of the internal encoding Redis would use for the value.
+    fn encoding(&self) -> &'static str {
+        match self {
+            Self::String(value)
+                if std::str::from_utf8(value).is_ok_and(|value| value.parse::<i64>().is_ok()) =>
+            {
+                "int"
+            }
+            Self::String(value) if value.len() <= 44 => "embstr",
+            Self::String(_) => "raw",
+            Self::Hash(fields)
+                if fields.len() <= 128
+                    && fields
+                        .iter()
+                        .all(|(field, entry)| field.len() <= 64 && entry.value.len() <= 64) =>
+            {
+                "listpack"
+            }
+            Self::Set(members)
+                if members.len() <= 512
+                    && members.iter().all(|member| member.parse::<i64>().is_ok()) =>
+            {
+                "intset"
+            }
+            Self::Set(members)
+                if members.len() <= 128 && members.iter().all(|member| member.len() <= 64) =>
+            {
+                "listpack"
+            }
+            Self::List(elements)
+                if elements.len() <= 128 && elements.iter().all(|element| element.len() <= 64) =>
+            {
+                "listpack"
+            }
+            Self::Hash(_) | Self::Set(_) => "hashtable",
+            Self::List(_) => "quicklist",
+        }
+    }
+
+    /// Estimates the number of bytes the value takes up, counting the contents of its strings
+    /// along with the bookkeeping kept for each of them.
+    fn memory_usage(&self) -> usize {
+        /// The bytes taken up by a string, including the bytes it owns on the heap.
+        fn string_usage(string: &str) -> usize {
+            size_of::<String>() + string.len()
+        }
+
+        match self {
+            Self::String(value) => size_of::<Vec<u8>>() + value.len(),
+            Self::Hash(fields) => fields
+                .iter()
+                .map(|(field, entry)| {
+                    string_usage(field) + size_of::<Entry<String>>() + entry.value.len()
+                })
+                .sum(),
+            Self::Set(members) => members.iter().map(|member| string_usage(member)).sum(),
+            Self::List(elements) => elements.iter().map(|element| string_usage(element)).sum(),
+        }
+    }
+}
+
+/// Signals an operation against a key holding the wrong kind of value.
+#[derive(Debug, PartialEq, Eq)]
+pub struct WrongType;
 
 /// Represents a key-value store.
 pub struct KvStore {
-    store: DashMap<String, String>,
+    store: DashMap<String, Entry<Value>>,
+    active_expire: AtomicBool,
+    /// The number of keys removed for having expired.
+    expired_keys: AtomicU64,
+    /// The number of keys removed to free memory.
+    evicted_keys: AtomicU64,
 }
 
 impl KvStore {
@@ -12,19 +158,17 @@ impl KvStore {
     /// # Returns
     ///
     /// A new `KvStore` instance.
-    fn new() -> Self {
+    pub fn new() -> Self {
         // TODO: load from disk
 
         Self {
             store: DashMap::new(),
+            active_expire: AtomicBool::new(true),
+            expired_keys: AtomicU64::new(0),
+            evicted_keys: AtomicU64::new(0),
         }
     }
 
-    /// Saves the contents of the key-value store to disk.
-    pub fn save(&self) {
-        todo!()
-    }
-
     /// Returns the number of key-value pairs in the store.
     ///
     /// # Returns
@@ -34,6 +178,206 @@ impl KvStore {
         self.store.len()
     }
 
+    /// Removes every key from the store.
+    pub fn flush(&self) {
+        self.store.clear();
+    }
+
+    /// Rebuilds the store from a snapshot of itself, as if it was saved and loaded again.
+    /// Keys that have expired by then are not restored.
+    ///
+    /// Every shard of the store stays locked from the snapshot until the rebuilt contents
+    /// replace it, so that other clients see either the old keys or the reloaded ones,
+    /// and no write lands in between to be lost.
+    pub fn reload(&self) {
+        let mut shards: Vec<_> = self
+            .store
+            .shards()
+            .iter()
+            .map(|shard| shard.write())
+            .collect();
+        let mut snapshot = Vec::new();
+        for shard in &shards {
+            for (key, entry) in shard.iter() {
+                write_entry(&mut snapshot, key, entry.get());
+            }
+        }
+
+        // hashing keys the same way puts each key in the same shard of the rebuilt map
+        let reloaded =
+            DashMap::with_hasher_and_shard_amount(self.store.hasher().clone(), shards.len());
+        let mut snapshot = &snapshot[..];
+        while let Some((key, entry)) = read_entry(&mut snapshot) {
+            reloaded.insert(key, entry);
+        }
+        for (shard, reloaded) in shards.iter_mut().zip(reloaded.into_shards()) {
+            **shard = reloaded.into_inner();
+        }
+    }
+
+    /// Serializes the value of a key, without the key itself or its expiry,
+    /// in the same encoding as a snapshot.
+    ///
+    /// # Returns
+    ///
+    /// The serialized value, or `None` if the key does not exist.
+    pub fn dump(&self, key: &str) -> Option<Vec<u8>> {
+        self.evict_if_expired(key);
+        self.store.get(key).map(|entry| {
+            let mut payload = Vec::new();
+            write_value(&mut payload, &entry.value);
+            payload
+        })
+    }
+
+    /// Recreates a key from a value serialized by `dump`.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to store the value under.
+    /// - `payload`: The serialized value.
+    /// - `ttl`: The time to live of the key, or `None` to keep it forever.
+    /// - `replace`: Whether to overwrite `key` if it already exists.
+    ///
+    /// # Returns
+    ///
+    /// `Some(true)` if the key was restored, `Some(false)` if it exists and `replace` is `false`,
+    /// or `None` if the payload does not hold a valid value.
+    pub fn restore_value(
+        &self,
+        key: &str,
+        mut payload: &[u8],
+        ttl: Option<Duration>,
+        replace: bool,
+    ) -> Option<bool> {
+        let value = read_value(&mut payload).filter(|_| payload.is_empty())?;
+        if !replace && self.exists(key) {
+            return Some(false);
+        }
+
+        let entry = Entry {
+            expires_at: ttl.and_then(|ttl| Instant::now().checked_add(ttl)),
+            ..Entry::new(value)
+        };
+        self.store.insert(key.into(), entry);
+        Some(true)
+    }
+
+    /// Checks whether expired keys are sampled and removed in the background,
+    /// rather than only when they are accessed.
+    pub fn active_expire(&self) -> bool {
+        self.active_expire.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables the background removal of expired keys.
+    pub fn set_active_expire(&self, enabled: bool) {
+        self.active_expire.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Retrieves the number of keys removed for having expired,
+    /// whether on access or by an active expiry cycle.
+    pub fn expired_keys(&self) -> u64 {
+        self.expired_keys.load(Ordering::Relaxed)
+    }
+
+    /// Retrieves the number of keys removed to free memory.
+    ///
+    /// Nothing evicts keys while the store has no memory limit, so this stays at `0`.
+    pub fn evicted_keys(&self) -> u64 {
+        self.evicted_keys.load(Ordering::Relaxed)
+    }
+
+    /// Samples up to `count` keys with an expiry, starting from a random key,
+    /// and removes those that have expired.
+    ///
+    /// Keys are otherwise only removed once they are accessed after expiring,
+    /// so this lets an active expiry cycle reclaim keys that are never accessed again.
+    ///
+    /// # Returns
+    ///
+    /// The number of expired keys that were removed.
+    pub fn sample_expired(&self, count: usize) -> usize {
+        let len = self.store.len();
+        if len == 0 {
+            return 0;
+        }
+
+        let start = usize::try_from(RandomState::new().hash_one(len)).unwrap_or_default() % len;
+        // the keys are collected first, since removing them while iterating would deadlock
+        let sample = |skip: usize, take: usize, count: usize| -> Vec<(String, bool)> {
+            self.store
+                .iter()
+                .skip(skip)
+                .take(take)
+                .filter(|entry| entry.expires_at.is_some())
+                .take(count)
+                .map(|entry| (entry.key().clone(), entry.is_expired()))
+                .collect()
+        };
+        let mut sampled = sample(start, len, count);
+        // wrap around to the first keys if the sample ran off the end
+        if sampled.len() < count {
+            sampled.extend(sample(0, start, count - sampled.len()));
+        }
+
+        let removed = sampled
+            .into_iter()
+            .filter(|(key, expired)| {
+                *expired
+                    && self
+                        .store
+                        .remove_if(key, |_, entry| entry.is_expired())
+                        .is_some()
+            })
+            .count();
+        self.expired_keys.fetch_add(
+            u64::try_from(removed).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        removed
+    }
+
+    /// Removes a key if it has expired, so that it is no longer visible to readers.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to check.
+    fn evict_if_expired(&self, key: &str) {
+        if self
+            .store
+            .remove_if(key, |_, entry| entry.is_expired())
+            .is_some()
+        {
+            self.expired_keys.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Looks up the live entry of a key for reading or writing its value,
+    /// which counts as an access for `idle_time`.
+    ///
+    /// # Returns
+    ///
+    /// The entry of the key, or `None` if the key does not exist.
+    fn lookup(&self, key: &str) -> Option<RefMut<'_, String, Entry<Value>>> {
+        self.evict_if_expired(key);
+        let mut entry = self.store.get_mut(key)?;
+        entry.accessed_at = Instant::now();
+
+        Some(entry)
+    }
+
+    /// Looks up the live entry of a key like `lookup`, storing a value first if the key does not exist.
+    fn lookup_or_insert(&self, key: &str, value: Value) -> RefMut<'_, String, Entry<Value>> {
+        self.evict_if_expired(key);
+        let mut entry = self
+            .store
+            .entry(key.into())
+            .or_insert_with(|| Entry::new(value));
+        entry.accessed_at = Instant::now();
+
+        entry
+    }
+
     /// Checks if a key exists in the store.
     ///
     /// # Parameters
@@ -44,9 +388,34 @@ impl KvStore {
     ///
     /// `true` if the key exists, `false` otherwise.
     pub fn exists(&self, key: &str) -> bool {
+        self.evict_if_expired(key);
         self.store.contains_key(key)
     }
 
+    /// Picks a live key at random.
+    ///
+    /// Only the chosen key is cloned, and an expired pick is removed before trying another,
+    /// so that expired keys are never returned.
+    ///
+    /// # Returns
+    ///
+    /// A random key, or `None` if the store is empty.
+    pub fn random_key(&self) -> Option<String> {
+        loop {
+            let len = self.store.len();
+            if len == 0 {
+                return None;
+            }
+
+            // hashing with a freshly seeded state yields a random number without a dependency
+            let index = usize::try_from(RandomState::new().hash_one(len)).unwrap_or_default() % len;
+            let key = self.store.iter().nth(index)?.key().clone();
+            if self.exists(&key) {
+                return Some(key);
+            }
+        }
+    }
+
     /// Retrieves the value associated with a key from the store.
     ///
     /// # Parameters
@@ -56,18 +425,249 @@ impl KvStore {
     /// # Returns
     ///
     /// The value associated with the key, or `None` if the key does not exist.
-    pub fn get(&self, key: &str) -> Option<String> {
-        self.store.get(key).map(|v| v.value().clone())
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a string.
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>, WrongType> {
+        self.lookup(key)
+            .map_or(Ok(None), |entry| match &entry.value {
+                Value::String(value) => Ok(Some(value.clone())),
+                Value::Hash(_) | Value::Set(_) | Value::List(_) => Err(WrongType),
+            })
+    }
+
+    /// Retrieves the name of the type of the value stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The type, such as `string` or `hash`, or `None` if the key does not exist.
+    pub fn type_name(&self, key: &str) -> Option<&'static str> {
+        self.evict_if_expired(key);
+        self.store.get(key).map(|entry| entry.value.type_name())
+    }
+
+    /// Retrieves the name of the internal encoding of the value stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The encoding, such as `int` or `embstr`, or `None` if the key does not exist.
+    pub fn encoding(&self, key: &str) -> Option<&'static str> {
+        self.evict_if_expired(key);
+        self.store.get(key).map(|entry| entry.value.encoding())
+    }
+
+    /// Estimates the number of bytes a key and its value take up in the store.
+    ///
+    /// # Returns
+    ///
+    /// The length of the key and the value along with their overhead,
+    /// or `None` if the key does not exist.
+    pub fn memory_usage(&self, key: &str) -> Option<usize> {
+        self.evict_if_expired(key);
+        self.store
+            .get(key)
+            .map(|entry| entry_usage(entry.key(), &entry))
+    }
+
+    /// Estimates the number of bytes every key and value take up in the store,
+    /// as the sum of the `memory_usage` of each key.
+    pub fn total_memory(&self) -> usize {
+        self.store
+            .iter()
+            .map(|entry| entry_usage(entry.key(), entry.value()))
+            .sum()
+    }
+
+    /// Retrieves how long ago a key was last read or written, without counting as an access.
+    ///
+    /// # Returns
+    ///
+    /// The idle time of the key, or `None` if the key does not exist.
+    pub fn idle_time(&self, key: &str) -> Option<Duration> {
+        self.evict_if_expired(key);
+        self.store.get(key).map(|entry| entry.accessed_at.elapsed())
     }
 
-    /// Sets a key-value pair in the store.
+    /// Sets a key-value pair in the store, discarding any expiry the key had.
     ///
     /// # Parameters
     ///
     /// - `key`: The key to set.
     /// - `value`: The value to associate with the key.
     pub fn set(&self, key: &str, value: &str) {
-        self.store.insert(key.into(), value.into());
+        self.store
+            .insert(key.into(), Entry::new(Value::String(value.into())));
+    }
+
+    /// Retrieves the bytes of the string stored at a key between two offsets, both inclusive.
+    /// Negative offsets count from the end of the string, and out-of-range offsets are clamped.
+    ///
+    /// # Returns
+    ///
+    /// The bytes in the range, empty if the range or the string is empty or the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a string.
+    pub fn getrange(&self, key: &str, start: i64, end: i64) -> Result<Vec<u8>, WrongType> {
+        let Some(value) = self.get(key)? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(index_range(value.len(), start, end)
+            .map_or_else(Vec::new, |range| value[range].to_vec()))
+    }
+
+    /// Counts the set bits of the string stored at a key, optionally only within a range
+    /// of bytes, both inclusive, taken like the range of `getrange`.
+    ///
+    /// # Returns
+    ///
+    /// The number of set bits, `0` if the range or the string is empty or the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a string.
+    pub fn bitcount(&self, key: &str, range: Option<(i64, i64)>) -> Result<usize, WrongType> {
+        let Some(value) = self.get(key)? else {
+            return Ok(0);
+        };
+
+        let bytes = match range {
+            Some((start, end)) => match index_range(value.len(), start, end) {
+                Some(range) => &value[range],
+                None => &[],
+            },
+            None => &value,
+        };
+        Ok(bytes.iter().map(|byte| byte.count_ones() as usize).sum())
+    }
+
+    /// Overwrites the string stored at a key from a byte offset onwards, keeping its expiry.
+    /// The string is created if the key does not exist, and padded with zero bytes
+    /// if it is shorter than the offset.
+    ///
+    /// # Returns
+    ///
+    /// The length of the string afterwards, or `0` if the key does not exist
+    /// and `value` is empty, in which case no key is created.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a string.
+    pub fn setrange(&self, key: &str, offset: usize, value: &[u8]) -> Result<usize, WrongType> {
+        if value.is_empty() {
+            return self
+                .get(key)
+                .map(|current| current.map_or(0, |current| current.len()));
+        }
+
+        let mut entry = self.lookup_or_insert(key, Value::String(Vec::new()));
+        let Value::String(current) = &mut entry.value else {
+            return Err(WrongType);
+        };
+
+        if current.len() < offset + value.len() {
+            current.resize(offset + value.len(), 0);
+        }
+        current[offset..offset + value.len()].copy_from_slice(value);
+        Ok(current.len())
+    }
+
+    /// Sets or clears the bit at an offset of the string stored at a key, keeping its expiry.
+    /// Bits are numbered from the most significant bit of the first byte.
+    /// The string is created if the key does not exist, and padded with zero bytes
+    /// if it is too short to hold the bit.
+    ///
+    /// # Returns
+    ///
+    /// The previous value of the bit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a string.
+    pub fn setbit(&self, key: &str, offset: usize, bit: bool) -> Result<bool, WrongType> {
+        let mut entry = self.lookup_or_insert(key, Value::String(Vec::new()));
+        let Value::String(current) = &mut entry.value else {
+            return Err(WrongType);
+        };
+
+        let (index, mask) = (offset / 8, 0x80 >> (offset % 8));
+        if current.len() <= index {
+            current.resize(index + 1, 0);
+        }
+        let previous = current[index] & mask != 0;
+        if bit {
+            current[index] |= mask;
+        } else {
+            current[index] &= !mask;
+        }
+        Ok(previous)
+    }
+
+    /// Retrieves the bit at an offset of the string stored at a key,
+    /// numbered from the most significant bit of the first byte.
+    ///
+    /// # Returns
+    ///
+    /// The value of the bit, `false` if the offset is beyond the string or the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a string.
+    pub fn getbit(&self, key: &str, offset: usize) -> Result<bool, WrongType> {
+        let Some(value) = self.get(key)? else {
+            return Ok(false);
+        };
+
+        Ok(value
+            .get(offset / 8)
+            .is_some_and(|byte| byte & (0x80 >> (offset % 8)) != 0))
+    }
+
+    /// Increments the number stored at a key by a floating-point amount, keeping its expiry.
+    /// A missing key is treated as holding `0`.
+    ///
+    /// # Returns
+    ///
+    /// The new value, formatted without trailing zeros, or `None` if the stored value
+    /// is not a valid float or the result would be infinite or not a number.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a string.
+    pub fn incrbyfloat(&self, key: &str, increment: f64) -> Result<Option<String>, WrongType> {
+        /// Adds the increment to a stored value, if both it and the sum are finite.
+        fn add(current: &[u8], increment: f64) -> Option<f64> {
+            std::str::from_utf8(current)
+                .ok()?
+                .parse::<f64>()
+                .ok()
+                .filter(|current| current.is_finite())
+                .map(|current| current + increment)
+                .filter(|value| value.is_finite())
+        }
+
+        self.evict_if_expired(key);
+        match self.store.entry(key.into()) {
+            dashmap::mapref::entry::Entry::Occupied(mut entry) => {
+                entry.get_mut().accessed_at = Instant::now();
+                let Value::String(current) = &mut entry.get_mut().value else {
+                    return Err(WrongType);
+                };
+                Ok(add(current, increment).map(|value| {
+                    let value = value.to_string();
+                    *current = value.clone().into_bytes();
+                    value
+                }))
+            }
+            dashmap::mapref::entry::Entry::Vacant(entry) => Ok(add(b"0", increment).map(|value| {
+                let value = value.to_string();
+                entry.insert(Entry::new(Value::String(value.clone().into_bytes())));
+                value
+            })),
+        }
     }
 
     /// Removes a key-value pair from the store.
@@ -78,18 +678,1310 @@ impl KvStore {
     ///
     /// # Returns
     ///
-    /// The value associated with the removed key, or `None` if the key does not exist.
-    pub fn del(&self, key: &str) -> Option<String> {
-        self.store.remove(key).map(|v| v.1)
+    /// `true` if the key was removed, `false` if the key does not exist.
+    pub fn del(&self, key: &str) -> bool {
+        self.evict_if_expired(key);
+        self.store.remove(key).is_some()
     }
-}
 
-/// Drop is not called for static variables.
-impl Drop for KvStore {
-    fn drop(&mut self) {
-        self.save();
+    /// Renames a key, moving its value and expiry to the new key.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to rename.
+    /// - `new_key`: The new name for the key.
+    /// - `replace`: Whether to overwrite `new_key` if it already exists.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `key` does not exist, `Some(false)` if `new_key` exists and `replace` is `false`,
+    /// or `Some(true)` if the key was renamed.
+    pub fn rename(&self, key: &str, new_key: &str, replace: bool) -> Option<bool> {
+        if !self.exists(key) {
+            return None;
+        }
+
+        if key == new_key {
+            return Some(replace);
+        }
+
+        if !replace && self.exists(new_key) {
+            return Some(false);
+        }
+
+        let (_, entry) = self.store.remove(key)?;
+        self.store.insert(new_key.into(), entry);
+        Some(true)
+    }
+
+    /// Copies the value and expiry of a key to another key, leaving the original intact.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to copy.
+    /// - `new_key`: The key to copy the value to.
+    /// - `replace`: Whether to overwrite `new_key` if it already exists.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the value was copied, `false` if `key` does not exist or `new_key` exists and
+    /// `replace` is `false`.
+    pub fn copy(&self, key: &str, new_key: &str, replace: bool) -> bool {
+        self.evict_if_expired(key);
+        let Some(entry) = self.store.get(key).map(|v| v.value().clone()) else {
+            return false;
+        };
+
+        if !replace && self.exists(new_key) {
+            return false;
+        }
+
+        self.store.insert(new_key.into(), entry);
+        true
+    }
+
+    /// Moves a key, along with its expiry, to another store.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was moved, `false` if it does not exist or the other store holds it.
+    pub fn move_key(&self, key: &str, target: &Self) -> bool {
+        self.evict_if_expired(key);
+        target.evict_if_expired(key);
+        let Some((key, entry)) = self.store.remove(key) else {
+            return false;
+        };
+
+        // the target is checked and written under one lock, so that a key written there
+        // meanwhile is never replaced
+        let key = match target.store.entry(key) {
+            dashmap::mapref::entry::Entry::Vacant(vacant) => {
+                vacant.insert(entry);
+                return true;
+            }
+            dashmap::mapref::entry::Entry::Occupied(occupied) => occupied.key().clone(),
+        };
+        // unless the source was written meanwhile, the entry is put back
+        self.store.entry(key).or_insert(entry);
+        false
+    }
+
+    /// Sets a key to expire after the given duration.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to set the expiry for.
+    /// - `ttl`: The time to live from now.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the expiry was set, `false` if the key does not exist.
+    pub fn expire(&self, key: &str, ttl: Duration) -> bool {
+        self.evict_if_expired(key);
+        let Some(expires_at) = Instant::now().checked_add(ttl) else {
+            return false;
+        };
+
+        self.store
+            .get_mut(key)
+            .map(|mut entry| entry.expires_at = Some(expires_at))
+            .is_some()
+    }
+
+    /// Retrieves the remaining time to live of a key.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to retrieve the time to live for.
+    ///
+    /// # Returns
+    ///
+    /// The `Expiry` of the key, or `None` if the key does not exist.
+    pub fn ttl(&self, key: &str) -> Option<Expiry> {
+        self.evict_if_expired(key);
+        self.store.get(key).map(|entry| match entry.expires_at {
+            Some(expires_at) => Expiry::In(expires_at.saturating_duration_since(Instant::now())),
+            None => Expiry::Persistent,
+        })
+    }
+
+    /// Removes the expiry of a key, making it persistent.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to persist.
+    ///
+    /// # Returns
+    ///
+    /// `true` if an expiry was removed, `false` if the key does not exist or has no expiry.
+    pub fn persist(&self, key: &str) -> bool {
+        self.evict_if_expired(key);
+        self.store
+            .get_mut(key)
+            .and_then(|mut entry| entry.expires_at.take())
+            .is_some()
+    }
+
+    /// Runs an operation on the live fields of the hash stored at a key,
+    /// removing the key afterwards if the hash was left empty.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key of the hash.
+    /// - `create`: Whether to create an empty hash if the key does not exist.
+    /// - `operation`: The operation to run on the fields of the hash.
+    ///
+    /// # Returns
+    ///
+    /// The result of the operation, or `None` if the key does not exist and `create` is `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    fn with_hash<R>(
+        &self,
+        key: &str,
+        create: bool,
+        operation: impl FnOnce(&mut Hash) -> R,
+    ) -> Result<Option<R>, WrongType> {
+        let result = {
+            let mut entry = if create {
+                self.lookup_or_insert(key, Value::Hash(Hash::new()))
+            } else {
+                match self.lookup(key) {
+                    Some(entry) => entry,
+                    None => return Ok(None),
+                }
+            };
+            let Value::Hash(fields) = &mut entry.value else {
+                return Err(WrongType);
+            };
+
+            fields.retain(|_, field| !field.is_expired());
+            operation(fields)
+        };
+
+        self.store.remove_if(
+            key,
+            |_, entry| matches!(&entry.value, Value::Hash(fields) if fields.is_empty()),
+        );
+        Ok(Some(result))
+    }
+
+    /// Sets a field of the hash stored at a key, discarding any expiry the field had.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key of the hash, which is created if it does not exist.
+    /// - `field`: The field to set.
+    /// - `value`: The value to associate with the field.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field is new, `false` if an existing field was updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hset(&self, key: &str, field: &str, value: &str) -> Result<bool, WrongType> {
+        self.with_hash(key, true, |fields| {
+            fields
+                .insert(field.into(), Entry::new(value.into()))
+                .is_none()
+        })
+        .map(Option::unwrap_or_default)
     }
-}
 
-/// The global instance of `KvStore`.
-pub static KV_STORE: Lazy<KvStore> = Lazy::new(KvStore::new);
+    /// Retrieves the value of a field of the hash stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The value of the field, or `None` if the key or field does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hget(&self, key: &str, field: &str) -> Result<Option<String>, WrongType> {
+        self.with_hash(key, false, |fields| {
+            fields.get(field).map(|field| field.value.clone())
+        })
+        .map(Option::flatten)
+    }
+
+    /// Retrieves the values of several fields of the hash stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The value of each field in the order given, `None` for fields that do not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hmget(&self, key: &str, fields: &[String]) -> Result<Vec<Option<String>>, WrongType> {
+        self.with_hash(key, false, |hash| {
+            fields
+                .iter()
+                .map(|field| hash.get(field).map(|entry| entry.value.clone()))
+                .collect()
+        })
+        .map(|values| values.unwrap_or_else(|| vec![None; fields.len()]))
+    }
+
+    /// Retrieves all fields and values of the hash stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The field-value pairs of the hash, empty if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hgetall(&self, key: &str) -> Result<Vec<(String, String)>, WrongType> {
+        self.with_hash(key, false, |fields| {
+            fields
+                .iter()
+                .map(|(field, entry)| (field.clone(), entry.value.clone()))
+                .collect()
+        })
+        .map(Option::unwrap_or_default)
+    }
+
+    /// Checks if a field exists in the hash stored at a key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hexists(&self, key: &str, field: &str) -> Result<bool, WrongType> {
+        self.with_hash(key, false, |fields| fields.contains_key(field))
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Retrieves the number of fields of the hash stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The number of fields, `0` if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hlen(&self, key: &str) -> Result<usize, WrongType> {
+        self.with_hash(key, false, |fields| fields.len())
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Retrieves the fields of the hash stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The fields of the hash, in the same order `hvals` returns their values,
+    /// empty if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hkeys(&self, key: &str) -> Result<Vec<String>, WrongType> {
+        self.with_hash(key, false, |fields| fields.keys().cloned().collect())
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Retrieves the values of the fields of the hash stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The values of the hash, in the same order `hkeys` returns their fields,
+    /// empty if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hvals(&self, key: &str) -> Result<Vec<String>, WrongType> {
+        self.with_hash(key, false, |fields| {
+            fields.values().map(|entry| entry.value.clone()).collect()
+        })
+        .map(Option::unwrap_or_default)
+    }
+
+    /// Removes a field from the hash stored at a key, removing the key if no fields remain.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field was removed, `false` if the key or field does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hdel(&self, key: &str, field: &str) -> Result<bool, WrongType> {
+        self.with_hash(key, false, |fields| fields.remove(field).is_some())
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Increments the integer stored in a field of the hash stored at a key,
+    /// keeping the field's expiry. A missing field is treated as holding `0`.
+    ///
+    /// # Returns
+    ///
+    /// The new value, or `None` if the stored value is not an integer
+    /// or the result would overflow.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hincrby(
+        &self,
+        key: &str,
+        field: &str,
+        increment: i64,
+    ) -> Result<Option<i64>, WrongType> {
+        self.with_hash(key, true, |fields| {
+            let current = fields
+                .get(field)
+                .map_or(Some(0), |entry| entry.value.parse().ok());
+            let value = current.and_then(|current: i64| current.checked_add(increment))?;
+            fields
+                .entry(field.into())
+                .or_insert_with(|| Entry::new(String::new()))
+                .value = value.to_string();
+            Some(value)
+        })
+        .map(Option::flatten)
+    }
+
+    /// Sets a field of the hash stored at a key to expire after the given duration.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the expiry was set, `false` if the key or field does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn hexpire(&self, key: &str, field: &str, ttl: Duration) -> Result<bool, WrongType> {
+        let Some(expires_at) = Instant::now().checked_add(ttl) else {
+            return Ok(false);
+        };
+
+        self.with_hash(key, false, |fields| {
+            fields
+                .get_mut(field)
+                .map(|field| field.expires_at = Some(expires_at))
+                .is_some()
+        })
+        .map(Option::unwrap_or_default)
+    }
+
+    /// Retrieves the remaining time to live of a field of the hash stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The `Expiry` of the field, or `None` if the key or field does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a hash.
+    pub fn httl(&self, key: &str, field: &str) -> Result<Option<Expiry>, WrongType> {
+        self.with_hash(key, false, |fields| {
+            fields.get(field).map(|field| match field.expires_at {
+                Some(expires_at) => {
+                    Expiry::In(expires_at.saturating_duration_since(Instant::now()))
+                }
+                None => Expiry::Persistent,
+            })
+        })
+        .map(Option::flatten)
+    }
+
+    /// Runs an operation on the members of the set stored at a key,
+    /// removing the key if the set is left empty.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key of the set.
+    /// - `create`: Whether to create an empty set if the key does not exist.
+    /// - `operation`: The operation to run on the members of the set.
+    ///
+    /// # Returns
+    ///
+    /// The result of the operation, or `None` if the key does not exist and `create` is `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a set.
+    fn with_set<R>(
+        &self,
+        key: &str,
+        create: bool,
+        operation: impl FnOnce(&mut Set) -> R,
+    ) -> Result<Option<R>, WrongType> {
+        let result = {
+            let mut entry = if create {
+                self.lookup_or_insert(key, Value::Set(Set::new()))
+            } else {
+                match self.lookup(key) {
+                    Some(entry) => entry,
+                    None => return Ok(None),
+                }
+            };
+            let Value::Set(members) = &mut entry.value else {
+                return Err(WrongType);
+            };
+
+            operation(members)
+        };
+
+        self.store.remove_if(
+            key,
+            |_, entry| matches!(&entry.value, Value::Set(members) if members.is_empty()),
+        );
+        Ok(Some(result))
+    }
+
+    /// Adds members to the set stored at a key.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key of the set, which is created if it does not exist.
+    /// - `members`: The members to add.
+    ///
+    /// # Returns
+    ///
+    /// The number of members that were not already in the set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a set.
+    pub fn sadd(&self, key: &str, members: &[String]) -> Result<usize, WrongType> {
+        self.with_set(key, true, |set| {
+            members
+                .iter()
+                .filter(|member| set.insert((*member).clone()))
+                .count()
+        })
+        .map(Option::unwrap_or_default)
+    }
+
+    /// Removes members from the set stored at a key, removing the key if no members remain.
+    ///
+    /// # Returns
+    ///
+    /// The number of members that were in the set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a set.
+    pub fn srem(&self, key: &str, members: &[String]) -> Result<usize, WrongType> {
+        self.with_set(key, false, |set| {
+            members.iter().filter(|member| set.remove(*member)).count()
+        })
+        .map(Option::unwrap_or_default)
+    }
+
+    /// Retrieves the members of the set stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The members of the set in sorted order, empty if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a set.
+    pub fn smembers(&self, key: &str) -> Result<Vec<String>, WrongType> {
+        self.with_set(key, false, |set| sorted_members(set.iter()))
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Checks if a member belongs to the set stored at a key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a set.
+    pub fn sismember(&self, key: &str, member: &str) -> Result<bool, WrongType> {
+        self.with_set(key, false, |set| set.contains(member))
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Retrieves the number of members of the set stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The cardinality of the set, `0` if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a set.
+    pub fn scard(&self, key: &str) -> Result<usize, WrongType> {
+        self.with_set(key, false, |set| set.len())
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Retrieves the sets stored at the given keys, treating missing keys as empty sets.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if any key holds a value other than a set.
+    fn sets(&self, keys: &[String]) -> Result<Vec<Set>, WrongType> {
+        keys.iter()
+            .map(|key| {
+                self.with_set(key, false, |set| set.clone())
+                    .map(Option::unwrap_or_default)
+            })
+            .collect()
+    }
+
+    /// Computes the intersection of the sets stored at the given keys.
+    ///
+    /// # Returns
+    ///
+    /// The members present in every set in sorted order,
+    /// empty if any key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if any key holds a value other than a set.
+    pub fn sinter(&self, keys: &[String]) -> Result<Vec<String>, WrongType> {
+        let sets = self.sets(keys)?;
+        let Some((first, rest)) = sets.split_first() else {
+            return Ok(vec![]);
+        };
+
+        Ok(sorted_members(first.iter().filter(|member| {
+            rest.iter().all(|set| set.contains(*member))
+        })))
+    }
+
+    /// Computes the union of the sets stored at the given keys.
+    ///
+    /// # Returns
+    ///
+    /// The members present in any set in sorted order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if any key holds a value other than a set.
+    pub fn sunion(&self, keys: &[String]) -> Result<Vec<String>, WrongType> {
+        let sets = self.sets(keys)?;
+        let union: Set = sets.iter().flatten().cloned().collect();
+        Ok(sorted_members(union.iter()))
+    }
+
+    /// Computes the difference between the set stored at the first key
+    /// and the sets stored at the remaining keys.
+    ///
+    /// # Returns
+    ///
+    /// The members of the first set that are in none of the others in sorted order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if any key holds a value other than a set.
+    pub fn sdiff(&self, keys: &[String]) -> Result<Vec<String>, WrongType> {
+        let sets = self.sets(keys)?;
+        let Some((first, rest)) = sets.split_first() else {
+            return Ok(vec![]);
+        };
+
+        Ok(sorted_members(first.iter().filter(|member| {
+            !rest.iter().any(|set| set.contains(*member))
+        })))
+    }
+
+    /// Replaces the value stored at a key with a set of the given members,
+    /// discarding any expiry the key had, or removes the key if there are no members.
+    ///
+    /// # Returns
+    ///
+    /// The number of members in the stored set.
+    pub fn store_set(&self, key: &str, members: Vec<String>) -> usize {
+        if members.is_empty() {
+            self.store.remove(key);
+            return 0;
+        }
+
+        let set: Set = members.into_iter().collect();
+        let len = set.len();
+        self.store.insert(key.into(), Entry::new(Value::Set(set)));
+        len
+    }
+
+    /// Retrieves the elements of the list or the members of the set stored at a key, for sorting.
+    ///
+    /// # Returns
+    ///
+    /// The elements of a list from head to tail, or the members of a set in no particular order,
+    /// empty if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a list or set.
+    pub fn elements(&self, key: &str) -> Result<Vec<String>, WrongType> {
+        let Some(entry) = self.lookup(key) else {
+            return Ok(vec![]);
+        };
+
+        match &entry.value {
+            Value::List(elements) => Ok(elements.iter().cloned().collect()),
+            Value::Set(members) => Ok(members.iter().cloned().collect()),
+            Value::String(_) | Value::Hash(_) => Err(WrongType),
+        }
+    }
+
+    /// Runs an operation on the elements of the list stored at a key,
+    /// removing the key if the list is left empty.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key of the list.
+    /// - `create`: Whether to create an empty list if the key does not exist.
+    /// - `operation`: The operation to run on the elements of the list.
+    ///
+    /// # Returns
+    ///
+    /// The result of the operation, or `None` if the key does not exist and `create` is `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a list.
+    fn with_list<R>(
+        &self,
+        key: &str,
+        create: bool,
+        operation: impl FnOnce(&mut List) -> R,
+    ) -> Result<Option<R>, WrongType> {
+        let result = {
+            let mut entry = if create {
+                self.lookup_or_insert(key, Value::List(List::new()))
+            } else {
+                match self.lookup(key) {
+                    Some(entry) => entry,
+                    None => return Ok(None),
+                }
+            };
+            let Value::List(elements) = &mut entry.value else {
+                return Err(WrongType);
+            };
+
+            operation(elements)
+        };
+
+        self.store.remove_if(
+            key,
+            |_, entry| matches!(&entry.value, Value::List(elements) if elements.is_empty()),
+        );
+        Ok(Some(result))
+    }
+
+    /// Pushes elements onto the head or the tail of the list stored at a key, one at a time,
+    /// creating the list if the key does not exist.
+    ///
+    /// # Returns
+    ///
+    /// The length of the list afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a list.
+    pub fn push(&self, key: &str, elements: &[String], tail: bool) -> Result<usize, WrongType> {
+        self.with_list(key, true, |list| {
+            for element in elements {
+                if tail {
+                    list.push_back(element.clone());
+                } else {
+                    list.push_front(element.clone());
+                }
+            }
+            list.len()
+        })
+        .map(Option::unwrap_or_default)
+    }
+
+    /// Pops up to `count` elements from the head or the tail of the list stored at a key,
+    /// removing the key once the list is empty.
+    ///
+    /// # Returns
+    ///
+    /// The popped elements in the order they were popped, or `None` if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a list.
+    pub fn pop(
+        &self,
+        key: &str,
+        count: usize,
+        tail: bool,
+    ) -> Result<Option<Vec<String>>, WrongType> {
+        self.with_list(key, false, |list| {
+            let count = count.min(list.len());
+            if tail {
+                list.drain(list.len() - count..).rev().collect()
+            } else {
+                list.drain(..count).collect()
+            }
+        })
+    }
+
+    /// Retrieves the number of elements of the list stored at a key.
+    ///
+    /// # Returns
+    ///
+    /// The length of the list, `0` if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a list.
+    pub fn llen(&self, key: &str) -> Result<usize, WrongType> {
+        self.with_list(key, false, |list| list.len())
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Retrieves the element at an index of the list stored at a key.
+    ///
+    /// A negative index counts back from the tail, so `-1` is the last element.
+    ///
+    /// # Returns
+    ///
+    /// The element, or `None` if the index is out of range or the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a list.
+    pub fn lindex(&self, key: &str, index: i64) -> Result<Option<String>, WrongType> {
+        self.with_list(key, false, |list| {
+            list_index(list.len(), index).map(|index| list[index].clone())
+        })
+        .map(Option::flatten)
+    }
+
+    /// Overwrites the element at an index of the list stored at a key.
+    ///
+    /// A negative index counts back from the tail, so `-1` is the last element.
+    ///
+    /// # Returns
+    ///
+    /// Whether the index is in range, or `None` if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a list.
+    pub fn lset(&self, key: &str, index: i64, value: &str) -> Result<Option<bool>, WrongType> {
+        self.with_list(key, false, |list| {
+            list_index(list.len(), index).is_some_and(|index| {
+                list[index] = value.into();
+                true
+            })
+        })
+    }
+
+    /// Trims the list stored at a key to the elements within a range of indexes, both inclusive,
+    /// removing the key if the range is empty.
+    ///
+    /// Negative indexes count back from the tail, and out-of-range indexes are clamped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a list.
+    pub fn ltrim(&self, key: &str, start: i64, stop: i64) -> Result<(), WrongType> {
+        self.with_list(key, false, |list| {
+            match index_range(list.len(), start, stop) {
+                Some(range) => {
+                    list.truncate(range.end() + 1);
+                    list.drain(..range.start());
+                }
+                None => list.clear(),
+            }
+        })
+        .map(|_| ())
+    }
+
+    /// Removes occurrences of an element from the list stored at a key,
+    /// removing the key if no elements remain.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key of the list.
+    /// - `count`: The most occurrences to remove, starting from the head if positive
+    ///   or from the tail if negative, with `0` removing every occurrence.
+    /// - `element`: The element to remove.
+    ///
+    /// # Returns
+    ///
+    /// The number of occurrences removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WrongType` if the key holds a value other than a list.
+    pub fn lrem(&self, key: &str, count: i64, element: &str) -> Result<usize, WrongType> {
+        self.with_list(key, false, |list| {
+            let occurrences = list.iter().filter(|value| *value == element).count();
+            let removed = match usize::try_from(count.unsigned_abs()) {
+                Ok(0) | Err(_) => occurrences,
+                Ok(limit) => limit.min(occurrences),
+            };
+            // removing from the tail keeps the occurrences nearest the head
+            let mut kept = if count < 0 { occurrences - removed } else { 0 };
+            let mut left = removed;
+            list.retain(|value| {
+                if value != element || left == 0 {
+                    true
+                } else if kept > 0 {
+                    kept -= 1;
+                    true
+                } else {
+                    left -= 1;
+                    false
+                }
+            });
+            removed
+        })
+        .map(Option::unwrap_or_default)
+    }
+}
+
+/// Resolves a range of offsets, both inclusive, into a sequence of length `len`,
+/// such as the bytes of a string or the elements of a list.
+/// Negative offsets count from the end of the sequence, and out-of-range offsets are clamped.
+///
+/// # Returns
+///
+/// The range of positions, or `None` if it is empty.
+fn index_range(len: usize, start: i64, end: i64) -> Option<RangeInclusive<usize>> {
+    let len = i64::try_from(len).unwrap_or(i64::MAX);
+    let start = if start < 0 { start + len } else { start }.max(0);
+    let end = if end < 0 { end + len } else { end }.min(len - 1);
+    if start > end {
+        return None;
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to the length
+    Some(start as usize..=end as usize)
+}
+
+/// Resolves an index into a list of length `len`, where negative indexes count from the end.
+///
+/// # Returns
+///
+/// The position of the element, or `None` if the index is out of range.
+fn list_index(len: usize, index: i64) -> Option<usize> {
+    let len = i64::try_from(len).unwrap_or(i64::MAX);
+    let index = if index < 0 { index + len } else { index };
+
+    usize::try_from(index).ok().filter(|_| index < len)
+}
+
+/// Estimates the number of bytes a key and its entry take up, including the entry's own fields.
+fn entry_usage(key: &str, entry: &Entry<Value>) -> usize {
+    size_of::<String>() + key.len() + size_of::<Entry<Value>>() + entry.value.memory_usage()
+}
+
+/// Collects set members into a sorted list, so that replies do not depend on hashing order.
+fn sorted_members<'a>(members: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut members: Vec<String> = members.cloned().collect();
+    members.sort_unstable();
+    members
+}
+
+/// Appends a length to a snapshot.
+fn write_len(snapshot: &mut Vec<u8>, len: usize) {
+    snapshot.extend_from_slice(&u64::try_from(len).unwrap_or(u64::MAX).to_le_bytes());
+}
+
+/// Appends a length-prefixed byte string to a snapshot.
+fn write_bytes(snapshot: &mut Vec<u8>, bytes: &[u8]) {
+    write_len(snapshot, bytes.len());
+    snapshot.extend_from_slice(bytes);
+}
+
+/// Appends an expiry to a snapshot, as the remaining milliseconds plus one, or zero for none.
+fn write_expiry(snapshot: &mut Vec<u8>, expires_at: Option<Instant>) {
+    let millis = expires_at.map_or(0, |expires_at| {
+        let ttl = expires_at.saturating_duration_since(Instant::now());
+        u64::try_from(ttl.as_millis())
+            .unwrap_or(u64::MAX)
+            .saturating_add(1)
+    });
+    snapshot.extend_from_slice(&millis.to_le_bytes());
+}
+
+/// Appends a key to a snapshot along with its expiry and value, unless it has expired.
+fn write_entry(snapshot: &mut Vec<u8>, key: &str, entry: &Entry<Value>) {
+    if entry.is_expired() {
+        return;
+    }
+
+    write_bytes(snapshot, key.as_bytes());
+    write_expiry(snapshot, entry.expires_at);
+    write_value(snapshot, &entry.value);
+}
+
+/// Appends a value to a snapshot, as its kind followed by its contents.
+/// Expired hash fields are left out.
+fn write_value(snapshot: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::String(value) => {
+            snapshot.push(0);
+            write_bytes(snapshot, value);
+        }
+        Value::Hash(fields) => {
+            snapshot.push(1);
+            let live = fields.iter().filter(|(_, field)| !field.is_expired());
+            write_len(snapshot, live.clone().count());
+            for (field, entry) in live {
+                write_bytes(snapshot, field.as_bytes());
+                write_expiry(snapshot, entry.expires_at);
+                write_bytes(snapshot, entry.value.as_bytes());
+            }
+        }
+        Value::Set(members) => {
+            snapshot.push(2);
+            write_len(snapshot, members.len());
+            for member in members {
+                write_bytes(snapshot, member.as_bytes());
+            }
+        }
+        Value::List(elements) => {
+            snapshot.push(3);
+            write_len(snapshot, elements.len());
+            for element in elements {
+                write_bytes(snapshot, element.as_bytes());
+            }
+        }
+    }
+}
+
+/// Reads a length from the front of a snapshot.
+fn read_len(snapshot: &mut &[u8]) -> Option<usize> {
+    let (len, rest) = snapshot.split_first_chunk::<8>()?;
+    *snapshot = rest;
+    usize::try_from(u64::from_le_bytes(*len)).ok()
+}
+
+/// Reads length-prefixed bytes from the front of a snapshot.
+fn read_bytes(snapshot: &mut &[u8]) -> Option<Vec<u8>> {
+    let len = read_len(snapshot)?;
+    let bytes = snapshot.get(..len)?;
+    *snapshot = &snapshot[len..];
+    Some(bytes.to_vec())
+}
+
+/// Reads a length-prefixed string from the front of a snapshot.
+fn read_string(snapshot: &mut &[u8]) -> Option<String> {
+    String::from_utf8(read_bytes(snapshot)?).ok()
+}
+
+/// Converts an expiry read from a snapshot back into an instant.
+fn expiry_from(millis: usize) -> Option<Instant> {
+    let millis = u64::try_from(millis.checked_sub(1)?).ok()?;
+    Instant::now().checked_add(Duration::from_millis(millis))
+}
+
+/// Reads a key and its entry from the front of a snapshot.
+fn read_entry(snapshot: &mut &[u8]) -> Option<(String, Entry<Value>)> {
+    let key = read_string(snapshot)?;
+    let expires_at = expiry_from(read_len(snapshot)?);
+    let value = read_value(snapshot)?;
+
+    Some((
+        key,
+        Entry {
+            expires_at,
+            ..Entry::new(value)
+        },
+    ))
+}
+
+/// Reads a value written by `write_value` from the front of a snapshot.
+fn read_value(snapshot: &mut &[u8]) -> Option<Value> {
+    let (&kind, rest) = snapshot.split_first()?;
+    *snapshot = rest;
+
+    let value = match kind {
+        0 => Value::String(read_bytes(snapshot)?),
+        1 => {
+            let mut fields = Hash::new();
+            for _ in 0..read_len(snapshot)? {
+                let field = read_string(snapshot)?;
+                let expires_at = expiry_from(read_len(snapshot)?);
+                let value = read_string(snapshot)?;
+                fields.insert(
+                    field,
+                    Entry {
+                        expires_at,
+                        ..Entry::new(value)
+                    },
+                );
+            }
+            Value::Hash(fields)
+        }
+        2 => {
+            let mut members = Set::new();
+            for _ in 0..read_len(snapshot)? {
+                members.insert(read_string(snapshot)?);
+            }
+            Value::Set(members)
+        }
+        3 => {
+            let mut elements = List::new();
+            for _ in 0..read_len(snapshot)? {
+                elements.push_back(read_string(snapshot)?);
+            }
+            Value::List(elements)
+        }
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// The number of databases, numbered from `0`, like Redis's default `databases 16`.
+pub const DATABASE_COUNT: usize = 16;
+
+/// Checks that a database index given by a client is in range.
+///
+/// # Returns
+///
+/// The index, or `None` if it is negative or there are not that many databases.
+pub fn database_index(index: i64) -> Option<usize> {
+    usize::try_from(index)
+        .ok()
+        .filter(|index| *index < DATABASE_COUNT)
+}
+
+/// Represents the databases of the server, each of which is a `KvStore` of its own.
+///
+/// The stores themselves never move. Instead, each database index refers to a store,
+/// so that swapping two databases only swaps which stores their indexes refer to.
+pub struct Databases {
+    stores: Vec<KvStore>,
+    /// The store each database index refers to.
+    order: RwLock<Vec<usize>>,
+}
+
+impl Databases {
+    /// Creates `DATABASE_COUNT` empty databases.
+    pub fn new() -> Self {
+        Self {
+            stores: (0..DATABASE_COUNT).map(|_| KvStore::new()).collect(),
+            order: RwLock::new((0..DATABASE_COUNT).collect()),
+        }
+    }
+
+    /// Retrieves the store of a database.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not below `DATABASE_COUNT`.
+    pub fn get(&self, index: usize) -> &KvStore {
+        let order = self.order.read().unwrap_or_else(PoisonError::into_inner);
+        &self.stores[order[index]]
+    }
+
+    /// Swaps the contents of two databases at once, as seen by every client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is not below `DATABASE_COUNT`.
+    pub fn swap(&self, index1: usize, index2: usize) {
+        self.order
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .swap(index1, index2);
+    }
+
+    /// Iterates over the stores of every database, in no particular order.
+    pub fn iter(&self) -> std::slice::Iter<'_, KvStore> {
+        self.stores.iter()
+    }
+}
+
+/// The databases of the server.
+pub static DATABASES: LazyLock<Databases> = LazyLock::new(Databases::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_databases_swap() {
+        let databases = Databases::new();
+        databases.get(0).set("first", "1");
+        databases.get(1).set("second", "2");
+
+        databases.swap(0, 1);
+        assert_eq!(databases.get(0).get("second"), Ok(Some("2".into())));
+        assert!(!databases.get(0).exists("first"));
+        assert_eq!(databases.get(1).get("first"), Ok(Some("1".into())));
+        assert!(!databases.get(1).exists("second"));
+    }
+
+    #[test]
+    fn test_database_index() {
+        assert_eq!(database_index(15), Some(15));
+        assert_eq!(database_index(16), None);
+        assert_eq!(database_index(-1), None);
+    }
+
+    #[test]
+    fn test_move_key() {
+        let (source, target) = (KvStore::new(), KvStore::new());
+        source.set("moved", "1");
+        source.set("held", "2");
+        source.expire("held", Duration::from_secs(100));
+        target.set("held", "3");
+
+        assert!(source.move_key("moved", &target));
+        assert_eq!(target.get("moved"), Ok(Some("1".into())));
+        assert!(!source.exists("moved"));
+
+        // the target's key is kept, and the source's is put back along with its expiry
+        assert!(!source.move_key("held", &target));
+        assert_eq!(target.get("held"), Ok(Some("3".into())));
+        assert_eq!(source.get("held"), Ok(Some("2".into())));
+        assert!(matches!(source.ttl("held"), Some(Expiry::In(_))));
+        assert!(!source.move_key("missing", &target));
+    }
+
+    #[test]
+    fn test_flush() {
+        let store = KvStore::new();
+        store.set("a", "1");
+        store.hset("b", "field", "2").unwrap();
+
+        store.flush();
+        assert_eq!(store.len(), 0);
+    }
+
+    #[test]
+    fn test_reload_round_trip() {
+        let store = KvStore::new();
+        store.set("string", "value");
+        store.expire("string", Duration::from_secs(100));
+        store.hset("hash", "a", "1").unwrap();
+        store.hset("hash", "b", "2").unwrap();
+        store
+            .hexpire("hash", "b", Duration::from_secs(100))
+            .unwrap();
+        store.sadd("set", &["a".into(), "b".into()]).unwrap();
+        store.push("list", &["a".into(), "b".into()], true).unwrap();
+        store.set("expired", "value");
+        store.expire("expired", Duration::ZERO);
+
+        store.reload();
+        assert_eq!(store.len(), 4);
+        assert_eq!(store.get("string"), Ok(Some("value".into())));
+        assert!(
+            matches!(store.ttl("string"), Some(Expiry::In(ttl)) if ttl > Duration::from_secs(99))
+        );
+        assert_eq!(store.hget("hash", "a"), Ok(Some("1".into())));
+        assert_eq!(store.httl("hash", "a"), Ok(Some(Expiry::Persistent)));
+        assert!(matches!(store.httl("hash", "b"), Ok(Some(Expiry::In(_)))));
+        assert_eq!(store.scard("set"), Ok(2));
+        assert_eq!(
+            store.pop("list", 2, false),
+            Ok(Some(vec!["a".into(), "b".into()]))
+        );
+    }
+
+    #[test]
+    fn test_reload_concurrent() {
+        let store = KvStore::new();
+        for i in 0..100 {
+            store.set(&format!("key:{i}"), "value");
+        }
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    store.reload();
+                }
+            });
+            // neither are existing keys ever missing, nor are writes made meanwhile lost
+            for i in 0..1_000 {
+                assert!(store.exists(&format!("key:{}", i % 100)));
+                store.set(&format!("new:{i}"), "value");
+            }
+        });
+        assert_eq!(store.len(), 1_100);
+    }
+
+    #[test]
+    fn test_sample_expired() {
+        let store = KvStore::new();
+        store.set("persistent", "1");
+        store.set("long", "2");
+        store.expire("long", Duration::from_secs(100));
+        for i in 0..10 {
+            store.set(&format!("short:{i}"), "3");
+            store.expire(&format!("short:{i}"), Duration::from_millis(1));
+        }
+        std::thread::sleep(Duration::from_millis(10));
+
+        let mut removed = 0;
+        while removed < 10 {
+            removed += store.sample_expired(4);
+        }
+        assert_eq!(store.sample_expired(20), 0);
+        assert_eq!(store.len(), 2);
+        assert!(store.exists("long"));
+        assert_eq!(store.expired_keys(), 10);
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let store = KvStore::new();
+        assert_eq!(store.memory_usage("string"), None);
+        assert_eq!(store.total_memory(), 0);
+
+        store.set("string", "a");
+        let short = store.memory_usage("string").unwrap();
+        store.set("string", &"a".repeat(100));
+        assert_eq!(store.memory_usage("string"), Some(short + 99));
+
+        store.push("list", &["a".into()], true).unwrap();
+        let list = store.memory_usage("list").unwrap();
+        store.push("list", &["b".into()], true).unwrap();
+        assert!(store.memory_usage("list").unwrap() > list);
+
+        store.hset("hash", "field", "value").unwrap();
+        store.sadd("set", &["member".into()]).unwrap();
+        assert_eq!(
+            store.total_memory(),
+            ["string", "list", "hash", "set"]
+                .iter()
+                .map(|key| store.memory_usage(key).unwrap())
+                .sum()
+        );
+    }
+
+    #[test]
+    fn test_idle_time() {
+        let store = KvStore::new();
+        store.set("key", "value");
+        std::thread::sleep(Duration::from_millis(20));
+
+        let idle = store.idle_time("key").unwrap();
+        assert!(idle >= Duration::from_millis(20));
+        // inspecting the idle time is not an access
+        assert!(store.idle_time("key").unwrap() >= idle);
+
+        store.get("key").unwrap();
+        assert!(store.idle_time("key").unwrap() < idle);
+        assert_eq!(store.idle_time("missing"), None);
+    }
+
+    #[test]
+    fn test_idle_time_collections() {
+        let store = KvStore::new();
+        store.hset("hash", "field", "value").unwrap();
+        store.sadd("set", &["member".into()]).unwrap();
+        store.push("list", &["element".into()], true).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+
+        // every command on a value counts as an access, not only GET
+        store.hget("hash", "field").unwrap();
+        store.smembers("set").unwrap();
+        store.lindex("list", 0).unwrap();
+        for key in ["hash", "set", "list"] {
+            assert!(store.idle_time(key).unwrap() < Duration::from_millis(20));
+        }
+    }
+}
@@ -1,36 +1,137 @@
 #![warn(clippy::all, clippy::pedantic, future_incompatible)]
 
-use std::io;
+use socket2::{SockRef, TcpKeepalive};
+use std::{collections::HashMap, env, future, io, sync::Arc, time::Duration};
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
     net::{TcpListener, TcpStream},
-    spawn,
+    select, spawn,
+    sync::{mpsc, Semaphore},
+    task,
+    time::{self, Instant, Interval},
 };
-use tracing::error;
+use tracing::{debug, debug_span, error, level_filters::LevelFilter, warn, Level};
+use tracing_subscriber::EnvFilter;
 
 mod command;
-use command::types::Command;
+use command::types::{Command, ExecuteInSession};
+
+mod config;
+use config::{ServerConfig, SERVER_CONFIG};
+
+mod glob;
 
 mod kvstore;
-use kvstore::KV_STORE;
+use kvstore::{KvStore, DATABASES};
+
+mod pubsub;
 
 mod request;
-use request::types::Request;
+use request::types::{Parsed, Request};
 
 mod response;
-use response::types::Response;
+use response::{encode::encode, types::Response};
+
+mod session;
+use session::Session;
 
 /// The main entry point of the Redis server.
 #[tokio::main]
 async fn main() -> Result<(), io::Error> {
-    KV_STORE.len(); // TODO: materialize(?) singleton
+    let config = ServerConfig::from_args_and_env(
+        &env::args().skip(1).collect::<Vec<_>>(),
+        &env::vars().collect::<HashMap<_, _>>(),
+    )
+    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    tracing_subscriber::fmt()
+        .with_env_filter(log_filter(
+            config.loglevel,
+            env::var("RUST_LOG").ok().as_deref(),
+        ))
+        .init();
+
+    run(config).await
+}
+
+/// Builds the filter of what gets logged from the directives of `RUST_LOG`, if any,
+/// logging anything at `level` or above that they do not cover.
+fn log_filter(level: Level, rust_log: Option<&str>) -> EnvFilter {
+    EnvFilter::builder()
+        .with_default_directive(LevelFilter::from_level(level).into())
+        .parse_lossy(rust_log.unwrap_or_default())
+}
+
+/// Runs the server with the given configuration, accepting and serving clients.
+async fn run(config: ServerConfig) -> Result<(), io::Error> {
+    let listener = TcpListener::bind((config.bind, config.port)).await?;
+    for store in DATABASES.iter() {
+        spawn(expire_cycle(store, Duration::from_secs(1) / config.hz));
+    }
+    *SERVER_CONFIG
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = config.clone();
+
+    serve(listener, &config).await
+}
+
+/// The number of keys with an expiry sampled at a time by the active expiry cycle.
+const ACTIVE_EXPIRE_SAMPLE: usize = 20;
 
-    let listener = TcpListener::bind("127.0.0.1:6379").await?;
+/// Periodically samples keys with an expiry and removes those that have expired,
+/// like Redis's active expiry cycle, unless disabled with `DEBUG SET-ACTIVE-EXPIRE 0`.
+///
+/// As long as more than a quarter of a sample has expired, another sample is taken right away,
+/// since many more keys are then likely to have expired too.
+async fn expire_cycle(store: &'static KvStore, period: Duration) {
+    let mut interval = time::interval(period);
+    loop {
+        interval.tick().await;
+        while store.active_expire()
+            && store.sample_expired(ACTIVE_EXPIRE_SAMPLE) > ACTIVE_EXPIRE_SAMPLE / 4
+        {
+            task::yield_now().await;
+        }
+    }
+}
+
+/// Accepts and serves clients, with at most `maxclients` of them connected at once.
+///
+/// Beyond the limit, new connections are not accepted until a connected client leaves,
+/// so they wait in the listener's backlog.
+async fn serve(listener: TcpListener, config: &ServerConfig) -> Result<(), io::Error> {
+    let slots = Arc::new(Semaphore::new(config.maxclients));
+    let (heartbeat, output_buffer_limit, query_buffer_limit, timeout, request_timeout) = (
+        config.heartbeat,
+        config.output_buffer_limit,
+        config.query_buffer_limit,
+        config.timeout,
+        config.request_timeout,
+    );
 
     loop {
+        let Ok(slot) = Arc::clone(&slots).acquire_owned().await else {
+            return Ok(());
+        };
+
         match listener.accept().await {
-            Ok((stream, _)) => {
-                spawn(handle_client(stream));
+            Ok((stream, addr)) => {
+                debug!("accepted connection from {addr}");
+                if let Err(e) = configure_stream(&stream, config.tcp_nodelay, config.tcp_keepalive)
+                {
+                    error!("failed to configure connection from {addr}: {e:?}");
+                }
+                spawn(async move {
+                    handle_client(
+                        stream,
+                        heartbeat,
+                        output_buffer_limit,
+                        query_buffer_limit,
+                        timeout,
+                        request_timeout,
+                    )
+                    .await;
+                    drop(slot);
+                });
             }
             Err(e) => {
                 error!("failed to accept connection: {e:?}");
@@ -39,47 +140,946 @@ async fn main() -> Result<(), io::Error> {
     }
 }
 
-/// Handles a client connection by reading requests and sending responses.
-async fn handle_client(mut stream: TcpStream) {
+/// Sets the socket options of an accepted connection.
+///
+/// With `nodelay`, small replies are sent right away instead of being held back by Nagle's
+/// algorithm. With a `keepalive` time, the kernel probes a connection idle for that long,
+/// like Redis's `tcp-keepalive`, every third of that time after, so dead peers are noticed.
+fn configure_stream(
+    stream: &TcpStream,
+    nodelay: bool,
+    keepalive: Option<Duration>,
+) -> Result<(), io::Error> {
+    stream.set_nodelay(nodelay)?;
+    let socket = SockRef::from(stream);
+    match keepalive {
+        Some(time) => {
+            let keepalive = TcpKeepalive::new().with_time(time).with_interval(time / 3);
+            socket.set_tcp_keepalive(&keepalive)
+        }
+        None => socket.set_keepalive(false),
+    }
+}
+
+/// A bidirectional byte stream over which a client is served.
+///
+/// As `AsyncRead` requires, a zero-length read means the peer has closed the stream,
+/// so a stream that is still open but has nothing to read must stay pending instead.
+trait ClientStream: AsyncRead + AsyncWrite + Unpin {}
+
+impl ClientStream for TcpStream {}
+
+/// Handles a client connection by reading requests and sending responses,
+/// while forwarding any messages pushed to the client's session, such as pub/sub messages.
+///
+/// With a `heartbeat` period, a subscribed connection that has been idle for that long is sent
+/// a `pong` message, framed like the reply to `PING` in subscribe mode, to keep proxies and
+/// load balancers from dropping it. Other connections are never sent a heartbeat, since a client
+/// waiting on replies would take it for the reply to its next command; TCP keepalive keeps
+/// those alive instead.
+///
+/// At most `output_buffer_limit` pushed messages may be pending delivery;
+/// a client that falls further behind is disconnected, even while a write to it is stalled.
+///
+/// At most `query_buffer_limit` bytes of incomplete requests may be buffered,
+/// so that a client cannot make the server hold on to ever more of a request it never finishes.
+///
+/// With a `timeout`, a client that sends nothing for that long is disconnected,
+/// unless it is subscribed to channels or patterns, since subscribers only ever listen.
+///
+/// With a `request_timeout`, a client is disconnected once a request it has started sending
+/// is still incomplete after that long, or once a write to it takes that long,
+/// so that a client trickling in requests or not reading replies cannot hold on to a connection.
+async fn handle_client(
+    mut stream: impl ClientStream,
+    heartbeat: Option<Duration>,
+    output_buffer_limit: usize,
+    query_buffer_limit: usize,
+    timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+) {
     let mut buffer = [0; 1_024];
+    let mut pending = Vec::new();
+    let mut responses = Vec::new();
+    let (sender, mut receiver) = mpsc::channel(output_buffer_limit);
+    let mut session = Session::new(sender);
+    let killed = session.killed();
+    let mut heartbeat = heartbeat.map(|period| time::interval_at(Instant::now() + period, period));
+    let mut idle = timeout.map(|period| time::interval_at(Instant::now() + period, period));
+    // when the request being received has to be complete by
+    let mut request_deadline = None;
 
-    loop {
-        match stream.read(&mut buffer).await {
-            Ok(buf_len) => {
-                if buf_len == 0 {
-                    break;
-                }
+    let conversation = async {
+        loop {
+            select! {
+                read = stream.read(&mut buffer) => match read {
+                    Ok(buf_len) => {
+                        if buf_len == 0 {
+                            break;
+                        }
+
+                        if let Some(heartbeat) = heartbeat.as_mut() {
+                            heartbeat.reset();
+                        }
+                        if let Some(idle) = idle.as_mut() {
+                            idle.reset();
+                        }
 
-                let response = process(&buffer);
-                if let Err(e) = stream.write_all(response.as_bytes()).await {
-                    error!("failed writing to stream: {e:?}");
+                        let started = pending.is_empty();
+                        pending.extend_from_slice(&buffer[..buf_len]);
+                        let buffered = pending.len();
+                        process(&mut pending, &mut session, &mut responses);
+                        if pending.len() > query_buffer_limit {
+                            warn!("closing connection: query buffer past {query_buffer_limit} bytes");
+                            break;
+                        }
+                        // a partial request left over after complete ones is a new request
+                        if pending.is_empty() {
+                            request_deadline = None;
+                        } else if started || pending.len() < buffered {
+                            request_deadline =
+                                request_timeout.map(|limit| Instant::now() + limit);
+                        }
+                        // sleep here rather than in the command, so other connections are served
+                        let delay = session.take_delay();
+                        if !delay.is_zero() {
+                            time::sleep(delay).await;
+                        }
+                        let written = write_within(&mut stream, &responses, request_timeout).await;
+                        responses.clear();
+                        if let Err(e) = written {
+                            log_write_error(&e);
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        if e.raw_os_error() != Some(54) {
+                            // ignore connection reset by peer
+                            error!("failed reading from stream: {e:?}");
+                        }
+                        break;
+                    }
+                },
+                Some(message) = receiver.recv() => {
+                    let mut encoded = Vec::new();
+                    encode(&message, &mut encoded);
+                    if let Err(e) = write_within(&mut stream, &encoded, request_timeout).await {
+                        log_write_error(&e);
+                        break;
+                    }
+                }
+                () = tick(heartbeat.as_mut()) => {
+                    if session.subscription_count() == 0 {
+                        continue;
+                    }
+                    let pong = Response::Array(vec![Response::bs("pong"), Response::bs("")]);
+                    let pong = pong.to_string();
+                    if let Err(e) = write_within(&mut stream, pong.as_bytes(), request_timeout).await {
+                        log_write_error(&e);
+                        break;
+                    }
+                }
+                () = until(request_deadline) => {
+                    warn!("closing connection: request incomplete after {request_timeout:?}");
                     break;
                 }
-            }
-            Err(e) => {
-                if e.raw_os_error() != Some(54) {
-                    // ignore connection reset by peer
-                    error!("failed reading from stream: {e:?}");
+                () = tick(idle.as_mut()) => {
+                    if session.subscription_count() == 0 {
+                        debug!("closing idle connection");
+                        break;
+                    }
                 }
+            }
+        }
+    };
+
+    select! {
+        () = conversation => {}
+        () = killed.notified() => {}
+    }
+}
+
+/// Waits for the next heartbeat, or forever if the heartbeat is off.
+async fn tick(heartbeat: Option<&mut Interval>) {
+    match heartbeat {
+        Some(heartbeat) => {
+            heartbeat.tick().await;
+        }
+        None => future::pending().await,
+    }
+}
+
+/// Waits until a deadline, or forever if there is none.
+async fn until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => time::sleep_until(deadline).await,
+        None => future::pending().await,
+    }
+}
+
+/// Writes all of `data` to a client, failing with `TimedOut` if that takes longer than `limit`.
+async fn write_within(
+    stream: &mut impl ClientStream,
+    data: &[u8],
+    limit: Option<Duration>,
+) -> Result<(), io::Error> {
+    match limit {
+        Some(limit) => time::timeout(limit, stream.write_all(data))
+            .await
+            .unwrap_or_else(|_| Err(io::ErrorKind::TimedOut.into())),
+        None => stream.write_all(data).await,
+    }
+}
+
+/// Logs a failed write to a client, as a warning if the client stopped reading for too long.
+fn log_write_error(e: &io::Error) {
+    if e.kind() == io::ErrorKind::TimedOut {
+        warn!("closing connection: write stalled past the request timeout");
+    } else {
+        error!("failed writing to stream: {e:?}");
+    }
+}
+
+/// Processes the complete requests buffered for a client within its session,
+/// and appends the corresponding responses to `responses`.
+///
+/// The caller owns the response buffer, so that it can be reused from one batch to the next
+/// instead of being allocated anew for every read.
+///
+/// Processed bytes are drained from the buffer, leaving any partial request to be completed
+/// by later reads. A malformed request discards the whole buffer.
+///
+/// Each command runs in a debug-level `command` span naming it along with its number of arguments,
+/// and reports how long it took to execute. Argument values are never logged, since they may hold
+/// secrets such as passwords.
+fn process(pending: &mut Vec<u8>, session: &mut Session, responses: &mut Vec<u8>) {
+    loop {
+        let request = match Request::parse(pending) {
+            Ok(Parsed::Complete(request, consumed)) => {
+                pending.drain(..consumed);
+                request
+            }
+            Ok(Parsed::Incomplete) => break,
+            Err(error) => {
+                pending.clear();
+                encode(&Response::from(error), responses);
                 break;
             }
+        };
+
+        let signatures: Vec<(String, usize)> = request
+            .commands()
+            .iter()
+            .map(|cmd| (cmd[0].to_lowercase(), cmd.len() - 1))
+            .collect();
+
+        let commands = Vec::<Result<Command, Response>>::from(request);
+        for (command, (name, args)) in commands.into_iter().zip(signatures) {
+            let _span = debug_span!("command", name, args).entered();
+            match command {
+                Ok(command) => {
+                    let started = time::Instant::now();
+                    command.execute_into(session, responses);
+                    debug!(elapsed_us = started.elapsed().as_micros(), "executed");
+                }
+                Err(error) => {
+                    // a command that cannot even be queued dooms the whole transaction
+                    session.abort_transaction();
+                    encode(&error, responses);
+                }
+            }
         }
     }
 }
 
-/// Processes a request and returns the corresponding response.
-fn process(request_buf: &[u8]) -> String {
-    Request::try_from(request_buf)
-        .map_err(Response::from)
-        .and_then(Vec::<Command>::try_from)
-        .map_or_else(
-            |error| error.to_string(),
-            |commands| {
-                commands
-                    .into_iter()
-                    .map(command::types::Execute::execute)
-                    .map(String::from)
-                    .collect()
-            },
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pubsub::PUBSUB;
+    use std::{
+        collections::VecDeque,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use tokio::io::{duplex, DuplexStream, ReadBuf};
+    use tracing_test::traced_test;
+
+    /// A scripted stream yielding one chunk per read, where an empty chunk is the end of the stream,
+    /// and which stays open with nothing to read once the script runs out.
+    struct MockStream {
+        reads: VecDeque<Vec<u8>>,
+        written: Vec<u8>,
+    }
+
+    impl MockStream {
+        fn new(reads: &[&[u8]]) -> Self {
+            Self {
+                reads: reads.iter().map(|chunk| chunk.to_vec()).collect(),
+                written: Vec::new(),
+            }
+        }
+    }
+
+    impl AsyncRead for MockStream {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            let Some(chunk) = self.reads.pop_front() else {
+                return Poll::Pending;
+            };
+            buf.put_slice(&chunk);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl AsyncWrite for MockStream {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.written.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl ClientStream for &mut MockStream {}
+
+    #[tokio::test]
+    async fn test_handle_client_stays_open_while_pending() {
+        let mut stream = MockStream::new(&[b"PING\r\n"]);
+        let served = time::timeout(
+            Duration::from_millis(100),
+            handle_client(&mut stream, None, 16, 1_024 * 1_024, None, None),
         )
+        .await;
+        assert!(served.is_err());
+        assert_eq!(stream.written, b"+PONG\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_zero_read_as_eof() {
+        let mut stream = MockStream::new(&[b"", b"PING\r\n"]);
+        handle_client(&mut stream, None, 16, 1_024 * 1_024, None, None).await;
+        assert!(stream.written.is_empty());
+    }
+
+    impl ClientStream for DuplexStream {}
+
+    /// Reads whatever the server sends to a client within `wait`,
+    /// after the client sends `request`, which may be empty.
+    async fn read_idle(heartbeat: Option<Duration>, request: &[u8], wait: Duration) -> Vec<u8> {
+        let (mut client, server) = duplex(1_024);
+        let handle = spawn(handle_client(
+            server,
+            heartbeat,
+            16,
+            1_024 * 1_024,
+            None,
+            None,
+        ));
+
+        client.write_all(request).await.unwrap();
+        time::sleep(wait).await;
+        handle.abort();
+        let _ = handle.await;
+
+        let mut received = Vec::new();
+        client.read_to_end(&mut received).await.unwrap();
+        received
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_heartbeat_on() {
+        let received = read_idle(
+            Some(Duration::from_millis(40)),
+            b"SUBSCRIBE heartbeat:channel\r\n",
+            Duration::from_millis(150),
+        )
+        .await;
+        let confirmation = b"*3\r\n$9\r\nsubscribe\r\n$17\r\nheartbeat:channel\r\n:1\r\n";
+        let pong = b"*2\r\n$4\r\npong\r\n$0\r\n\r\n";
+        assert!(received.starts_with(&[&confirmation[..], pong, pong].concat()));
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_heartbeat_skips_request_reply() {
+        let received = read_idle(
+            Some(Duration::from_millis(40)),
+            b"",
+            Duration::from_millis(150),
+        )
+        .await;
+        assert!(received.is_empty());
+
+        // replies stay paired with requests however long the client idles between them
+        let (mut client, server) = duplex(1_024);
+        let handle = spawn(handle_client(
+            server,
+            Some(Duration::from_millis(20)),
+            16,
+            1_024 * 1_024,
+            None,
+            None,
+        ));
+        let mut reply = [0; 6];
+        for message in ["one", "two", "six"] {
+            time::sleep(Duration::from_millis(70)).await;
+            client
+                .write_all(format!("ECHO {message}\r\n").as_bytes())
+                .await
+                .unwrap();
+            client.read_exact(&mut reply).await.unwrap();
+            assert_eq!(reply, format!("+{message}\r\n").as_bytes());
+        }
+        handle.abort();
+        let _ = handle.await;
+        let mut rest = Vec::new();
+        client.read_to_end(&mut rest).await.unwrap();
+        assert!(rest.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_closes_idle_connection() {
+        let (mut client, server) = duplex(1_024);
+        let handle = spawn(handle_client(
+            server,
+            None,
+            16,
+            1_024 * 1_024,
+            Some(Duration::from_millis(50)),
+            None,
+        ));
+
+        // each request resets the timeout
+        let mut reply = [0; 7];
+        for _ in 0..4 {
+            time::sleep(Duration::from_millis(30)).await;
+            client.write_all(b"PING\r\n").await.unwrap();
+            client.read_exact(&mut reply).await.unwrap();
+            assert_eq!(&reply, b"+PONG\r\n");
+        }
+
+        time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(client.read(&mut reply).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_keeps_idle_subscriber() {
+        let (mut client, server) = duplex(1_024);
+        let handle = spawn(handle_client(
+            server,
+            None,
+            16,
+            1_024 * 1_024,
+            Some(Duration::from_millis(30)),
+            None,
+        ));
+        client
+            .write_all(b"SUBSCRIBE idle:channel\r\n")
+            .await
+            .unwrap();
+
+        time::sleep(Duration::from_millis(100)).await;
+        assert!(!handle.is_finished());
+        handle.abort();
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_handle_client_cuts_off_stalled_write() {
+        DATABASES
+            .get(0)
+            .set("request-timeout:large", &"x".repeat(4_096));
+        let (mut client, server) = duplex(256);
+        let handle = spawn(handle_client(
+            server,
+            None,
+            16,
+            1_024 * 1_024,
+            None,
+            Some(Duration::from_millis(50)),
+        ));
+
+        // the reply outgrows the pipe, and the client never reads it
+        client
+            .write_all(b"GET request-timeout:large\r\n")
+            .await
+            .unwrap();
+        time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(logs_contain("write stalled past the request timeout"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_handle_client_cuts_off_incomplete_request() {
+        let (mut client, server) = duplex(1_024);
+        let handle = spawn(handle_client(
+            server,
+            None,
+            16,
+            1_024 * 1_024,
+            None,
+            Some(Duration::from_millis(80)),
+        ));
+
+        // complete requests are served however far apart they are
+        let mut reply = [0; 7];
+        for _ in 0..2 {
+            time::sleep(Duration::from_millis(100)).await;
+            client.write_all(b"PING\r\n").await.unwrap();
+            client.read_exact(&mut reply).await.unwrap();
+            assert_eq!(&reply, b"+PONG\r\n");
+        }
+
+        // while a request trickling in is cut off, even if every read is on time
+        for chunk in [&b"*2\r\n"[..], b"$4\r\n", b"ECHO\r\n", b"$5\r\n"] {
+            if client.write_all(chunk).await.is_err() {
+                break;
+            }
+            time::sleep(Duration::from_millis(30)).await;
+        }
+        time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(logs_contain("request incomplete after"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_handle_client_caps_query_buffer() {
+        let (mut client, server) = duplex(1_024);
+        let handle = spawn(handle_client(server, None, 16, 64, None, None));
+
+        // complete requests longer than the limit are served
+        let mut reply = [0; 5];
+        let request = format!("SET query-buffer:key {}\r\n", "x".repeat(100));
+        client.write_all(request.as_bytes()).await.unwrap();
+        client.read_exact(&mut reply).await.unwrap();
+        assert_eq!(&reply, b"+OK\r\n");
+
+        // while an incomplete one outgrowing it is not
+        let _ = client.write_all(b"*2\r\n$4\r\nECHO\r\n$1000\r\n").await;
+        let _ = client.write_all(&[b'x'; 100]).await;
+        time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(logs_contain("query buffer past 64 bytes"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_heartbeat_off() {
+        let received = read_idle(None, b"", Duration::from_millis(150)).await;
+        assert!(received.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_request_split_across_reads() {
+        let mut stream = MockStream::new(&[
+            b"*2\r\n$4\r\nECHO\r\n$5\r\nhe",
+            b"llo\r\n*1\r\n$4\r\nPING\r\n",
+            b"",
+        ]);
+        handle_client(&mut stream, None, 16, 1_024 * 1_024, None, None).await;
+        assert_eq!(stream.written, b"+hello\r\n+PONG\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_inline_request_split_across_reads() {
+        let mut stream = MockStream::new(&[
+            b"set split:inline va",
+            b"lue\r\nECHO hello\r",
+            b"\n*1\r\n$4\r\nPING\r\n",
+            b"",
+        ]);
+        handle_client(&mut stream, None, 16, 1_024 * 1_024, None, None).await;
+        assert_eq!(stream.written, b"+OK\r\n+hello\r\n+PONG\r\n");
+        assert_eq!(
+            DATABASES.get(0).get("split:inline"),
+            Ok(Some("value".into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_serve_limits_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = ServerConfig {
+            maxclients: 2,
+            ..ServerConfig::default()
+        };
+        spawn(async move { serve(listener, &config).await });
+
+        let mut reply = [0; 7];
+        let mut clients = Vec::new();
+        for _ in 0..2 {
+            let mut client = TcpStream::connect(addr).await.unwrap();
+            client.write_all(b"PING\r\n").await.unwrap();
+            client.read_exact(&mut reply).await.unwrap();
+            assert_eq!(&reply, b"+PONG\r\n");
+            clients.push(client);
+        }
+
+        let mut queued = TcpStream::connect(addr).await.unwrap();
+        queued.write_all(b"PING\r\n").await.unwrap();
+        let waited = time::timeout(Duration::from_millis(100), queued.read_exact(&mut reply)).await;
+        assert!(waited.is_err());
+
+        drop(clients.remove(0));
+        time::timeout(Duration::from_secs(1), queued.read_exact(&mut reply))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&reply, b"+PONG\r\n");
+    }
+
+    /// Collects the logs written by a subscriber.
+    #[derive(Clone, Default)]
+    struct Logs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl io::Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Serves a single client with logs filtered as by `--loglevel` and `RUST_LOG`,
+    /// returning what was logged.
+    async fn serve_logged(level: Level, rust_log: Option<&str>) -> String {
+        let logs = Logs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_env_filter(log_filter(level, rust_log))
+            .with_writer(move || writer.clone())
+            .finish();
+        // the runtime of the test runs the server on this thread too
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = spawn(async { serve(listener, &ServerConfig::default()).await });
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client.write_all(b"PING\r\n").await.unwrap();
+        let mut reply = [0; 7];
+        client.read_exact(&mut reply).await.unwrap();
+        server.abort();
+
+        let logs = logs.0.lock().unwrap();
+        String::from_utf8_lossy(&logs).into_owned()
+    }
+
+    #[tokio::test]
+    async fn test_serve_logs_accepted_connections_at_debug() {
+        let logs = serve_logged(Level::DEBUG, None).await;
+        assert!(logs.contains("accepted connection from 127.0.0.1:"));
+
+        let logs = serve_logged(Level::INFO, None).await;
+        assert!(!logs.contains("accepted connection"));
+
+        // `RUST_LOG` takes precedence over the level
+        let logs = serve_logged(Level::INFO, Some("redis_server=debug")).await;
+        assert!(logs.contains("accepted connection"));
+    }
+
+    #[tokio::test]
+    async fn test_configure_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let _client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+
+        let config = ServerConfig::default();
+        configure_stream(&stream, config.tcp_nodelay, config.tcp_keepalive).unwrap();
+        assert!(stream.nodelay().unwrap());
+        assert!(SockRef::from(&stream).keepalive().unwrap());
+
+        configure_stream(&stream, false, None).unwrap();
+        assert!(!stream.nodelay().unwrap());
+        assert!(!SockRef::from(&stream).keepalive().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_disconnects_slow_subscriber() {
+        let (mut client, server) = duplex(256);
+        let handle = spawn(handle_client(server, None, 4, 1_024 * 1_024, None, None));
+
+        let mut confirmation = [0; 48];
+        client
+            .write_all(b"SUBSCRIBE backpressure:flood\r\n")
+            .await
+            .unwrap();
+        client.read_exact(&mut confirmation).await.unwrap();
+
+        // the client stops reading, so its pending messages pile up until it is disconnected,
+        // while publishing never waits on it
+        let flood = time::timeout(Duration::from_millis(100), async {
+            let mut published = 0;
+            while PUBSUB.publish("backpressure:flood", "message") > 0 {
+                published += 1;
+                task::yield_now().await;
+            }
+            published
+        })
+        .await
+        .unwrap();
+        assert!(flood >= 4);
+
+        time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_handle_client_debug_sleep() {
+        let (mut sleeper, server) = duplex(1_024);
+        spawn(handle_client(server, None, 16, 1_024 * 1_024, None, None));
+        let (mut other, server) = duplex(1_024);
+        spawn(handle_client(server, None, 16, 1_024 * 1_024, None, None));
+
+        let started = Instant::now();
+        sleeper.write_all(b"DEBUG SLEEP 0.2\r\n").await.unwrap();
+
+        // the other connection is served while the sleeping one waits
+        let mut reply = [0; 7];
+        other.write_all(b"PING\r\n").await.unwrap();
+        other.read_exact(&mut reply).await.unwrap();
+        assert_eq!(&reply, b"+PONG\r\n");
+        assert!(started.elapsed() < Duration::from_millis(200));
+
+        let mut reply = [0; 5];
+        sleeper.read_exact(&mut reply).await.unwrap();
+        assert_eq!(&reply, b"+OK\r\n");
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(200) && elapsed < Duration::from_secs(1));
+    }
+
+    /// Drives raw request bytes through `process` within one session, as a client would,
+    /// so that tests can assert the exact bytes replied.
+    struct Harness {
+        session: Session,
+        pending: Vec<u8>,
+    }
+
+    impl Harness {
+        fn new() -> Self {
+            Self {
+                session: Session::new(mpsc::channel(16).0),
+                pending: Vec::new(),
+            }
+        }
+
+        /// Sends some bytes, possibly holding several requests or only part of one,
+        /// and returns the replies to the requests they complete.
+        fn send(&mut self, input: &[u8]) -> String {
+            self.pending.extend_from_slice(input);
+            let mut responses = Vec::new();
+            process(&mut self.pending, &mut self.session, &mut responses);
+            String::from_utf8(responses).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_process_set_get() {
+        let mut harness = Harness::new();
+        assert_eq!(
+            harness.send(b"*3\r\n$3\r\nSET\r\n$12\r\nharness:key1\r\n$5\r\nhello\r\n"),
+            "+OK\r\n"
+        );
+        assert_eq!(
+            harness.send(b"*2\r\n$3\r\nGET\r\n$12\r\nharness:key1\r\n"),
+            "+hello\r\n"
+        );
+        assert_eq!(
+            harness.send(b"*2\r\n$3\r\nGET\r\n$15\r\nharness:missing\r\n"),
+            "$-1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_process_pipelined_and_split() {
+        let mut harness = Harness::new();
+        let requests = b"*3\r\n$3\r\nSET\r\n$12\r\nharness:key2\r\n$5\r\nworld\r\n\
+            *2\r\n$3\r\nGET\r\n$12\r\nharness:key2\r\n";
+        let (first, second) = requests.split_at(20);
+
+        assert_eq!(harness.send(first), "");
+        assert_eq!(harness.send(second), "+OK\r\n+world\r\n");
+    }
+
+    #[test]
+    fn test_process_select_per_session() {
+        let mut selected = Harness::new();
+        let mut other = Harness::new();
+        assert_eq!(selected.send(b"SELECT 4\r\n"), "+OK\r\n");
+        assert_eq!(selected.send(b"SET harness:db value\r\n"), "+OK\r\n");
+
+        // each session keeps its own database, even on the same thread
+        assert_eq!(other.send(b"GET harness:db\r\n"), "$-1\r\n");
+        assert_eq!(selected.send(b"GET harness:db\r\n"), "+value\r\n");
+        assert!(!DATABASES.get(0).exists("harness:db"));
+    }
+
+    #[test]
+    fn test_process_errors() {
+        let mut harness = Harness::new();
+        assert_eq!(
+            harness.send(b"*2\r\n$6\r\nFOOBAR\r\n$3\r\nkey\r\n"),
+            "-ERR unknown command 'FOOBAR', with args beginning with: 'key' \r\n"
+        );
+        assert_eq!(
+            harness.send(b"*1\r\n$3\r\nGET\r\n"),
+            "-ERR wrong number of arguments for 'get' command\r\n"
+        );
+        // the session carries on after an error
+        assert_eq!(harness.send(b"PING\r\n"), "+PONG\r\n");
+    }
+
+    #[test]
+    fn test_process_line_breaks_in_replies() {
+        let mut harness = Harness::new();
+        assert_eq!(
+            harness.send(b"*2\r\n$3\r\nfoo\r\n$9\r\nx\r\n+OK\r\nZ\r\n"),
+            "-ERR unknown command 'foo', with args beginning with: 'x  +OK  Z' \r\n"
+        );
+
+        // values spanning several lines are sent whole, as bulk strings
+        harness.send(b"*3\r\n$3\r\nSET\r\n$13\r\nharness:lines\r\n$4\r\na\r\nb\r\n");
+        assert_eq!(harness.send(b"GET harness:lines\r\n"), "$4\r\na\r\nb\r\n");
+    }
+
+    #[test]
+    fn test_process_pipelined_with_invalid_command() {
+        let requests = b"SET harness:pipelined 1\r\nGET\r\nGET harness:pipelined\r\n";
+        let expected = "+OK\r\n-ERR wrong number of arguments for 'get' command\r\n+1\r\n";
+
+        // every command gets a reply of its own, however the requests are split into reads
+        for at in [requests.len(), 25, 30] {
+            DATABASES.get(0).del("harness:pipelined");
+            let mut harness = Harness::new();
+            let (first, second) = requests.split_at(at);
+            assert_eq!(harness.send(first) + &harness.send(second), expected);
+        }
+    }
+
+    #[test]
+    fn test_process_matches_per_command_responses() {
+        let requests = "PING\r\nECHO hello\r\nGET harness:buffer\r\nLPOP harness:buffer 2\r\n\
+            HGETALL harness:buffer\r\nCOMMAND DOCS get\r\n";
+        let mut session = Session::new(mpsc::channel(16).0);
+
+        // replies formatted one command at a time, then concatenated
+        let request = Request::try_from(requests.as_bytes()).unwrap();
+        let expected: String = Vec::<Result<Command, Response>>::from(request)
+            .into_iter()
+            .map(|command| String::from(command.unwrap().execute_in(&mut session)))
+            .collect();
+
+        // the buffer is appended to, keeping whatever it already held
+        let mut responses = b"+earlier\r\n".to_vec();
+        let mut pending = requests.as_bytes().to_vec();
+        process(&mut pending, &mut session, &mut responses);
+        assert_eq!(responses, format!("+earlier\r\n{expected}").into_bytes());
+    }
+
+    #[test]
+    fn test_process_empty_and_null_arrays() {
+        let mut harness = Harness::new();
+        assert_eq!(harness.send(b"*0\r\n"), "");
+        assert_eq!(harness.send(b"*-1\r\n"), "");
+        let requests = b"*0\r\n*1\r\n$4\r\nPING\r\n*-1\r\n*2\r\n$4\r\nECHO\r\n$2\r\nhi\r\n";
+        assert_eq!(harness.send(requests), "+PONG\r\n+hi\r\n");
+        assert!(harness.pending.is_empty());
+    }
+
+    #[test]
+    fn test_process_transaction() {
+        let mut harness = Harness::new();
+        assert_eq!(harness.send(b"MULTI\r\n"), "+OK\r\n");
+        assert_eq!(
+            harness.send(b"SET harness:transaction queued\r\n"),
+            "+QUEUED\r\n"
+        );
+        assert_eq!(harness.send(b"GET harness:transaction\r\n"), "+QUEUED\r\n");
+        assert_eq!(DATABASES.get(0).get("harness:transaction"), Ok(None));
+        assert_eq!(harness.send(b"EXEC\r\n"), "*2\r\n+OK\r\n+queued\r\n");
+
+        // nothing is queued once the transaction is over
+        assert_eq!(harness.send(b"PING\r\n"), "+PONG\r\n");
+    }
+
+    #[test]
+    fn test_process_transaction_aborted() {
+        let mut harness = Harness::new();
+        harness.send(b"MULTI\r\n");
+        assert_eq!(
+            harness.send(b"SET harness:aborted value\r\n"),
+            "+QUEUED\r\n"
+        );
+        assert_eq!(
+            harness.send(b"GET\r\n"),
+            "-ERR wrong number of arguments for 'get' command\r\n"
+        );
+        assert_eq!(
+            harness.send(b"EXEC\r\n"),
+            "-EXECABORT Transaction discarded because of previous errors.\r\n"
+        );
+        assert_eq!(DATABASES.get(0).get("harness:aborted"), Ok(None));
+
+        harness.send(b"MULTI\r\n");
+        harness.send(b"SET harness:aborted value\r\n");
+        assert_eq!(harness.send(b"DISCARD\r\n"), "+OK\r\n");
+        assert_eq!(DATABASES.get(0).get("harness:aborted"), Ok(None));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_process_logs_command_span() {
+        let mut session = Session::new(mpsc::channel(16).0);
+        let mut pending = b"SET tracing:key secret\r\nGET tracing:key\r\n".to_vec();
+        process(&mut pending, &mut session, &mut Vec::new());
+
+        assert!(logs_contain("command{name=\"get\" args=1}"));
+        assert!(logs_contain("elapsed_us="));
+        assert!(!logs_contain("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_expire_cycle_reclaims_unaccessed_keys() {
+        let store: &'static KvStore = Box::leak(Box::new(KvStore::new()));
+        store.set("expire_cycle:persistent", "1");
+        store.set("expire_cycle:short", "2");
+        store.expire("expire_cycle:short", Duration::from_millis(20));
+
+        let cycle = spawn(expire_cycle(store, Duration::from_millis(10)));
+        time::sleep(Duration::from_millis(200)).await;
+        cycle.abort();
+
+        // the store holds only the persistent key, though the other was never accessed again
+        assert_eq!(store.len(), 1);
+    }
 }
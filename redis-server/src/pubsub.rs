@@ -0,0 +1,186 @@
+use dashmap::DashMap;
+use std::{collections::HashMap, sync::LazyLock};
+use tokio::sync::mpsc::{error::TrySendError, Sender};
+use tracing::warn;
+
+use crate::{glob, response::types::Response, session};
+
+/// Maps a channel or pattern to the senders of its subscribers, keyed by client id.
+type Subscribers = DashMap<String, HashMap<u64, Sender<Response>>>;
+
+/// Represents the registry of publish/subscribe channels and patterns.
+pub struct PubSub {
+    channels: Subscribers,
+    patterns: Subscribers,
+}
+
+impl PubSub {
+    /// Creates a new instance of `PubSub`.
+    fn new() -> Self {
+        Self {
+            channels: DashMap::new(),
+            patterns: DashMap::new(),
+        }
+    }
+
+    /// Registers a client as a subscriber of a channel.
+    ///
+    /// # Parameters
+    ///
+    /// - `channel`: The channel to subscribe to.
+    /// - `id`: The id of the subscribing client.
+    /// - `sender`: The channel over which messages are delivered to the client.
+    pub fn subscribe(&self, channel: &str, id: u64, sender: &Sender<Response>) {
+        self.channels
+            .entry(channel.into())
+            .or_default()
+            .insert(id, sender.clone());
+    }
+
+    /// Removes a client from the subscribers of a channel.
+    ///
+    /// # Parameters
+    ///
+    /// - `channel`: The channel to unsubscribe from.
+    /// - `id`: The id of the unsubscribing client.
+    pub fn unsubscribe(&self, channel: &str, id: u64) {
+        Self::remove(&self.channels, channel, id);
+    }
+
+    /// Registers a client as a subscriber of all channels matching a glob pattern.
+    ///
+    /// # Parameters
+    ///
+    /// - `pattern`: The glob pattern to subscribe to.
+    /// - `id`: The id of the subscribing client.
+    /// - `sender`: The channel over which messages are delivered to the client.
+    pub fn psubscribe(&self, pattern: &str, id: u64, sender: &Sender<Response>) {
+        self.patterns
+            .entry(pattern.into())
+            .or_default()
+            .insert(id, sender.clone());
+    }
+
+    /// Removes a client from the subscribers of a glob pattern.
+    ///
+    /// # Parameters
+    ///
+    /// - `pattern`: The glob pattern to unsubscribe from.
+    /// - `id`: The id of the unsubscribing client.
+    pub fn punsubscribe(&self, pattern: &str, id: u64) {
+        Self::remove(&self.patterns, pattern, id);
+    }
+
+    fn remove(subscribers: &Subscribers, name: &str, id: u64) {
+        if let Some(mut clients) = subscribers.get_mut(name) {
+            clients.remove(&id);
+        }
+        subscribers.remove_if(name, |_, clients| clients.is_empty());
+    }
+
+    /// Drops a client from every channel and pattern, and disconnects it.
+    fn evict(&self, id: u64) {
+        for subscribers in [&self.channels, &self.patterns] {
+            subscribers.alter_all(|_, mut clients| {
+                clients.remove(&id);
+                clients
+            });
+            subscribers.retain(|_, clients| !clients.is_empty());
+        }
+        session::kill(id);
+    }
+
+    /// Publishes a message to the subscribers of a channel and of all matching patterns.
+    ///
+    /// Delivery never waits on a subscriber: one whose pending messages have reached the limit
+    /// is disconnected instead, so that a slow reader cannot hold up the publisher
+    /// or have messages pile up without bound.
+    ///
+    /// # Parameters
+    ///
+    /// - `channel`: The channel to publish to.
+    /// - `message`: The message to publish.
+    ///
+    /// # Returns
+    ///
+    /// The number of clients the message was delivered to.
+    pub fn publish(&self, channel: &str, message: &str) -> usize {
+        let mut receivers = 0;
+        let mut evicted = Vec::new();
+
+        if let Some(clients) = self.channels.get(channel) {
+            let frame = Response::Array(vec![
+                Response::bs("message"),
+                Response::bs(channel),
+                Response::bs(message),
+            ]);
+            receivers += deliver(&clients, &frame, &mut evicted);
+        }
+
+        for entry in &self.patterns {
+            if !glob::matches(entry.key(), channel) {
+                continue;
+            }
+
+            let frame = Response::Array(vec![
+                Response::bs("pmessage"),
+                Response::bs(entry.key()),
+                Response::bs(channel),
+                Response::bs(message),
+            ]);
+            receivers += deliver(entry.value(), &frame, &mut evicted);
+        }
+
+        for id in evicted {
+            warn!("disconnecting client {id}: output buffer limit reached");
+            self.evict(id);
+        }
+
+        receivers
+    }
+}
+
+/// Delivers a message to subscribers without waiting on any of them.
+///
+/// # Parameters
+///
+/// - `clients`: The senders of the subscribers, keyed by client id.
+/// - `frame`: The message to deliver.
+/// - `evicted`: Collects the ids of subscribers whose pending messages are at the limit.
+///
+/// # Returns
+///
+/// The number of clients the message was delivered to.
+fn deliver(
+    clients: &HashMap<u64, Sender<Response>>,
+    frame: &Response,
+    evicted: &mut Vec<u64>,
+) -> usize {
+    let mut delivered = 0;
+    for (id, sender) in clients {
+        match sender.try_send(frame.clone()) {
+            Ok(()) => delivered += 1,
+            Err(TrySendError::Full(_)) => evicted.push(*id),
+            Err(TrySendError::Closed(_)) => {}
+        }
+    }
+    delivered
+}
+
+/// Builds the reply confirming a change to a client's subscriptions.
+///
+/// # Parameters
+///
+/// - `kind`: The kind of change, such as `subscribe` or `punsubscribe`.
+/// - `name`: The affected channel or pattern, if any.
+/// - `count`: The number of subscriptions the client holds afterwards.
+pub fn confirmation(kind: &str, name: Option<&str>, count: usize) -> Response {
+    Response::Array(vec![
+        Response::bs(kind),
+        name.map_or(Response::Null, Response::bs),
+        Response::Integer(i64::try_from(count).unwrap_or(i64::MAX)),
+    ])
+}
+
+/// The global instance of `PubSub`.
+pub static PUBSUB: LazyLock<PubSub> = LazyLock::new(PubSub::new);
@@ -1,115 +1,532 @@
 /// This module provides functions for deserializing Redis requests.
 use crate::{
-    command::types::{Command, CommandBuilder},
+    command::{
+        metadata,
+        types::{Command, CommandBuilder, CommandError},
+    },
     response::types::Response,
 };
 
 use super::types::Request;
 
-/// Parses a Redis request into a vector of executable commands.
+/// Parses a Redis request into its commands, each built on its own.
+///
+/// A command that fails to build does not affect the others, so that every command
+/// of a pipelined request gets a reply of its own.
 ///
 /// # Arguments
 /// * `request` - The Redis request to parse.
 ///
 /// # Returns
-/// * `Result<Vec<Command>, String>` - A vector of executable commands, or an error message.
-pub fn parse_commands(request: &Request) -> Result<Vec<Command>, Response> {
-    let mut commands: Vec<Command> = Vec::new();
-    for cmd in request.commands() {
-        let cmd_type = match cmd.first() {
-            Some(cmd) => match cmd.parse::<CommandBuilder>() {
-                Ok(result) => result,
-                Err(error) => return Err(Response::from(error)),
-            },
-            None => return Err(Response::err("", "empty command")),
-        };
-
-        commands.push(match cmd_type {
-            CommandBuilder::Ping(builder) => match cmd.len() {
-                1 => Command::Ping(builder.build()),
-                2 => Command::Ping(builder.message(cmd[1].as_str()).build()),
-                _ => return Err(Response::err("", "unexpected number of arguments for PING")),
-            },
-            CommandBuilder::Echo(builder) => match cmd.len() {
-                2 => match builder.message(cmd[1].as_str()).build() {
-                    Ok(result) => Command::Echo(result),
-                    Err(error) => return Err(Response::from(error)),
-                },
-                _ => return Err(Response::err("", "unexpected number of arguments for ECHO")),
-            },
-            CommandBuilder::Exists(builder) => match cmd.len() {
-                2 => match builder.key(cmd[1].as_str()).build() {
-                    Ok(result) => Command::Exists(result),
-
-                    Err(error) => return Err(Response::from(error)),
-                },
-                _ => {
-                    return Err(Response::err(
-                        "",
-                        "unexpected number of arguments for EXISTS",
-                    ))
-                }
-            },
-            CommandBuilder::Config(builder) => match cmd.len() {
-                3 => match builder.args(cmd[1..].to_vec()).build() {
-                    Ok(result) => Command::Config(result),
-                    Err(error) => return Err(Response::from(error)),
-                },
-                _ => {
-                    return Err(Response::err(
-                        "",
-                        "unexpected number of arguments for CONFIG",
-                    ))
-                }
-            },
-            CommandBuilder::Set(builder) => match cmd.len() {
-                3 => match builder.key(cmd[1].as_str()).value(cmd[2].as_str()).build() {
-                    Ok(result) => Command::Set(result),
+/// * `Vec<Result<Command, Response>>` - Each executable command, or the error to reply with.
+pub fn parse_commands(request: &Request) -> Vec<Result<Command, Response>> {
+    request
+        .commands()
+        .iter()
+        .map(|cmd| parse_command(cmd))
+        .collect()
+}
 
-                    Err(error) => return Err(Response::from(error)),
-                },
-                _ => return Err(Response::err("", "unexpected number of arguments for SET")),
-            },
-            CommandBuilder::Get(builder) => match cmd.len() {
-                2 => match builder.key(cmd[1].as_str()).build() {
-                    Ok(result) => Command::Get(result),
-                    Err(error) => return Err(Response::from(error)),
-                },
-                _ => return Err(Response::err("", "unexpected number of arguments for GET")),
-            },
-            CommandBuilder::Del(builder) => match cmd.len() {
-                2 => match builder.key(cmd[1].as_str()).build() {
-                    Ok(result) => Command::Del(result),
-                    Err(error) => return Err(Response::from(error)),
-                },
-                _ => return Err(Response::err("", "unexpected number of arguments for DEL")),
-            },
-        });
+/// Parses a single command of a Redis request.
+///
+/// The number of arguments of the command is checked against the command table
+/// before its builder is given them, so the builders may index into them freely.
+///
+/// # Arguments
+/// * `cmd` - The name of the command followed by its arguments.
+///
+/// # Returns
+/// * `Result<Command, Response>` - The executable command, or the error to reply with.
+#[allow(clippy::too_many_lines)]
+fn parse_command(cmd: &[String]) -> Result<Command, Response> {
+    let cmd_type = match cmd.first() {
+        Some(name) => match name.parse::<CommandBuilder>() {
+            Ok(result) => result,
+            Err(error) => return Err(Response::from(error.with_args(&cmd[1..]))),
+        },
+        None => return Err(Response::err("", "empty command")),
+    };
+    // without an entry, the number of arguments is unknown, and indexing into them could panic
+    let Some(metadata) = metadata::lookup(&cmd[0]) else {
+        return Err(Response::err(
+            "ERR",
+            &format!(
+                "internal error: '{}' is missing from the command table",
+                cmd[0]
+            ),
+        ));
+    };
+    if !metadata.accepts(cmd.len()) {
+        return Err(Response::from(CommandError::WrongArity {
+            name: metadata.name.into(),
+        }));
     }
-    Ok(commands)
+
+    Ok(match cmd_type {
+        CommandBuilder::Ping(builder) => match cmd.get(1) {
+            Some(message) => Command::Ping(builder.message(message).build()),
+            None => Command::Ping(builder.build()),
+        },
+        CommandBuilder::Echo(builder) => match builder.message(cmd[1].as_str()).build() {
+            Ok(result) => Command::Echo(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Exists(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::Exists(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Config(builder) => match builder.args(cmd[1..].to_vec()).build() {
+            Ok(result) => Command::Config(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Set(builder) => {
+            match builder.key(cmd[1].as_str()).value(cmd[2].as_str()).build() {
+                Ok(result) => Command::Set(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::Get(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::Get(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Del(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::Del(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Rename(builder) | CommandBuilder::RenameNx(builder) => match builder
+            .key(cmd[1].as_str())
+            .new_key(cmd[2].as_str())
+            .build()
+        {
+            Ok(result) => Command::Rename(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Copy(builder) => match builder
+            .key(cmd[1].as_str())
+            .destination(cmd[2].as_str())
+            .options(&cmd[3..])
+            .build()
+        {
+            Ok(result) => Command::Copy(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Expire(builder) | CommandBuilder::PExpire(builder) => {
+            match builder.key(cmd[1].as_str()).ttl(cmd[2].as_str()).build() {
+                Ok(result) => Command::Expire(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::Ttl(builder) | CommandBuilder::PTtl(builder) => {
+            match builder.key(cmd[1].as_str()).build() {
+                Ok(result) => Command::Ttl(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::Persist(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::Persist(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Subscribe(builder) | CommandBuilder::PSubscribe(builder) => {
+            match builder.channels(&cmd[1..]).build() {
+                Ok(result) => Command::Subscribe(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::Unsubscribe(builder) | CommandBuilder::PUnsubscribe(builder) => {
+            Command::Unsubscribe(builder.channels(&cmd[1..]).build())
+        }
+        CommandBuilder::Publish(builder) => match builder
+            .channel(cmd[1].as_str())
+            .message(cmd[2].as_str())
+            .build()
+        {
+            Ok(result) => Command::Publish(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Command(builder) => match builder.args(cmd[1..].to_vec()).build() {
+            Ok(result) => Command::Command(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Client(builder) => match builder.args(cmd[1..].to_vec()).build() {
+            Ok(result) => Command::Client(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::HExpire(builder) => match builder
+            .key(cmd[1].as_str())
+            .ttl(cmd[2].as_str())
+            .fields(&cmd[3..])
+            .build()
+        {
+            Ok(result) => Command::HExpire(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::HTtl(builder) => {
+            match builder.key(cmd[1].as_str()).fields(&cmd[2..]).build() {
+                Ok(result) => Command::HTtl(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::Debug(builder) => match builder.args(cmd[1..].to_vec()).build() {
+            Ok(result) => Command::Debug(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Object(builder) => match builder.args(cmd[1..].to_vec()).build() {
+            Ok(result) => Command::Object(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::HSet(builder) | CommandBuilder::HMSet(builder) => {
+            match builder.key(cmd[1].as_str()).pairs(&cmd[2..]).build() {
+                Ok(result) => Command::HSet(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::HGet(builder) => {
+            match builder.key(cmd[1].as_str()).field(cmd[2].as_str()).build() {
+                Ok(result) => Command::HGet(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::HGetAll(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::HGetAll(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::HDel(builder) => {
+            match builder.key(cmd[1].as_str()).fields(&cmd[2..]).build() {
+                Ok(result) => Command::HDel(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::Type(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::Type(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::SAdd(builder) => {
+            match builder.key(cmd[1].as_str()).members(&cmd[2..]).build() {
+                Ok(result) => Command::SAdd(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::SRem(builder) => {
+            match builder.key(cmd[1].as_str()).members(&cmd[2..]).build() {
+                Ok(result) => Command::SRem(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::SMembers(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::SMembers(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::SIsMember(builder) => {
+            match builder.key(cmd[1].as_str()).member(cmd[2].as_str()).build() {
+                Ok(result) => Command::SIsMember(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::SCard(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::SCard(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Wait(builder) => match builder
+            .numreplicas(cmd[1].as_str())
+            .timeout(cmd[2].as_str())
+            .build()
+        {
+            Ok(result) => Command::Wait(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::GetRange(builder) => match builder
+            .key(cmd[1].as_str())
+            .start(cmd[2].as_str())
+            .end(cmd[3].as_str())
+            .build()
+        {
+            Ok(result) => Command::GetRange(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::SetRange(builder) => match builder
+            .key(cmd[1].as_str())
+            .offset(cmd[2].as_str())
+            .value(cmd[3].as_str())
+            .build()
+        {
+            Ok(result) => Command::SetRange(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::RandomKey(builder) => Command::RandomKey(builder.build()),
+        CommandBuilder::IncrByFloat(builder) => match builder
+            .key(cmd[1].as_str())
+            .increment(cmd[2].as_str())
+            .build()
+        {
+            Ok(result) => Command::IncrByFloat(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::LPush(builder) | CommandBuilder::RPush(builder) => {
+            match builder.key(cmd[1].as_str()).elements(&cmd[2..]).build() {
+                Ok(result) => Command::Push(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::LPop(builder) | CommandBuilder::RPop(builder) => {
+            let mut builder = builder.key(cmd[1].as_str());
+            if let Some(count) = cmd.get(2) {
+                builder = builder.count(count);
+            }
+            match builder.build() {
+                Ok(result) => Command::Pop(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::LLen(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::LLen(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::ExpireAt(builder) | CommandBuilder::PExpireAt(builder) => match builder
+            .key(cmd[1].as_str())
+            .timestamp(cmd[2].as_str())
+            .build()
+        {
+            Ok(result) => Command::ExpireAt(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Multi(builder) => Command::Multi(builder.build()),
+        CommandBuilder::Exec(builder) => Command::Exec(builder.build()),
+        CommandBuilder::Discard(builder) => Command::Discard(builder.build()),
+        CommandBuilder::Dump(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::Dump(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Restore(builder) => match builder
+            .key(cmd[1].as_str())
+            .ttl(cmd[2].as_str())
+            .serialized(cmd[3].as_str())
+            .options(&cmd[4..])
+            .build()
+        {
+            Ok(result) => Command::Restore(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::SetBit(builder) => match builder
+            .key(cmd[1].as_str())
+            .offset(cmd[2].as_str())
+            .bit(cmd[3].as_str())
+            .build()
+        {
+            Ok(result) => Command::SetBit(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::GetBit(builder) => {
+            match builder.key(cmd[1].as_str()).offset(cmd[2].as_str()).build() {
+                Ok(result) => Command::GetBit(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::BitCount(builder) => {
+            match builder.key(cmd[1].as_str()).range(&cmd[2..]).build() {
+                Ok(result) => Command::BitCount(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::LIndex(builder) => {
+            match builder.key(cmd[1].as_str()).index(cmd[2].as_str()).build() {
+                Ok(result) => Command::LIndex(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::LSet(builder) => match builder
+            .key(cmd[1].as_str())
+            .index(cmd[2].as_str())
+            .value(cmd[3].as_str())
+            .build()
+        {
+            Ok(result) => Command::LSet(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::SInter(builder)
+        | CommandBuilder::SUnion(builder)
+        | CommandBuilder::SDiff(builder) => match builder.keys(&cmd[1..]).build() {
+            Ok(result) => Command::SetOp(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::HIncrBy(builder) => match builder
+            .key(cmd[1].as_str())
+            .field(cmd[2].as_str())
+            .increment(cmd[3].as_str())
+            .build()
+        {
+            Ok(result) => Command::HIncrBy(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Info(builder) => Command::Info(builder.sections(&cmd[1..]).build()),
+        CommandBuilder::SInterStore(builder)
+        | CommandBuilder::SUnionStore(builder)
+        | CommandBuilder::SDiffStore(builder) => {
+            match builder.destination(cmd[1].as_str()).keys(&cmd[2..]).build() {
+                Ok(result) => Command::SetOp(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::Memory(builder) => match builder.args(cmd[1..].to_vec()).build() {
+            Ok(result) => Command::Memory(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Time(builder) => Command::Time(builder.build()),
+        CommandBuilder::LRem(builder) => match builder
+            .key(cmd[1].as_str())
+            .count(cmd[2].as_str())
+            .element(cmd[3].as_str())
+            .build()
+        {
+            Ok(result) => Command::LRem(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::LTrim(builder) => match builder
+            .key(cmd[1].as_str())
+            .start(cmd[2].as_str())
+            .stop(cmd[3].as_str())
+            .build()
+        {
+            Ok(result) => Command::LTrim(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::HExists(builder) => {
+            match builder.key(cmd[1].as_str()).field(cmd[2].as_str()).build() {
+                Ok(result) => Command::HExists(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::HKeys(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::HKeys(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::HVals(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::HVals(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::HLen(builder) => match builder.key(cmd[1].as_str()).build() {
+            Ok(result) => Command::HLen(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::HMGet(builder) => {
+            match builder.key(cmd[1].as_str()).fields(&cmd[2..]).build() {
+                Ok(result) => Command::HMGet(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::Sort(builder) => {
+            match builder.key(cmd[1].as_str()).options(&cmd[2..]).build() {
+                Ok(result) => Command::Sort(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::Move(builder) => {
+            match builder.key(cmd[1].as_str()).db(cmd[2].as_str()).build() {
+                Ok(result) => Command::Move(result),
+                Err(error) => return Err(Response::from(error)),
+            }
+        }
+        CommandBuilder::SwapDb(builder) => match builder
+            .index1(cmd[1].as_str())
+            .index2(cmd[2].as_str())
+            .build()
+        {
+            Ok(result) => Command::SwapDb(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+        CommandBuilder::Select(builder) => match builder.index(cmd[1].as_str()).build() {
+            Ok(result) => Command::Select(result),
+            Err(error) => return Err(Response::from(error)),
+        },
+    })
 }
 
 #[cfg(test)]
 /// Module containing unit tests for the `stringify` and `parse_commands` functions.
 mod tests {
     use super::*;
-    use crate::command;
+    use crate::{command::types::ExecuteInSession, session::Session};
+    use tokio::sync::mpsc;
 
     /// Test case for `parse_commands` function with an "echo" command.
     #[test]
     fn test_parse_commands_echo() {
         let request = "echo ling\r\n".parse::<Request>().unwrap();
-        let result = parse_commands(&request);
-        assert!(result.is_ok());
-        let commands = result.unwrap();
+        let commands = parse_commands(&request);
         assert_eq!(commands.len(), 1);
+        let mut session = Session::new(mpsc::channel(16).0);
         assert_eq!(
             commands
                 .into_iter()
-                .map(command::types::Execute::execute)
+                .map(|command| command.unwrap().execute_in(&mut session))
                 .map(String::from)
                 .collect::<String>(),
             "+ling\r\n"
         );
     }
+
+    #[test]
+    fn test_parse_commands_unknown() {
+        let request = "FOOBAR key value\r\n".parse::<Request>().unwrap();
+        let Some(Err(response)) = parse_commands(&request).pop() else {
+            panic!("unknown command should be rejected");
+        };
+        assert_eq!(
+            String::from(response),
+            "-ERR unknown command 'FOOBAR', with args beginning with: 'key' 'value' \r\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_commands_substr() {
+        crate::kvstore::DATABASES
+            .get(0)
+            .set("substr:key", "This is a string");
+        let mut session = Session::new(mpsc::channel(16).0);
+        let mut run = |request: &str| {
+            let request = request.parse::<Request>().unwrap();
+            parse_commands(&request)
+                .into_iter()
+                .map(|command| String::from(command.unwrap().execute_in(&mut session)))
+                .collect::<String>()
+        };
+
+        for (start, end) in [("0", "3"), ("-3", "-1"), ("5", "100"), ("10", "2")] {
+            assert_eq!(
+                run(&format!("SUBSTR substr:key {start} {end}\r\n")),
+                run(&format!("GETRANGE substr:key {start} {end}\r\n"))
+            );
+        }
+        assert_eq!(run("substr substr:key 0 3\r\n"), "$4\r\nThis\r\n");
+    }
+
+    #[test]
+    fn test_parse_commands_each_on_its_own() {
+        let request = "PING\r\nGET\r\nECHO hi\r\n".parse::<Request>().unwrap();
+        let commands = parse_commands(&request);
+        assert_eq!(commands.len(), 3);
+        assert!(commands[0].is_ok());
+        assert!(commands[1].is_err());
+        assert!(commands[2].is_ok());
+    }
+
+    #[test]
+    fn test_parse_commands_wrong_arity() {
+        for (request, name) in [
+            ("GET\r\n", "get"),
+            ("get key extra\r\n", "get"),
+            ("PING one two\r\n", "ping"),
+            ("HSET key field\r\n", "hset"),
+            ("SUBSCRIBE\r\n", "subscribe"),
+            ("TIME now\r\n", "time"),
+        ] {
+            let request = request.parse::<Request>().unwrap();
+            let Some(Err(response)) = parse_commands(&request).pop() else {
+                panic!("{request:?} should be rejected");
+            };
+            assert_eq!(
+                String::from(response),
+                format!("-ERR wrong number of arguments for '{name}' command\r\n")
+            );
+        }
+    }
 }
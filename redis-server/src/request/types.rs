@@ -36,16 +36,72 @@ impl TryFrom<&[u8]> for Request {
     type Error = ParseError;
 
     fn try_from(request_buf: &[u8]) -> Result<Self, Self::Error> {
-        let request_str = match std::str::from_utf8(request_buf) {
-            Ok(result) => result.trim_matches('\0'),
-            Err(error) => return Err(Self::Error::Utf8(error)),
+        let start = request_buf
+            .iter()
+            .position(|&b| b != 0)
+            .unwrap_or(request_buf.len());
+        let end = request_buf
+            .iter()
+            .rposition(|&b| b != 0)
+            .map_or(start, |i| i + 1);
+        Self::parse_whole(&request_buf[start..end])
+    }
+}
+
+/// Represents the outcome of parsing the start of a buffer of incoming bytes.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Parsed {
+    /// One or more complete commands, along with the number of bytes they took up.
+    Complete(Request, usize),
+    /// The buffer does not hold a complete command yet, so more bytes are needed.
+    Incomplete,
+}
+
+impl Request {
+    /// Parses the complete commands at the start of a buffer,
+    /// leaving a trailing partial command to be parsed once the rest of it arrives.
+    ///
+    /// Empty and null arrays, `*0` and `*-1`, are consumed without yielding a command,
+    /// so a buffer holding only those is complete, with no commands.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if the buffer holds a malformed command.
+    pub fn parse(buf: &[u8]) -> Result<Parsed, ParseError> {
+        let (commands, consumed) = if buf.first() == Some(&b'*') {
+            parse_bulk_requests(buf)?
+        } else {
+            parse_inline_request(buf, false)?
         };
 
-        Ok(Self::new(if request_str.starts_with('*') {
-            parse_bulk_requests(request_str)?
+        Ok(if consumed == 0 {
+            Parsed::Incomplete
         } else {
-            parse_inline_request(request_str)?
-        }))
+            Parsed::Complete(Self::new(commands), consumed)
+        })
+    }
+
+    /// Parses a buffer that must hold nothing but complete commands.
+    fn parse_whole(buf: &[u8]) -> Result<Self, ParseError> {
+        if buf.is_empty() {
+            return Err(ParseError::EmptyRequest);
+        }
+
+        if buf.first() != Some(&b'*') {
+            return Ok(Self::new(parse_inline_request(buf, true)?.0));
+        }
+
+        let (commands, consumed) = parse_bulk_requests(buf)?;
+        if consumed < buf.len() {
+            // a truncated command is missing either its length or some of its tokens
+            return Err(if read_line(buf, consumed).is_none() {
+                ParseError::InvalidBulkLength
+            } else {
+                ParseError::InvalidTokenLength
+            });
+        }
+
+        Ok(Self::new(commands))
     }
 }
 
@@ -94,102 +150,145 @@ impl FromStr for Request {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::new(if s.starts_with('*') {
-            parse_bulk_requests(s)?
-        } else {
-            parse_inline_request(s)?
-        }))
+        Self::parse_whole(s.as_bytes())
     }
 }
 
-fn parse_bulk_requests(s: &str) -> Result<Vec<Vec<String>>, ParseError> {
+/// The longest bulk string a request may hold, like Redis's default `proto-max-bulk-len` of 512 MB.
+pub const PROTO_MAX_BULK_LEN: usize = 512 * 1_024 * 1_024;
+
+/// Finds the line starting at `start`.
+///
+/// # Returns
+///
+/// The line without its `\r\n` terminator, along with the position right after it,
+/// or `None` if the line is not terminated yet.
+fn read_line(buf: &[u8], start: usize) -> Option<(&[u8], usize)> {
+    let len = buf.get(start..)?.windows(2).position(|w| w == b"\r\n")?;
+    Some((&buf[start..start + len], start + len + 2))
+}
+
+/// Parses a line holding a non-negative length.
+fn parse_length(line: &[u8]) -> Option<usize> {
+    std::str::from_utf8(line).ok()?.parse().ok()
+}
+
+/// Parses consecutive bulk commands, stopping at the first incomplete one.
+///
+/// # Returns
+///
+/// The complete commands, along with the number of bytes they took up.
+fn parse_bulk_requests(buf: &[u8]) -> Result<(Vec<Vec<String>>, usize), ParseError> {
     let mut commands: Vec<Vec<String>> = Vec::new();
 
-    let chars: Vec<_> = s.chars().collect();
-    let mut num_tokens = usize::default();
     let mut i = 0;
-    while i < chars.len() {
-        match chars.get(i) {
-            Some('*') => {
-                parse_bulk_request_length(s, &chars, &mut i, &mut num_tokens)?;
-                let cmd = parse_bulk_request_args(s, &chars, &mut i, num_tokens)?;
+    while i < buf.len() {
+        if buf[i] != b'*' {
+            return Err(ParseError::InvalidRequest);
+        }
 
-                commands.push(cmd);
+        match parse_bulk_request(buf, i)? {
+            Some((cmd, end)) => {
+                // like Redis, empty and null arrays are skipped rather than rejected
+                if !cmd.is_empty() {
+                    commands.push(cmd);
+                }
+                i = end;
             }
-            _ => return Err(ParseError::InvalidRequest),
+            None => break,
         }
     }
 
-    Ok(commands)
+    Ok((commands, i))
 }
 
-fn parse_bulk_request_length(
-    s: &str,
-    chars: &[char],
-    i: &mut usize,
-    num_tokens: &mut usize,
-) -> Result<(), ParseError> {
-    let mut j = *i + 1;
-    while j < s.len() && chars[j] != '\r' {
-        j += 1;
-    }
-
-    *num_tokens = s[*i + 1..j]
-        .parse::<usize>()
-        .map_err(|_| ParseError::InvalidBulkLength)?;
-    *i = j + 2;
-
-    if *num_tokens < 1 {
-        return Err(ParseError::EmptyCommand);
-    }
-
-    Ok(())
-}
+/// Parses a single bulk command starting at `start`.
+///
+/// # Returns
+///
+/// The command along with the position right after it, or `None` if it is incomplete.
+/// The command is empty for an empty array `*0`, or a null array such as `*-1`.
+fn parse_bulk_request(
+    buf: &[u8],
+    start: usize,
+) -> Result<Option<(Vec<String>, usize)>, ParseError> {
+    let Some((line, mut i)) = read_line(buf, start + 1) else {
+        return Ok(None);
+    };
+
+    let num_tokens: i64 = std::str::from_utf8(line)
+        .ok()
+        .and_then(|line| line.parse().ok())
+        .ok_or(ParseError::InvalidBulkLength)?;
+    let Ok(num_tokens) = usize::try_from(num_tokens) else {
+        return Ok(Some((Vec::new(), i)));
+    };
 
-fn parse_bulk_request_args(
-    s: &str,
-    chars: &[char],
-    i: &mut usize,
-    num_tokens: usize,
-) -> Result<Vec<String>, ParseError> {
     let mut cmd: Vec<String> = Vec::new();
     for _ in 0..num_tokens {
         // parse token length
-        if chars.get(*i) != Some(&'$') {
-            return Err(ParseError::InvalidTokenLength);
-        }
-        *i += 1;
-
-        let mut j = *i;
-        while j < s.len() && chars[j] != '\r' {
-            j += 1;
+        match buf.get(i) {
+            Some(b'$') => {}
+            Some(_) => return Err(ParseError::InvalidTokenLength),
+            None => return Ok(None),
         }
 
-        let token_len = s[*i..j]
-            .parse::<usize>()
-            .map_err(|_| ParseError::InvalidTokenLength)?;
-
-        if *i + token_len >= s.len() {
-            return Err(ParseError::InvalidTokenLength);
-        }
-        *i = j + 2;
+        let Some((line, token_start)) = read_line(buf, i + 1) else {
+            return Ok(None);
+        };
+        let token_len = parse_length(line)
+            .filter(|len| *len <= PROTO_MAX_BULK_LEN)
+            .ok_or(ParseError::InvalidTokenLength)?;
 
         // parse token
-        let token = s[*i..*i + token_len].into();
-        *i += token_len + 2;
+        let token_end = token_start
+            .checked_add(token_len)
+            .ok_or(ParseError::InvalidTokenLength)?;
+        match buf.get(token_end..token_end + 2) {
+            Some(b"\r\n") => {}
+            Some(_) => return Err(ParseError::InvalidTokenLength),
+            None => return Ok(None),
+        }
 
-        cmd.push(token);
+        let token = std::str::from_utf8(&buf[token_start..token_end])?;
+        cmd.push(token.into());
+        i = token_end + 2;
     }
 
-    Ok(cmd)
+    Ok(Some((cmd, i)))
 }
 
-fn parse_inline_request(s: &str) -> Result<Vec<Vec<String>>, ParseError> {
+/// Parses consecutive inline commands, one per line.
+///
+/// Only whole lines are consumed, so a command split across reads is parsed once its line is
+/// complete. Unless the buffer is whole, parsing also stops at a line opening a bulk command,
+/// which is left to be parsed as such.
+///
+/// # Parameters
+///
+/// - `buf`: The buffer to parse.
+/// - `whole`: Whether the buffer is complete, so that a last unterminated line is a command too.
+///
+/// # Returns
+///
+/// The complete commands, along with the number of bytes they took up.
+fn parse_inline_request(buf: &[u8], whole: bool) -> Result<(Vec<Vec<String>>, usize), ParseError> {
     let mut commands = Vec::new();
 
-    for line in s.lines() {
+    let mut i = 0;
+    while i < buf.len() {
+        if !whole && i > 0 && buf[i] == b'*' {
+            break;
+        }
+
+        let (line, next) = match buf[i..].iter().position(|&b| b == b'\n') {
+            Some(len) => (&buf[i..i + len], i + len + 1),
+            None if whole => (&buf[i..], buf.len()),
+            None => break,
+        };
+
         let mut args = Vec::new();
-        for arg in line.split_whitespace() {
+        for arg in std::str::from_utf8(line)?.split_whitespace() {
             args.push(arg.into());
         }
 
@@ -198,13 +297,10 @@ fn parse_inline_request(s: &str) -> Result<Vec<Vec<String>>, ParseError> {
         }
 
         commands.push(args);
+        i = next;
     }
 
-    if commands.is_empty() {
-        return Err(ParseError::EmptyRequest);
-    }
-
-    Ok(commands)
+    Ok((commands, i))
 }
 
 #[cfg(test)]
@@ -297,4 +393,99 @@ mod tests {
         let result = request_str.parse::<Request>();
         assert_eq!(result, Err(ParseError::InvalidRequest));
     }
+
+    #[test]
+    fn test_parse_incremental() {
+        let buf = b"*1\r\n$4\r\nping\r\n*2\r\n$4\r\necho\r\n$2\r\nli";
+        assert_eq!(
+            Request::parse(buf),
+            Ok(Parsed::Complete(
+                Request::new(vec![vec!["ping".into()]]),
+                14
+            ))
+        );
+        assert_eq!(Request::parse(&buf[14..]), Ok(Parsed::Incomplete));
+        assert_eq!(Request::parse(b"ping"), Ok(Parsed::Incomplete));
+        assert_eq!(Request::parse(b""), Ok(Parsed::Incomplete));
+    }
+
+    #[test]
+    fn test_parse_inline_incremental() {
+        assert_eq!(Request::parse(b"set fo"), Ok(Parsed::Incomplete));
+        assert_eq!(Request::parse(b"set foo bar\r"), Ok(Parsed::Incomplete));
+        assert_eq!(
+            Request::parse(b"set foo bar\r\nget fo"),
+            Ok(Parsed::Complete(
+                Request::new(vec![vec!["set".into(), "foo".into(), "bar".into()]]),
+                13
+            ))
+        );
+
+        // a bulk command after an inline one is left to be parsed as such
+        let buf = b"ping\r\n*1\r\n$4\r\nping\r\n";
+        assert_eq!(
+            Request::parse(buf),
+            Ok(Parsed::Complete(Request::new(vec![vec!["ping".into()]]), 6))
+        );
+        assert_eq!(
+            Request::parse(&buf[6..]),
+            Ok(Parsed::Complete(
+                Request::new(vec![vec!["ping".into()]]),
+                buf.len() - 6
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_multibyte_token() {
+        let request_str = "*2\r\n$4\r\necho\r\n$6\r\nhéllo\r\n";
+        let result = request_str.parse::<Request>().unwrap();
+        assert_eq!(
+            result,
+            Request::new(vec![vec!["echo".into(), "héllo".into()]])
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_utf8() {
+        let result = Request::parse(b"*1\r\n$1\r\n\xff\r\n");
+        assert!(matches!(result, Err(ParseError::Utf8(_))));
+    }
+
+    #[test]
+    fn test_parse_oversized_token_length() {
+        // a length that would wrap the end of the token around to its own length line
+        let result = Request::parse(b"*1\r\n$18446744073709551614\r\nab\r\n");
+        assert_eq!(result, Err(ParseError::InvalidTokenLength));
+
+        let result = Request::parse(format!("*1\r\n${}\r\n", PROTO_MAX_BULK_LEN + 1).as_bytes());
+        assert_eq!(result, Err(ParseError::InvalidTokenLength));
+        let result = Request::parse(format!("*1\r\n${PROTO_MAX_BULK_LEN}\r\nab").as_bytes());
+        assert_eq!(result, Ok(Parsed::Incomplete));
+    }
+
+    #[test]
+    fn test_parse_empty_and_null_arrays() {
+        let buf = b"*0\r\n*1\r\n$4\r\nping\r\n*-1\r\n*1\r\n$4\r\nping\r\n";
+        assert_eq!(
+            Request::parse(buf),
+            Ok(Parsed::Complete(
+                Request::new(vec![vec!["ping".into()], vec!["ping".into()]]),
+                buf.len()
+            ))
+        );
+
+        assert_eq!(
+            Request::parse(b"*0\r\n"),
+            Ok(Parsed::Complete(Request::new(Vec::new()), 4))
+        );
+        assert_eq!(
+            Request::parse(b"*-1\r\n*1\r\n$4\r\npi"),
+            Ok(Parsed::Complete(Request::new(Vec::new()), 5))
+        );
+        assert_eq!(
+            "*-1\r\n*0\r\n".parse::<Request>(),
+            Ok(Request::new(Vec::new()))
+        );
+    }
 }
@@ -0,0 +1,122 @@
+//! This module encodes responses into the RESP wire format, independently of how they are
+//! written out, so that replies and pushed pub/sub messages share a single encoder.
+use std::io::Write;
+
+use super::types::{single_line, Response, TERM};
+
+/// Appends the RESP encoding of a response to a buffer.
+///
+/// # Parameters
+///
+/// - `response`: The response to encode, whose arrays may nest to any depth.
+/// - `out`: The buffer to append the encoding to, as bytes since bulk strings may hold any.
+pub fn encode(response: &Response, out: &mut Vec<u8>) {
+    match response {
+        Response::SimpleString(s) => {
+            let _ = write!(out, "+{}{TERM}", single_line(s));
+        }
+        Response::BulkString(s) => {
+            let _ = write!(out, "${}{TERM}", s.len());
+            out.extend_from_slice(s);
+            out.extend_from_slice(TERM.as_bytes());
+        }
+        Response::Error(e) => {
+            let _ = write!(out, "{e}");
+        }
+        Response::Integer(i) => {
+            let _ = write!(out, ":{i}{TERM}");
+        }
+        Response::Null => {
+            let _ = write!(out, "$-1{TERM}");
+        }
+        Response::NullArray => {
+            let _ = write!(out, "*-1{TERM}");
+        }
+        Response::Array(elements) => {
+            let _ = write!(out, "*{}{TERM}", elements.len());
+            for element in elements {
+                encode(element, out);
+            }
+        }
+        // each reply is encoded in turn, without a header of its own
+        Response::Multi(replies) => {
+            for reply in replies {
+                encode(reply, out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded(response: &Response) -> String {
+        let mut out = Vec::new();
+        encode(response, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_encode_scalars() {
+        assert_eq!(encoded(&Response::ss("OK")), "+OK\r\n");
+        assert_eq!(encoded(&Response::bs("héllo")), "$6\r\nhéllo\r\n");
+        assert_eq!(encoded(&Response::bs("")), "$0\r\n\r\n");
+        assert_eq!(
+            encoded(&Response::err("WRONGTYPE", "wrong kind")),
+            "-WRONGTYPE wrong kind\r\n"
+        );
+        assert_eq!(encoded(&Response::err("", "oops")), "-ERR oops\r\n");
+        assert_eq!(encoded(&Response::Integer(-42)), ":-42\r\n");
+        assert_eq!(encoded(&Response::Null), "$-1\r\n");
+        assert_eq!(encoded(&Response::NullArray), "*-1\r\n");
+    }
+
+    #[test]
+    fn test_encode_binary_bulk_string() {
+        let mut out = Vec::new();
+        encode(&Response::BulkString(vec![0x80, 0xff]), &mut out);
+        assert_eq!(out, b"$2\r\n\x80\xff\r\n");
+    }
+
+    #[test]
+    fn test_encode_line_breaks() {
+        assert_eq!(encoded(&Response::ss("a\r\n+OK\r\nb")), "+a  +OK  b\r\n");
+        assert_eq!(
+            encoded(&Response::err("ERR", "unknown 'x\r\n+OK\r\nZ'")),
+            "-ERR unknown 'x  +OK  Z'\r\n"
+        );
+        assert_eq!(encoded(&Response::err("BAD\nKIND", "")), "-BAD KIND \r\n");
+    }
+
+    #[test]
+    fn test_encode_nested_arrays() {
+        let response = Response::Array(vec![
+            Response::Integer(1),
+            Response::Array(vec![
+                Response::bs("a"),
+                Response::Array(vec![]),
+                Response::Null,
+            ]),
+            Response::ss("end"),
+        ]);
+        assert_eq!(
+            encoded(&response),
+            "*3\r\n:1\r\n*3\r\n$1\r\na\r\n*0\r\n$-1\r\n+end\r\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_multi() {
+        let response = Response::Multi(vec![Response::Integer(1), Response::ss("OK")]);
+        assert_eq!(encoded(&response), ":1\r\n+OK\r\n");
+    }
+
+    #[test]
+    fn test_encode_appends() {
+        let mut out = b"+earlier\r\n".to_vec();
+        encode(&Response::Integer(1), &mut out);
+        assert_eq!(out, b"+earlier\r\n:1\r\n");
+        assert_eq!(Response::Integer(1).to_string(), ":1\r\n");
+    }
+}
@@ -4,21 +4,42 @@ use crate::command::{
     config::CommandBuildError,
     types::{ArgumentError, CommandError},
 };
+use crate::kvstore::WrongType;
+
+use super::encode::encode;
 use crate::request::types::ParseError;
 
-const TERM: &str = "\r\n";
+/// The terminator ending every line of the RESP wire format.
+pub const TERM: &str = "\r\n";
+
+/// Makes text safe to send on a single line, such as in a simple string or an error,
+/// by replacing any line breaks in it with spaces, like Redis does.
+///
+/// Otherwise, text echoed back from a client could end the line early and forge more replies.
+pub fn single_line(text: &str) -> String {
+    text.replace(['\r', '\n'], " ")
+}
 
 /// Represents the possible types of responses from a Redis server.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Response {
     /// Represents a simple string response.
     SimpleString(String),
+    /// Represents a binary-safe bulk string response, which may hold any bytes.
+    BulkString(Vec<u8>),
     /// Represents an error response.
     Error(RedisError),
+    /// Represents an integer response.
+    Integer(i64),
     /// Represents a null response.
     Null,
-    /// Represents an array response.
-    Array(Vec<String>),
+    /// Represents a null array response, sent in place of a missing array.
+    NullArray,
+    /// Represents an array response, whose elements may be responses of any type.
+    Array(Vec<Response>),
+    /// Represents several consecutive replies to a single command,
+    /// such as the per-channel confirmations of `SUBSCRIBE`.
+    Multi(Vec<Response>),
 }
 
 impl Response {
@@ -27,6 +48,11 @@ impl Response {
         Self::SimpleString(s.into())
     }
 
+    /// Creates a new `Response` object with a bulk string response.
+    pub fn bs(s: &str) -> Self {
+        Self::BulkString(s.into())
+    }
+
     /// Creates a new `Response` object with an error response.
     pub fn err(kind: &str, message: &str) -> Self {
         Self::Error(RedisError::new(kind, message))
@@ -37,27 +63,25 @@ impl Response {
         Self::Error(RedisError::new("ERR", e.to_string().as_str()))
     }
 
-    /// Creates a new `Response` object with an array response.
+    /// Creates a new `Response` object with an array response of bulk strings.
     pub fn arr(arr: Vec<String>) -> Self {
-        Self::Array(arr)
+        Self::Array(
+            arr.into_iter()
+                .map(|s| Self::BulkString(s.into()))
+                .collect(),
+        )
     }
 }
 
 impl fmt::Display for Response {
-    /// Formats the `Response` object as a string.
+    /// Formats the `Response` object as a string, in the RESP wire format.
+    ///
+    /// Bulk strings that are not valid UTF-8 have their invalid bytes replaced with `U+FFFD`,
+    /// so replies are written to clients with `encode` instead.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::SimpleString(s) => write!(f, "+{s}{TERM}"),
-            Self::Error(e) => write!(f, "{e}"),
-            Self::Null => write!(f, "$-1{TERM}"),
-            Self::Array(arr) => {
-                let mut res = format!("*{}{TERM}", arr.len());
-                for s in arr {
-                    res.push_str(&format!("${}{TERM}{s}{TERM}", s.len()));
-                }
-                write!(f, "{res}")
-            }
-        }
+        let mut out = Vec::new();
+        encode(self, &mut out);
+        f.write_str(&String::from_utf8_lossy(&out))
     }
 }
 
@@ -92,8 +116,17 @@ impl From<CommandBuildError> for Response {
     }
 }
 
+impl From<WrongType> for Response {
+    fn from(_: WrongType) -> Self {
+        Self::err(
+            "WRONGTYPE",
+            "Operation against a key holding the wrong kind of value",
+        )
+    }
+}
+
 /// Represents an error returned by a Redis server.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RedisError {
     kind: String,
     message: String,
@@ -104,11 +137,10 @@ impl RedisError {
     pub fn new(kind: &str, message: &str) -> Self {
         Self {
             kind: match kind {
-                "" => "ERR",
-                _ => kind,
-            }
-            .into(),
-            message: message.into(),
+                "" => "ERR".into(),
+                _ => single_line(kind),
+            },
+            message: single_line(message),
         }
     }
 }
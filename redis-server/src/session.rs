@@ -0,0 +1,284 @@
+use dashmap::DashMap;
+use std::{
+    collections::HashSet,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, LazyLock,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc::Sender, Notify};
+
+use crate::{
+    command::types::Command,
+    kvstore::{KvStore, DATABASES},
+    pubsub::PUBSUB,
+    response::types::Response,
+};
+
+/// The source of unique client connection ids.
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Describes a live client connection, as reported by `CLIENT LIST`.
+struct ClientInfo {
+    name: Option<String>,
+    connected_at: Instant,
+    killed: Arc<Notify>,
+}
+
+/// The registry of live client connections, keyed by client id.
+static CLIENTS: LazyLock<DashMap<u64, ClientInfo>> = LazyLock::new(DashMap::new);
+
+/// Represents the commands queued since `MULTI`, to be executed together by `EXEC`.
+pub struct Transaction {
+    pub commands: Vec<Command>,
+    /// Whether a command failed to be queued, in which case `EXEC` discards the transaction.
+    pub aborted: bool,
+}
+
+/// Represents the state of a single client connection.
+pub struct Session {
+    id: u64,
+    sender: Sender<Response>,
+    channels: HashSet<String>,
+    patterns: HashSet<String>,
+    delay: Duration,
+    transaction: Option<Transaction>,
+    /// The database the client's commands are executed against, chosen with `SELECT`.
+    db: usize,
+}
+
+impl Session {
+    /// Creates a new `Session` with a unique id.
+    ///
+    /// # Parameters
+    ///
+    /// - `sender`: The channel over which messages are pushed to the client,
+    ///   whose capacity bounds the messages that may be pending delivery.
+    pub fn new(sender: Sender<Response>) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        CLIENTS.insert(
+            id,
+            ClientInfo {
+                name: None,
+                connected_at: Instant::now(),
+                killed: Arc::new(Notify::new()),
+            },
+        );
+
+        Self {
+            id,
+            sender,
+            channels: HashSet::new(),
+            patterns: HashSet::new(),
+            delay: Duration::ZERO,
+            transaction: None,
+            db: 0,
+        }
+    }
+
+    /// Returns the unique id of the client.
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the name the client has given its connection, if any.
+    pub fn name(&self) -> Option<String> {
+        CLIENTS.get(&self.id).and_then(|client| client.name.clone())
+    }
+
+    /// Names the client's connection, or clears its name if `name` is empty.
+    pub fn set_name(&self, name: &str) {
+        if let Some(mut client) = CLIENTS.get_mut(&self.id) {
+            client.name = (!name.is_empty()).then(|| name.into());
+        }
+    }
+
+    /// Returns the signal that the server has decided to disconnect the client.
+    pub fn killed(&self) -> Arc<Notify> {
+        CLIENTS.get(&self.id).map_or_else(
+            || Arc::new(Notify::new()),
+            |client| Arc::clone(&client.killed),
+        )
+    }
+
+    /// Returns the index of the database the client has selected.
+    pub const fn db(&self) -> usize {
+        self.db
+    }
+
+    /// Looks up the store of the client's database, as it is at the moment.
+    ///
+    /// A command looks it up once, so that it acts on a single store even if `SWAPDB` runs
+    /// meanwhile.
+    pub fn store(&self) -> &'static KvStore {
+        DATABASES.get(self.db)
+    }
+
+    /// Switches the client to another database, starting with the command executed next.
+    pub const fn select(&mut self, db: usize) {
+        self.db = db;
+    }
+
+    /// Holds back the client's pending responses for a while longer, such as for `DEBUG SLEEP`.
+    pub fn delay(&mut self, duration: Duration) {
+        self.delay = self.delay.saturating_add(duration);
+    }
+
+    /// Returns how long the client's pending responses are to be held back, and resets it.
+    pub fn take_delay(&mut self) -> Duration {
+        std::mem::take(&mut self.delay)
+    }
+
+    /// Checks whether the client has started a transaction with `MULTI`.
+    pub const fn in_transaction(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    /// Starts queuing the client's commands instead of executing them.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the client is already in a transaction.
+    pub fn begin_transaction(&mut self) -> bool {
+        if self.in_transaction() {
+            return false;
+        }
+
+        self.transaction = Some(Transaction {
+            commands: Vec::new(),
+            aborted: false,
+        });
+        true
+    }
+
+    /// Queues a command in the client's transaction, if it has started one.
+    pub fn queue(&mut self, command: Command) {
+        if let Some(transaction) = self.transaction.as_mut() {
+            transaction.commands.push(command);
+        }
+    }
+
+    /// Marks the client's transaction to be discarded by `EXEC`, if it has started one.
+    pub fn abort_transaction(&mut self) {
+        if let Some(transaction) = self.transaction.as_mut() {
+            transaction.aborted = true;
+        }
+    }
+
+    /// Ends the client's transaction, returning it if it had started one.
+    pub fn take_transaction(&mut self) -> Option<Transaction> {
+        self.transaction.take()
+    }
+
+    /// Returns the number of channels and patterns the client is subscribed to.
+    pub fn subscription_count(&self) -> usize {
+        self.channels.len() + self.patterns.len()
+    }
+
+    /// Returns the channels the client is subscribed to.
+    pub fn channels(&self) -> Vec<String> {
+        self.channels.iter().cloned().collect()
+    }
+
+    /// Returns the patterns the client is subscribed to.
+    pub fn patterns(&self) -> Vec<String> {
+        self.patterns.iter().cloned().collect()
+    }
+
+    /// Subscribes the client to a channel.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscriptions the client holds afterwards.
+    pub fn subscribe(&mut self, channel: &str) -> usize {
+        if self.channels.insert(channel.into()) {
+            PUBSUB.subscribe(channel, self.id, &self.sender);
+        }
+        self.subscription_count()
+    }
+
+    /// Unsubscribes the client from a channel.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscriptions the client holds afterwards.
+    pub fn unsubscribe(&mut self, channel: &str) -> usize {
+        if self.channels.remove(channel) {
+            PUBSUB.unsubscribe(channel, self.id);
+        }
+        self.subscription_count()
+    }
+
+    /// Subscribes the client to all channels matching a glob pattern.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscriptions the client holds afterwards.
+    pub fn psubscribe(&mut self, pattern: &str) -> usize {
+        if self.patterns.insert(pattern.into()) {
+            PUBSUB.psubscribe(pattern, self.id, &self.sender);
+        }
+        self.subscription_count()
+    }
+
+    /// Unsubscribes the client from a glob pattern.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscriptions the client holds afterwards.
+    pub fn punsubscribe(&mut self, pattern: &str) -> usize {
+        if self.patterns.remove(pattern) {
+            PUBSUB.punsubscribe(pattern, self.id);
+        }
+        self.subscription_count()
+    }
+}
+
+/// Releases the client's subscriptions when the connection goes away.
+impl Drop for Session {
+    fn drop(&mut self) {
+        CLIENTS.remove(&self.id);
+        for channel in &self.channels {
+            PUBSUB.unsubscribe(channel, self.id);
+        }
+        for pattern in &self.patterns {
+            PUBSUB.punsubscribe(pattern, self.id);
+        }
+    }
+}
+
+/// Signals a client's connection to close, such as when it cannot keep up with its messages.
+///
+/// # Returns
+///
+/// `true` if the client was connected, `false` otherwise.
+pub fn kill(id: u64) -> bool {
+    CLIENTS
+        .get(&id)
+        .map(|client| client.killed.notify_one())
+        .is_some()
+}
+
+/// Describes every live client connection, one line per connection, ordered by id.
+pub fn client_list() -> String {
+    let mut clients: Vec<_> = CLIENTS
+        .iter()
+        .map(|client| {
+            (
+                *client.key(),
+                client.name.clone().unwrap_or_default(),
+                client.connected_at.elapsed().as_secs(),
+            )
+        })
+        .collect();
+    clients.sort_unstable();
+
+    clients
+        .into_iter()
+        .fold(String::new(), |mut list, (id, name, age)| {
+            let _ = writeln!(list, "id={id} name={name} age={age}");
+            list
+        })
+}
@@ -0,0 +1,296 @@
+//! Counts the lines, words, characters and bytes of an input, and the width of its longest line,
+//! the way GNU wc does.
+
+use std::io;
+use std::ops::AddAssign;
+
+/// The counts of an input, or the totals of several inputs.
+#[derive(Debug, Default, PartialEq)]
+pub struct Counts {
+    /// The number of newlines, so a last line without one is not counted.
+    pub lines: usize,
+    pub words: usize,
+    pub bytes: usize,
+    /// The number of valid UTF-8 characters.
+    pub chars: usize,
+    /// The display width of the longest line.
+    pub max_line_length: usize,
+}
+
+impl AddAssign for Counts {
+    fn add_assign(&mut self, other: Counts) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.bytes += other.bytes;
+        self.chars += other.chars;
+        // the longest line of all inputs, as it is not a sum
+        self.max_line_length = self.max_line_length.max(other.max_line_length);
+    }
+}
+
+/// Counts everything a reader yields, one buffered chunk at a time.
+///
+/// A character split across two chunks is decoded with the rest of its bytes,
+/// and words and lines running on from one chunk to the next are counted once.
+///
+/// Bytes and lines are counted on the raw bytes, so input that is not valid UTF-8 is counted too.
+/// Like GNU wc, invalid sequences are skipped otherwise: they are not characters, nor part
+/// of words, and take up no width.
+///
+/// # Errors
+///
+/// Fails if reading fails.
+pub fn count_reader<R: io::BufRead>(reader: &mut R) -> io::Result<Counts> {
+    let mut counter = Counter::default();
+    loop {
+        let chunk = match reader.fill_buf() {
+            Ok(chunk) => chunk,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if chunk.is_empty() {
+            break;
+        }
+
+        counter.update(chunk);
+        let len = chunk.len();
+        reader.consume(len);
+    }
+
+    Ok(counter.finish())
+}
+
+/// Counts an input fed one chunk at a time, keeping what a chunk leaves in progress.
+#[derive(Default)]
+struct Counter {
+    counts: Counts,
+    /// The leading bytes of a character left incomplete by the previous chunk.
+    pending: Vec<u8>,
+    in_word: bool,
+    line_len: usize,
+}
+
+impl Counter {
+    fn update(&mut self, mut chunk: &[u8]) {
+        // complete the character split across chunks first, one byte at a time
+        while !self.pending.is_empty() {
+            let Some((byte, rest)) = chunk.split_first() else {
+                return;
+            };
+            self.pending.push(*byte);
+            chunk = rest;
+
+            match std::str::from_utf8(&self.pending) {
+                Err(e) if e.error_len().is_none() => {},
+                // either a whole character, or an invalid sequence that no more bytes can fix
+                _ => {
+                    let pending = std::mem::take(&mut self.pending);
+                    self.count(&pending);
+                },
+            }
+        }
+
+        let incomplete = incomplete_suffix_len(chunk);
+        let (complete, incomplete) = chunk.split_at(chunk.len() - incomplete);
+        self.count(complete);
+        self.pending.extend_from_slice(incomplete);
+    }
+
+    /// Counts a chunk that ends on a character boundary, or at the end of the input.
+    fn count(&mut self, chunk: &[u8]) {
+        self.counts.lines += count_lines(chunk);
+        self.counts.bytes += chunk.len();
+        for text in chunk.utf8_chunks() {
+            let valid = text.valid();
+            self.counts.words += count_words(valid, &mut self.in_word);
+            self.counts.chars += count_chars(valid);
+            let max_line_length = count_max_line_length(valid, &mut self.line_len);
+            self.counts.max_line_length = self.counts.max_line_length.max(max_line_length);
+        }
+    }
+
+    fn finish(mut self) -> Counts {
+        // at the end of the input, an incomplete character is as invalid as any other sequence
+        let pending = std::mem::take(&mut self.pending);
+        self.count(&pending);
+
+        self.counts
+    }
+}
+
+/// Measures the trailing bytes of a chunk that start a character the next chunk may complete.
+fn incomplete_suffix_len(bytes: &[u8]) -> usize {
+    let Some(text) = bytes.utf8_chunks().last() else {
+        return 0;
+    };
+
+    match std::str::from_utf8(text.invalid()) {
+        Err(e) if e.error_len().is_none() => text.invalid().len(),
+        _ => 0,
+    }
+}
+
+/// Counts the newlines of a chunk, like GNU wc, so a last line without one is not counted.
+fn count_lines(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|byte| **byte == b'\n').count()
+}
+
+/// Counts the words of a chunk, where `in_word` tells whether the previous chunk ended partway
+/// through a word, already counted, and is updated for the next chunk.
+fn count_words(s: &str, in_word: &mut bool) -> usize {
+    if s.is_empty() {
+        return 0;
+    }
+
+    let mut words = s.split_whitespace().count();
+    if *in_word && !s.starts_with(char::is_whitespace) {
+        words -= 1;
+    }
+    *in_word = !s.ends_with(char::is_whitespace);
+
+    words
+}
+
+/// Measures the display width of the longest line of a chunk, where `line_len` is the width
+/// of the line left in progress by the previous chunk, and is updated for the next chunk.
+///
+/// Like GNU wc, tabs advance to the next multiple of 8, carriage returns and form feeds return
+/// to the start of the line, and other control characters take no space.
+/// Every other character is taken to be one column wide.
+fn count_max_line_length(s: &str, line_len: &mut usize) -> usize {
+    let mut max_line_length = *line_len;
+    for c in s.chars() {
+        match c {
+            '\n' | '\r' | '\x0c' => *line_len = 0,
+            '\t' => *line_len += 8 - *line_len % 8,
+            c if c.is_control() => {},
+            _ => *line_len += 1,
+        }
+        max_line_length = max_line_length.max(*line_len);
+    }
+
+    max_line_length
+}
+
+fn count_chars(s: &str) -> usize {
+    s.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    /// Counts some bytes, read `capacity` bytes at a time.
+    fn count_in_chunks(bytes: &[u8], capacity: usize) -> Counts {
+        count_reader(&mut BufReader::with_capacity(capacity, bytes)).unwrap()
+    }
+
+    #[test]
+    fn test_count_reader() {
+        let counts = count_in_chunks(b"one two\n\tthree\n", 8 * 1024);
+        let expected = Counts {
+            lines: 2,
+            words: 3,
+            bytes: 15,
+            chars: 15,
+            max_line_length: 13,
+        };
+        assert_eq!(counts, expected);
+        assert_eq!(count_in_chunks(b"", 8 * 1024), Counts::default());
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut total = count_in_chunks(b"a much longer line\n", 4);
+        total += count_in_chunks(b"short\nlines\n", 4);
+
+        let expected = Counts {
+            lines: 3,
+            words: 6,
+            bytes: 31,
+            chars: 31,
+            max_line_length: 18,
+        };
+        assert_eq!(total, expected);
+    }
+
+    #[test]
+    fn test_count_multi_byte_chars_across_reads() {
+        // two-byte characters, read five bytes at a time, land each boundary mid-character
+        let text = "é".repeat(999);
+        let counts = count_in_chunks(text.as_bytes(), 5);
+
+        assert_eq!(counts.chars, 999);
+        assert_eq!(counts.bytes, 1998);
+        // a four-byte character split over three chunks
+        assert_eq!(count_in_chunks("a🦀b".as_bytes(), 2).chars, 3);
+    }
+
+    #[test]
+    fn test_count_words_and_lines_across_reads() {
+        let text = "hello world\nthe quick brown fox\n  spaced   out   words  \n\nlast line\n";
+
+        // as counted by GNU wc
+        let expected = Counts {
+            lines: 5,
+            words: 11,
+            bytes: 68,
+            chars: 68,
+            max_line_length: 24,
+        };
+        for capacity in [1, 4, 5, 7, 16, 8 * 1024] {
+            assert_eq!(count_in_chunks(text.as_bytes(), capacity), expected);
+        }
+    }
+
+    #[test]
+    fn test_count_lines_trailing_newline() {
+        assert_eq!(count_in_chunks(b"one\ntwo\n", 5).lines, 2);
+        assert_eq!(count_in_chunks(b"one\ntwo", 5).lines, 1);
+    }
+
+    #[test]
+    fn test_count_incomplete_char_at_end() {
+        let counts = count_in_chunks(b"ab\xC3", 4);
+        assert_eq!(counts.bytes, 3);
+        assert_eq!(counts.chars, 2);
+        assert_eq!(counts.words, 1);
+    }
+
+    #[test]
+    fn test_count_invalid_utf8() {
+        let counts = count_in_chunks(b"caf\xC3\xA9 \xFF\xFEbinary\n\xFF \xFF\n", 4);
+
+        // as counted by GNU wc
+        let expected = Counts {
+            lines: 2,
+            words: 2,
+            bytes: 19,
+            chars: 14,
+            max_line_length: 11,
+        };
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn test_count_max_line_length() {
+        let text = "short\na much longer line, read in small chunks\n\nmid-length line\nend";
+        for capacity in [4, 7, 8 * 1024] {
+            assert_eq!(count_in_chunks(text.as_bytes(), capacity).max_line_length, 40);
+        }
+
+        // the last line counts even without a trailing newline
+        assert_eq!(count_in_chunks(b"ab\nabcdef", 4).max_line_length, 6);
+    }
+
+    #[test]
+    fn test_count_max_line_length_tabs() {
+        let mut line_len = 0;
+        assert_eq!(count_max_line_length("\tx\n", &mut line_len), 9);
+        assert_eq!(count_max_line_length("abc\tx\n", &mut line_len), 9);
+        assert_eq!(count_max_line_length("12345678\t\n", &mut line_len), 16);
+        assert_eq!(count_max_line_length("a\tb\tc", &mut line_len), 17);
+        assert_eq!(line_len, 17);
+    }
+}
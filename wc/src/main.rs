@@ -1,141 +1,728 @@
 use std::env;
 use std::error;
 use std::fs;
-use std::io;
+use std::io::{self, Read, Write};
+use std::panic;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 
-enum Options {
-    All,
-    Lines,
-    Words,
-    Bytes,
-    Chars,
+use wc::{count_reader, Counts};
+
+/// The counts enabled by the flags, each printed as a column, and how to count and print them.
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    lines: bool,
+    words: bool,
+    chars: bool,
+    bytes: bool,
+    max_line_length: bool,
+    /// Whether to count several files at once, on as many threads as can run at once.
+    parallel: bool,
+    /// Whether to leave out the file name column, including the `total` label.
+    no_filename: bool,
+    total: Total,
+}
+
+/// When to print a `total` row, as set by `--total=WHEN`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum Total {
+    /// Only when there is more than one input.
+    #[default]
+    Auto,
+    Always,
+    /// Instead of the row of each input, and without the `total` label.
+    Only,
+    Never,
+}
+
+impl Total {
+    /// Whether a `total` row is printed after the rows of some number of inputs.
+    fn shows(self, inputs: usize) -> bool {
+        match self {
+            Total::Auto => inputs > 1,
+            Total::Always | Total::Only => true,
+            Total::Never => false,
+        }
+    }
 }
 
 impl Options {
-    fn from_str(s: &str) -> Options {
-        match s {
-            "l" => Options::Lines,
-            "w" => Options::Words,
-            "c" => Options::Bytes,
-            "m" => Options::Chars,
-            _ => Options::All,
+    /// Every count but the maximum line length, as when no flag is given.
+    fn all() -> Options {
+        Options {
+            lines: true,
+            words: true,
+            chars: true,
+            bytes: true,
+            max_line_length: false,
+            parallel: false,
+            no_filename: false,
+            total: Total::Auto,
         }
     }
+
+    /// Whether no count is enabled.
+    fn counts_nothing(&self) -> bool {
+        !(self.lines || self.words || self.chars || self.bytes || self.max_line_length)
+    }
+
+    /// Enables the counts of a cluster of short flags without its leading dash, such as `lw`.
+    fn enable(&mut self, flags: &str) -> Result<(), String> {
+        for flag in flags.chars() {
+            match flag {
+                'l' => self.lines = true,
+                'w' => self.words = true,
+                'm' => self.chars = true,
+                'c' => self.bytes = true,
+                'L' => self.max_line_length = true,
+                'h' => self.no_filename = true,
+                _ => return Err(format!("invalid option -- '{}'", flag)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enables the count of a long option without its leading dashes, such as `words`.
+    fn enable_long(&mut self, option: &str) -> Result<(), String> {
+        match option {
+            "lines" => self.lines = true,
+            "words" => self.words = true,
+            "chars" => self.chars = true,
+            "bytes" => self.bytes = true,
+            "max-line-length" => self.max_line_length = true,
+            "parallel" => self.parallel = true,
+            "no-filename" => self.no_filename = true,
+            _ => {
+                let Some(when) = option.strip_prefix("total=") else {
+                    return Err(format!("unrecognized option '--{}'", option));
+                };
+                self.total = match when {
+                    "auto" => Total::Auto,
+                    "always" => Total::Always,
+                    "only" => Total::Only,
+                    "never" => Total::Never,
+                    _ => return Err(format!("invalid argument '{}' for '--total'", when)),
+                };
+            },
+        }
+
+        Ok(())
+    }
 }
 
-// TODO: fix incorrect counts with smaller buffers; likely due to splitting multi-byte characters
-const BUF_LEN: usize = 1_024_000;
+/// What the arguments ask of wc.
+#[derive(Debug, PartialEq)]
+enum Action {
+    Count(Options, Vec<String>),
+    /// Counts the files named in a list, itself named by the second field.
+    CountFrom(Options, String),
+    Help,
+    Version,
+}
 
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let args: Vec<String> = env::args().collect();
-    let options: Options;
-    let prefix_flag = args.len() > 1 && &args[1][..1] == "-";
+const USAGE: &str = "\
+Usage: wc [OPTION]... [FILE]...
+Print line, word, and byte counts for each FILE, and a total line if more than one FILE is given.
+With no FILE, read standard input.
 
-    let mut reader_buffered: Box<dyn io::BufRead>;
-    let stdin = io::stdin().lock();
+  -c, --bytes            print the byte counts
+  -m, --chars            print the character counts
+  -l, --lines            print the newline counts
+  -L, --max-line-length  print the maximum display width
+  -w, --words            print the word counts
+      --files0-from=F    read input from the files specified by
+                           NUL-terminated names in file F;
+                           If F is - then read names from standard input
+      --parallel         count several files at once, one per CPU
+  -h, --no-filename      print the counts without the file names
+      --total=WHEN       when to print a line with total counts;
+                           WHEN can be: auto, always, only, never
+      --help             display this help and exit
+      --version          output version information and exit";
 
-    match args.len() {
-        1 => {
-            options = Options::All;
-            reader_buffered = Box::new(create_buffered_reader(BUF_LEN, stdin));
+const BUF_LEN: usize = 1_024_000;
+
+/// The file name standing for stdin.
+const STDIN: &str = "-";
+
+fn main() -> Result<(), Box<dyn error::Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (options, file_names, listed) = match parse_args(&args) {
+        Ok(Action::Count(options, file_names)) => (options, file_names, false),
+        Ok(Action::CountFrom(options, list)) => match read_file_list(&list) {
+            Ok(file_names) => (options, file_names, true),
+            Err(e) => {
+                eprintln!("wc: cannot open '{}' for reading: {}", list, e);
+                process::exit(1);
+            },
         },
-        2 => {
-            if prefix_flag {
-                options = Options::All;
-                reader_buffered = Box::new(create_buffered_reader(BUF_LEN, stdin));
-            } else {
-                options = Options::All;
-                let file = &args[1];
-                reader_buffered = Box::new(create_buffered_reader(BUF_LEN, fs::File::open(file)?));
-            }
+        Ok(Action::Help) => {
+            println!("{}", USAGE);
+            return Ok(());
+        },
+        Ok(Action::Version) => {
+            println!("wc {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
         },
-        3 => {
-            options = Options::from_str(&args[1][1..]);
-            let file = &args[2];
-            reader_buffered = Box::new(create_buffered_reader(BUF_LEN, fs::File::open(file)?));
+        Err(e) => {
+            eprintln!("wc: {}\nTry 'wc --help' for more information.", e);
+            process::exit(1);
         },
-        _ => return Err(From::from("invalid arguments")),
+    };
+    let file_names = &file_names[..];
+
+    let mut stdout = io::stdout().lock();
+    // an empty list counts nothing, rather than stdin
+    if file_names.is_empty() && !listed {
+        let counts = count_reader(&mut create_buffered_reader(BUF_LEN, io::stdin().lock()))?;
+        if options.total != Total::Only {
+            writeln!(stdout, "{}", format_row(&counts, None, &options))?;
+        }
+        write_total(&mut stdout, &counts, 1, &options)?;
+    } else if !count_files(file_names, &options, &mut io::stdin().lock(), &mut stdout)? {
+        process::exit(1);
     }
 
-    match process(&mut reader_buffered, options) {
-        Ok(_) => {},
-        Err(e) => eprintln!("{}", e),
+    Ok(())
+}
+
+/// Parses the arguments into the enabled counts and the file names, or a request for help
+/// or the version.
+///
+/// Short flags may be combined, as in `-lw`, or repeated, as in `-l -w`, and each has a long
+/// alias such as `--lines`. Every count is enabled when none is given.
+/// Arguments after `--` are file names, even if they start with a dash, and `-` is always
+/// a file name, standing for stdin.
+///
+/// With `--files0-from=F`, or `--files0-from F`, the file names are read from `F` instead,
+/// so none may be given as arguments.
+fn parse_args(args: &[String]) -> Result<Action, String> {
+    let mut options = Options::default();
+    let mut file_names = Vec::new();
+    let mut files0_from = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            file_names.extend(args.by_ref().cloned());
+        } else if let Some(list) = arg.strip_prefix("--files0-from=") {
+            files0_from = Some(list.to_string());
+        } else if let Some(option) = arg.strip_prefix("--") {
+            match option {
+                "help" => return Ok(Action::Help),
+                "version" => return Ok(Action::Version),
+                "files0-from" => {
+                    let list = args.next().ok_or("option '--files0-from' requires an argument")?;
+                    files0_from = Some(list.to_string());
+                },
+                _ => options.enable_long(option)?,
+            }
+        } else if let Some(flags) = arg.strip_prefix('-').filter(|flags| !flags.is_empty()) {
+            options.enable(flags)?;
+        } else {
+            file_names.push(arg.to_string());
+        }
+    }
+
+    if options.counts_nothing() {
+        options = Options {
+            parallel: options.parallel,
+            no_filename: options.no_filename,
+            total: options.total,
+            ..Options::all()
+        };
     }
 
-    if (args.len() == 2 && !prefix_flag) || args.len() == 3 {
-        println!(" {}", &args[args.len() - 1]);
+    match (files0_from, file_names.first()) {
+        (Some(_), Some(file_name)) => Err(format!(
+            "extra operand '{}'\nfile operands cannot be combined with --files0-from",
+            file_name
+        )),
+        (Some(list), None) => Ok(Action::CountFrom(options, list)),
+        (None, _) => Ok(Action::Count(options, file_names)),
+    }
+}
+
+/// Reads the NUL-separated file names of a list, which may be `-` for stdin.
+fn read_file_list(list: &str) -> io::Result<Vec<String>> {
+    let mut bytes = Vec::new();
+    if list == STDIN {
+        io::stdin().lock().read_to_end(&mut bytes)?;
     } else {
-        println!();
+        fs::File::open(list)?.read_to_end(&mut bytes)?;
     }
 
-    Ok(())
+    Ok(split_file_list(&bytes))
 }
 
-fn process(
-    reader: &mut Box<dyn io::BufRead>,
-    options: Options,
-) -> Result<(), Box<dyn error::Error>> {
-    let mut lines = 0;
-    let mut words = 0;
-    let mut bytes = 0;
-    let mut chars = 0;
-
-    let mut buf = [0; BUF_LEN];
-    loop {
-        let n = reader.read(&mut buf)?;
-        if n == 0 {
-            break;
-        }
+/// Counts files on as many worker threads as can run at once, each taking the next file
+/// no other has taken, so that only that many files are open at a time, however many there are.
+/// Stdin is counted on the calling thread meanwhile.
+///
+/// Returns the counts in the order of the file names.
+fn count_files_parallel(
+    file_names: &[String],
+    mut count_stdin: impl FnMut() -> io::Result<Counts>,
+) -> Vec<io::Result<Counts>> {
+    let workers = thread::available_parallelism().map_or(1, usize::from).min(file_names.len());
+    let next = AtomicUsize::new(0);
 
-        let slice = std::str::from_utf8(&buf[..n])?;
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut counted = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        match file_names.get(index) {
+                            Some(file_name) if file_name == STDIN => {},
+                            Some(file_name) => counted.push((index, count_file(file_name))),
+                            None => return counted,
+                        }
+                    }
+                })
+            })
+            .collect();
 
-        match options {
-            Options::All => {
-                bytes += n;
-                lines += count_lines(slice);
-                words += count_words(slice);
-                chars += count_chars(slice);
+        let mut all_counts: Vec<Option<io::Result<Counts>>> = file_names
+            .iter()
+            .map(|file_name| (file_name == STDIN).then(&mut count_stdin))
+            .collect();
+        for handle in handles {
+            let counted = handle.join().unwrap_or_else(|e| panic::resume_unwind(e));
+            for (index, counts) in counted {
+                all_counts[index] = Some(counts);
             }
-            Options::Lines => lines += count_lines(slice),
-            Options::Words => words += count_words(slice),
-            Options::Bytes => bytes += n,
-            Options::Chars => chars += count_chars(slice),
         }
+
+        all_counts
+            .into_iter()
+            .map(|counts| counts.expect("every file is counted by a worker"))
+            .collect()
+    })
+}
+
+/// Splits a list of file names, each ended or separated by a NUL.
+fn split_file_list(bytes: &[u8]) -> Vec<String> {
+    if bytes.is_empty() {
+        return Vec::new();
     }
 
-    match options {
-        Options::All => {
-            print!("{:>8}{:>8}{:>8}{:>8}", lines, words, bytes, chars);
-        },
-        Options::Lines => {
-            print!("{:>8}", lines);
-        },
-        Options::Words => {
-            print!("{:>8}", words);
-        },
-        Options::Bytes => {
-            print!("{:>8}", bytes);
-        },
-        Options::Chars => {
-            print!("{:>8}", chars);
-        },
+    bytes
+        .strip_suffix(b"\0")
+        .unwrap_or(bytes)
+        .split(|byte| *byte == 0)
+        .map(|file_name| String::from_utf8_lossy(file_name).into_owned())
+        .collect()
+}
+
+/// Counts each file and writes a row of its counts and name, followed by a `total` row
+/// when there are several files, or as `--total` says otherwise.
+///
+/// The file name `-` stands for stdin.
+/// A file that cannot be counted is reported on stderr, and the rest are still counted.
+/// Returns whether every file was counted.
+///
+/// In parallel, the files are counted by `count_files_parallel`, but the rows are still
+/// written in the order of the file names.
+fn count_files(
+    file_names: &[String],
+    options: &Options,
+    stdin: &mut impl io::Read,
+    output: &mut impl Write,
+) -> io::Result<bool> {
+    let mut count_stdin = || count_reader(&mut create_buffered_reader(BUF_LEN, &mut *stdin));
+
+    let all_counts: Vec<io::Result<Counts>> = if options.parallel {
+        count_files_parallel(file_names, count_stdin)
+    } else {
+        file_names
+            .iter()
+            .map(|file_name| {
+                if file_name == STDIN {
+                    count_stdin()
+                } else {
+                    count_file(file_name)
+                }
+            })
+            .collect()
+    };
+
+    let mut total = Counts::default();
+    let mut counted_all = true;
+
+    for (file_name, counts) in file_names.iter().zip(all_counts) {
+        match counts {
+            Ok(counts) => {
+                if options.total != Total::Only {
+                    writeln!(output, "{}", format_row(&counts, Some(file_name), options))?;
+                }
+                total += counts;
+            },
+            Err(e) => {
+                eprintln!("wc: {}: {}", file_name, e);
+                counted_all = false;
+            },
+        }
     }
 
-    Ok(())
+    write_total(output, &total, file_names.len(), options)?;
+
+    Ok(counted_all)
+}
+
+/// Writes the `total` row of some number of inputs, if `--total` says it is printed.
+fn write_total(
+    output: &mut impl Write,
+    total: &Counts,
+    inputs: usize,
+    options: &Options,
+) -> io::Result<()> {
+    if !options.total.shows(inputs) {
+        return Ok(());
+    }
+
+    let label = (options.total != Total::Only).then_some("total");
+    writeln!(output, "{}", format_row(total, label, options))
 }
 
-fn count_lines(s: &str) -> usize {
-    s.lines().count()
+/// Counts a file other than stdin.
+fn count_file(file_name: &str) -> io::Result<Counts> {
+    // only a list of file names can hold an empty one
+    if file_name.is_empty() {
+        let message = "invalid zero-length file name";
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+    }
+
+    let file = fs::File::open(file_name)?;
+    count_reader(&mut create_buffered_reader(BUF_LEN, file))
 }
 
-fn count_words(s: &str) -> usize {
-    s.split_whitespace().count()
+/// Formats the enabled counts as right-aligned columns, in the order GNU wc prints them:
+/// lines, words, chars, bytes, then the maximum line length.
+fn format_counts(counts: &Counts, options: &Options) -> String {
+    let columns = [
+        (options.lines, counts.lines),
+        (options.words, counts.words),
+        (options.chars, counts.chars),
+        (options.bytes, counts.bytes),
+        (options.max_line_length, counts.max_line_length),
+    ];
+
+    columns
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, count)| format!("{:>8}", count))
+        .collect()
 }
 
-fn count_chars(s: &str) -> usize {
-    s.chars().count()
+/// Formats a row of output: the enabled counts, then the label of their input, such as
+/// a file name or `total`, unless there is none, as for stdin read without a file name,
+/// or file names are turned off.
+fn format_row(counts: &Counts, label: Option<&str>, options: &Options) -> String {
+    match label.filter(|_| !options.no_filename) {
+        Some(label) => format!("{} {}", format_counts(counts, options), label),
+        None => format_counts(counts, options),
+    }
 }
 
 fn create_buffered_reader<R: io::Read>(capacity: usize, input: R) -> impl io::BufRead {
     io::BufReader::with_capacity(capacity, input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Creates an empty directory in the system's temporary directory for a test to use alone,
+    /// named after the test and the process so that concurrent runs do not collide.
+    fn unique_temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("wc-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn test_count_files_total() {
+        let dir = unique_temp_dir("total");
+        let first = dir.join("first.txt");
+        let second = dir.join("second.txt");
+        fs::write(&first, "one two\nthree\n").unwrap();
+        fs::write(&second, "four\n").unwrap();
+        let file_names = [first, second].map(|path| path.to_str().unwrap().to_string());
+
+        let mut output = Vec::new();
+        let counted_all =
+            count_files(&file_names, &Options::all(), &mut io::empty(), &mut output).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(counted_all);
+        let output = String::from_utf8(output).unwrap();
+        let rows: Vec<&str> = output.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], format!("       2       3      14      14 {}", file_names[0]));
+        assert_eq!(rows[2], "       3       4      19      19 total");
+    }
+
+    #[test]
+    fn test_count_files_parallel() {
+        let dir = unique_temp_dir("parallel");
+        let mut file_names: Vec<String> = (1..=6)
+            .map(|i| {
+                let file_name = dir.join(format!("{}.txt", i));
+                fs::write(&file_name, "word ".repeat(i * 1_000) + "\n").unwrap();
+                file_name.to_str().unwrap().to_string()
+            })
+            .collect();
+        file_names.insert(3, STDIN.to_string());
+
+        let (sequential, _) = parse(&["-lwL"]);
+        let (parallel, _) = parse(&["-lwL", "--parallel"]);
+        assert!(parallel.parallel);
+        let mut sequential_output = Vec::new();
+        let mut parallel_output = Vec::new();
+        let mut stdin = "from stdin\n".as_bytes();
+        count_files(&file_names, &sequential, &mut stdin, &mut sequential_output).unwrap();
+        let mut stdin = "from stdin\n".as_bytes();
+        count_files(&file_names, &parallel, &mut stdin, &mut parallel_output).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(parallel_output, sequential_output);
+        let output = String::from_utf8(parallel_output).unwrap();
+        assert!(output.ends_with("       7   21002   30000 total\n"));
+    }
+
+    #[test]
+    fn test_count_files_parallel_more_files_than_workers() {
+        let dir = unique_temp_dir("parallel-many");
+        let workers = thread::available_parallelism().map_or(1, usize::from);
+        let file_names: Vec<String> = (1..=workers * 4 + 1)
+            .map(|i| {
+                let file_name = dir.join(format!("{}.txt", i));
+                fs::write(&file_name, "line\n".repeat(i)).unwrap();
+                file_name.to_str().unwrap().to_string()
+            })
+            .collect();
+
+        let all_counts = count_files_parallel(&file_names, || unreachable!());
+        fs::remove_dir_all(&dir).unwrap();
+
+        let lines: Vec<usize> =
+            all_counts.into_iter().map(|counts| counts.unwrap().lines).collect();
+        assert_eq!(lines, (1..=file_names.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_split_file_list() {
+        assert!(split_file_list(b"").is_empty());
+        assert_eq!(split_file_list(b"\0"), [""]);
+        assert_eq!(split_file_list(b"a\0b c\0"), ["a", "b c"]);
+        assert_eq!(split_file_list(b"a\0b c"), ["a", "b c"]);
+        assert_eq!(split_file_list(b"a\0\0"), ["a", ""]);
+    }
+
+    #[test]
+    fn test_count_files_from_list() {
+        let dir = unique_temp_dir("files0");
+        let first = dir.join("first.txt");
+        let second = dir.join("second.txt");
+        let list = dir.join("list");
+        fs::write(&first, "one two\n").unwrap();
+        fs::write(&second, "three\n").unwrap();
+        let mut names = Vec::new();
+        for file_name in [&first, &second] {
+            names.extend_from_slice(file_name.to_str().unwrap().as_bytes());
+            names.push(0);
+        }
+        fs::write(&list, names).unwrap();
+
+        let list = list.to_str().unwrap().to_string();
+        let args = ["-w".to_string(), format!("--files0-from={}", list)];
+        let Ok(Action::CountFrom(options, from)) = parse_args(&args) else {
+            panic!("expected counting from a list");
+        };
+        assert_eq!(from, list);
+        let file_names = read_file_list(&from).unwrap();
+        let mut output = Vec::new();
+        assert!(count_files(&file_names, &options, &mut io::empty(), &mut output).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.ends_with("       3 total\n"));
+    }
+
+    #[test]
+    fn test_parse_args_files0_from() {
+        let args = ["--files0-from".to_string(), "-".to_string()];
+        assert!(matches!(parse_args(&args), Ok(Action::CountFrom(_, list)) if list == "-"));
+
+        let args = ["--files0-from=list".to_string(), "extra".to_string()];
+        assert!(parse_args(&args).unwrap_err().starts_with("extra operand 'extra'"));
+        let args = ["--files0-from".to_string()];
+        assert!(parse_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_count_files_stdin() {
+        let (options, file_names) = parse(&["-w", "-"]);
+        assert_eq!(file_names, ["-"]);
+
+        let mut stdin = "piped into wc\n".as_bytes();
+        let mut output = Vec::new();
+        assert!(count_files(&file_names, &options, &mut stdin, &mut output).unwrap());
+        assert_eq!(String::from_utf8(output).unwrap(), "       3 -\n");
+    }
+
+    #[test]
+    fn test_format_row() {
+        let counts = Counts {
+            lines: 1,
+            words: 2,
+            ..Counts::default()
+        };
+        let (options, _) = parse(&["-lw"]);
+        assert_eq!(format_row(&counts, Some("file"), &options), "       1       2 file");
+        assert_eq!(format_row(&counts, None, &options), "       1       2");
+
+        let (options, _) = parse(&["-lw", "--no-filename"]);
+        assert_eq!(format_row(&counts, Some("file"), &options), "       1       2");
+        assert_eq!(parse(&["-lwh"]).0, options);
+    }
+
+    #[test]
+    fn test_count_files_no_filename() {
+        let (options, file_names) = parse(&["-w", "-h", "-", "-"]);
+
+        let mut stdin = "piped into wc\n".as_bytes();
+        let mut output = Vec::new();
+        assert!(count_files(&file_names, &options, &mut stdin, &mut output).unwrap());
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "       3\n       0\n       3\n");
+    }
+
+    #[test]
+    fn test_count_files_total_option() {
+        let file_names = ["-".to_string()];
+        let count = |args: &[&str]| {
+            let (options, _) = parse(args);
+            let mut output = Vec::new();
+            count_files(&file_names, &options, &mut "one two\n".as_bytes(), &mut output).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(count(&["-w"]), "       2 -\n");
+        assert_eq!(count(&["-w", "--total=always"]), "       2 -\n       2 total\n");
+        assert_eq!(count(&["-w", "--total=only"]), "       2\n");
+        assert_eq!(count(&["-w", "--total=never", "--total=auto"]), "       2 -\n");
+
+        let args = ["--total=sometimes".to_string()];
+        assert_eq!(parse_args(&args).unwrap_err(), "invalid argument 'sometimes' for '--total'");
+    }
+
+    #[test]
+    fn test_count_files_missing() {
+        let dir = unique_temp_dir("missing");
+        let file_name = dir.join("present.txt");
+        fs::write(&file_name, "present\n").unwrap();
+        let file_names = [
+            dir.join("missing.txt").to_str().unwrap().to_string(),
+            file_name.to_str().unwrap().to_string(),
+        ];
+
+        let mut output = Vec::new();
+        let options = Options {
+            lines: true,
+            ..Options::default()
+        };
+        let counted_all =
+            count_files(&file_names, &options, &mut io::empty(), &mut output).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!counted_all);
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, format!("       1 {}\n       1 total\n", file_names[1]));
+    }
+
+    fn parse(args: &[&str]) -> (Options, Vec<String>) {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        match parse_args(&args).unwrap() {
+            Action::Count(options, file_names) => (options, file_names),
+            action => panic!("expected counting, got {:?}", action),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_combined_flags() {
+        let counts = Counts {
+            lines: 1,
+            words: 2,
+            chars: 3,
+            bytes: 4,
+            max_line_length: 5,
+        };
+
+        let (options, file_names) = parse(&["-lw", "file"]);
+        assert_eq!(file_names, ["file"]);
+        assert_eq!(format_counts(&counts, &options), "       1       2");
+
+        // columns keep their order, whatever the order of the flags
+        let (options, _) = parse(&["-cl", "file"]);
+        assert_eq!(format_counts(&counts, &options), "       1       4");
+        assert_eq!(parse(&["-c", "-l", "file"]).0, options);
+        assert_eq!(parse(&["-ccl", "file"]).0, options);
+    }
+
+    #[test]
+    fn test_parse_args_default() {
+        let (options, file_names) = parse(&["a", "b"]);
+        assert_eq!(options, Options::all());
+        assert_eq!(file_names, ["a", "b"]);
+
+        let counts = Counts {
+            lines: 1,
+            words: 2,
+            chars: 3,
+            bytes: 4,
+            max_line_length: 5,
+        };
+        assert_eq!(format_counts(&counts, &options), "       1       2       3       4");
+    }
+
+    #[test]
+    fn test_parse_args_invalid_flag() {
+        let args = ["-lx".to_string()];
+        assert_eq!(parse_args(&args).unwrap_err(), "invalid option -- 'x'");
+        let args = ["--lengths".to_string()];
+        assert_eq!(parse_args(&args).unwrap_err(), "unrecognized option '--lengths'");
+    }
+
+    #[test]
+    fn test_parse_args_long_options() {
+        assert_eq!(parse(&["--words", "file"]), parse(&["-w", "file"]));
+        assert_eq!(parse(&["--lines", "--bytes", "file"]), parse(&["-lc", "file"]));
+        assert_eq!(parse(&["--chars", "-l", "file"]), parse(&["-ml", "file"]));
+
+        let (_, file_names) = parse(&["-w", "--", "--lines", "-"]);
+        assert_eq!(file_names, ["--lines", "-"]);
+    }
+
+    #[test]
+    fn test_parse_args_max_line_length() {
+        let (options, _) = parse(&["-L"]);
+        assert!(options.max_line_length);
+        assert_eq!(parse(&["--max-line-length"]), parse(&["-L"]));
+    }
+
+    #[test]
+    fn test_parse_args_help_and_version() {
+        let args = ["-l".to_string(), "--help".to_string()];
+        assert_eq!(parse_args(&args), Ok(Action::Help));
+        let args = ["--version".to_string()];
+        assert_eq!(parse_args(&args), Ok(Action::Version));
+    }
+}